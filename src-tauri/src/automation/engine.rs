@@ -0,0 +1,534 @@
+use super::types::{
+    AutomationError, AutomationResult, AutomationScript, AutomationStep, TranscriptEntry,
+};
+use crate::terminal::{AnsiStripper, ScrollbackReplay};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+/// expect/capture 기본 대기 시간
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+
+/// 새 출력 확인 주기
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// 한 번의 실행에서 처리할 수 있는 최대 단계 수 (goto 무한 루프 방지)
+const MAX_EXECUTED_STEPS: usize = 10_000;
+
+/// 스크립트를 실행할 세션
+///
+/// 출력은 스크롤백 오프셋 기준으로 읽으므로 프론트엔드로 가는 출력 스트림에는 영향을 주지 않습니다.
+pub trait AutomationTarget {
+    fn session_id(&self) -> &str;
+
+    /// from_offset 이후의 출력 읽기
+    fn read(
+        &self,
+        from_offset: u64,
+    ) -> impl Future<Output = Result<ScrollbackReplay, String>> + Send;
+
+    /// 입력 쓰기
+    fn write(&self, data: &str) -> impl Future<Output = Result<(), String>> + Send;
+}
+
+/// 스크립트 실행
+///
+/// 스크립트 검증(패턴, 라벨) 실패는 에러로 반환하고, 실행 중 실패(시간 초과, fail 단계,
+/// 세션 에러)는 그때까지의 트랜스크립트와 함께 결과에 담아 반환합니다.
+pub async fn run_script<T: AutomationTarget>(
+    target: &T,
+    script: &AutomationScript,
+) -> Result<AutomationResult, AutomationError> {
+    let compiled = CompiledScript::compile(script)?;
+
+    let from_offset = if script.include_existing_output {
+        0
+    } else {
+        u64::MAX
+    };
+    let mut runner = Runner {
+        target,
+        started_at: Instant::now(),
+        default_timeout: Duration::from_millis(
+            script.default_timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS),
+        ),
+        output: OutputBuffer::new(from_offset),
+        transcript: Vec::new(),
+        captures: BTreeMap::new(),
+    };
+
+    // 시작 오프셋을 먼저 고정해야 첫 send의 응답을 놓치지 않음
+    let outcome = match runner.output.fill(target).await {
+        Ok(()) => runner.run(&compiled).await,
+        Err(e) => Err(e),
+    };
+
+    Ok(AutomationResult {
+        session_id: target.session_id().to_string(),
+        success: outcome.is_ok(),
+        error: outcome.err(),
+        captures: runner.captures,
+        transcript: runner.transcript,
+        duration_ms: runner.started_at.elapsed().as_millis() as u64,
+    })
+}
+
+/// 패턴과 라벨을 미리 검증한 스크립트
+struct CompiledScript<'a> {
+    steps: &'a [AutomationStep],
+    labels: HashMap<&'a str, usize>,
+    /// 단계별 매처 (expect: 대안 순서, capture: [start, end])
+    matchers: Vec<Vec<Regex>>,
+}
+
+impl<'a> CompiledScript<'a> {
+    fn compile(script: &'a AutomationScript) -> Result<Self, AutomationError> {
+        let mut labels = HashMap::new();
+        for (index, step) in script.steps.iter().enumerate() {
+            if let AutomationStep::Label { name } = step {
+                if labels.insert(name.as_str(), index).is_some() {
+                    return Err(AutomationError::DuplicateLabel(name.clone()));
+                }
+            }
+        }
+
+        let check_label = |label: &Option<String>| match label {
+            Some(label) if !labels.contains_key(label.as_str()) => {
+                Err(AutomationError::UnknownLabel(label.clone()))
+            }
+            _ => Ok(()),
+        };
+
+        let mut matchers = Vec::with_capacity(script.steps.len());
+        for step in &script.steps {
+            let step_matchers = match step {
+                AutomationStep::Expect {
+                    alternatives,
+                    on_timeout,
+                    ..
+                } => {
+                    if alternatives.is_empty() {
+                        return Err(AutomationError::InvalidScript(
+                            "expect requires at least one alternative".to_string(),
+                        ));
+                    }
+                    check_label(on_timeout)?;
+                    alternatives
+                        .iter()
+                        .map(|alternative| {
+                            check_label(&alternative.goto)?;
+                            compile_pattern(&alternative.pattern, alternative.regex)
+                        })
+                        .collect::<Result<Vec<_>, _>>()?
+                }
+                AutomationStep::Capture {
+                    start,
+                    end,
+                    regex,
+                    on_timeout,
+                    ..
+                } => {
+                    check_label(on_timeout)?;
+                    vec![
+                        compile_pattern(start, *regex)?,
+                        compile_pattern(end, *regex)?,
+                    ]
+                }
+                AutomationStep::Goto { label } => {
+                    check_label(&Some(label.clone()))?;
+                    Vec::new()
+                }
+                _ => Vec::new(),
+            };
+            matchers.push(step_matchers);
+        }
+
+        Ok(Self {
+            steps: &script.steps,
+            labels,
+            matchers,
+        })
+    }
+
+    fn label_index(&self, label: &str) -> usize {
+        // compile에서 모든 라벨 참조를 검증함
+        self.labels[label]
+    }
+}
+
+fn compile_pattern(pattern: &str, regex: bool) -> Result<Regex, AutomationError> {
+    let source = if regex {
+        pattern.to_string()
+    } else {
+        regex::escape(pattern)
+    };
+    Regex::new(&source).map_err(|e| AutomationError::InvalidPattern {
+        pattern: pattern.to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// 아직 매칭에 소비되지 않은 세션 출력 (ANSI 제거)
+struct OutputBuffer {
+    /// 다음에 읽을 스트림 오프셋
+    offset: u64,
+    stripper: AnsiStripper,
+    pending: String,
+}
+
+impl OutputBuffer {
+    fn new(offset: u64) -> Self {
+        Self {
+            offset,
+            stripper: AnsiStripper::new(),
+            pending: String::new(),
+        }
+    }
+
+    /// 세션에서 새 출력을 읽어 추가
+    async fn fill<T: AutomationTarget>(&mut self, target: &T) -> Result<(), String> {
+        let replay = target.read(self.offset).await?;
+        self.offset = replay.end_offset;
+        let text = self.stripper.push(replay.data.as_bytes());
+        self.pending.push_str(&text);
+        Ok(())
+    }
+
+    /// 앞에서부터 end 바이트까지 소비하고 반환
+    fn consume(&mut self, end: usize) -> String {
+        self.pending.drain(..end).collect()
+    }
+}
+
+struct Runner<'t, T> {
+    target: &'t T,
+    started_at: Instant,
+    default_timeout: Duration,
+    output: OutputBuffer,
+    transcript: Vec<TranscriptEntry>,
+    captures: BTreeMap<String, String>,
+}
+
+impl<T: AutomationTarget> Runner<'_, T> {
+    async fn run(&mut self, script: &CompiledScript<'_>) -> Result<(), String> {
+        let mut next = 0;
+        let mut executed = 0;
+
+        while let Some(step) = script.steps.get(next) {
+            executed += 1;
+            if executed > MAX_EXECUTED_STEPS {
+                return Err(format!("Step limit exceeded ({})", MAX_EXECUTED_STEPS));
+            }
+
+            let index = next;
+            next += 1;
+
+            match step {
+                AutomationStep::Label { .. } => {}
+                AutomationStep::Send { text } => {
+                    self.target.write(text).await?;
+                    self.transcript.push(TranscriptEntry::Sent {
+                        step: index,
+                        text: text.clone(),
+                        elapsed_ms: self.elapsed_ms(),
+                    });
+                }
+                AutomationStep::Expect {
+                    alternatives,
+                    timeout_ms,
+                    on_timeout,
+                } => {
+                    let matchers = &script.matchers[index];
+                    let timeout = self.timeout(*timeout_ms);
+                    match self
+                        .wait_for(timeout, |text| find_first(matchers, text))
+                        .await?
+                    {
+                        Some((alternative, range)) => {
+                            let mut output = self.output.consume(range.end);
+                            let matched = output.split_off(range.start);
+                            self.transcript.push(TranscriptEntry::Matched {
+                                step: index,
+                                alternative,
+                                pattern: alternatives[alternative].pattern.clone(),
+                                matched,
+                                output,
+                                elapsed_ms: self.elapsed_ms(),
+                            });
+                            if let Some(label) = &alternatives[alternative].goto {
+                                next = self.jump(script, index, label);
+                            }
+                        }
+                        None => next = self.timed_out(script, index, on_timeout)?,
+                    }
+                }
+                AutomationStep::Capture {
+                    name,
+                    timeout_ms,
+                    on_timeout,
+                    ..
+                } => {
+                    let [start, end] = script.matchers[index].as_slice() else {
+                        unreachable!("capture is compiled with start and end matchers");
+                    };
+                    let timeout = self.timeout(*timeout_ms);
+                    match self
+                        .wait_for(timeout, |text| find_capture(start, end, text))
+                        .await?
+                    {
+                        Some((range, consumed)) => {
+                            let text = self.output.pending[range].to_string();
+                            self.output.consume(consumed);
+                            self.captures.insert(name.clone(), text.clone());
+                            self.transcript.push(TranscriptEntry::Captured {
+                                step: index,
+                                name: name.clone(),
+                                text,
+                                elapsed_ms: self.elapsed_ms(),
+                            });
+                        }
+                        None => next = self.timed_out(script, index, on_timeout)?,
+                    }
+                }
+                AutomationStep::Goto { label } => next = self.jump(script, index, label),
+                AutomationStep::Sleep { ms } => {
+                    tokio::time::sleep(Duration::from_millis(*ms)).await;
+                }
+                AutomationStep::Fail { message } => {
+                    self.transcript.push(TranscriptEntry::Failed {
+                        step: index,
+                        message: message.clone(),
+                        elapsed_ms: self.elapsed_ms(),
+                    });
+                    return Err(message.clone());
+                }
+                AutomationStep::End => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 새 출력을 읽으며 find가 결과를 돌려줄 때까지 대기 (시간 초과 시 None)
+    async fn wait_for<R>(
+        &mut self,
+        timeout: Duration,
+        find: impl Fn(&str) -> Option<R>,
+    ) -> Result<Option<R>, String> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            self.output.fill(self.target).await?;
+            if let Some(found) = find(&self.output.pending) {
+                return Ok(Some(found));
+            }
+            if Instant::now() >= deadline {
+                return Ok(None);
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// 시간 초과 기록 후 on_timeout 라벨의 위치 반환 (라벨이 없으면 실패)
+    fn timed_out(
+        &mut self,
+        script: &CompiledScript<'_>,
+        step: usize,
+        on_timeout: &Option<String>,
+    ) -> Result<usize, String> {
+        self.transcript.push(TranscriptEntry::TimedOut {
+            step,
+            output: self.output.pending.clone(),
+            elapsed_ms: self.elapsed_ms(),
+        });
+
+        match on_timeout {
+            Some(label) => Ok(self.jump(script, step, label)),
+            None => Err(format!("Timed out waiting for output at step {}", step)),
+        }
+    }
+
+    fn jump(&mut self, script: &CompiledScript<'_>, step: usize, label: &str) -> usize {
+        self.transcript.push(TranscriptEntry::Jumped {
+            step,
+            label: label.to_string(),
+            elapsed_ms: self.elapsed_ms(),
+        });
+        script.label_index(label)
+    }
+
+    fn timeout(&self, timeout_ms: Option<u64>) -> Duration {
+        timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(self.default_timeout)
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        self.started_at.elapsed().as_millis() as u64
+    }
+}
+
+/// 가장 앞에서 매칭되는 대안 (위치가 같으면 먼저 나열된 대안)
+fn find_first(matchers: &[Regex], text: &str) -> Option<(usize, Range<usize>)> {
+    matchers
+        .iter()
+        .enumerate()
+        .filter_map(|(index, matcher)| matcher.find(text).map(|m| (index, m.range())))
+        .min_by_key(|(index, range)| (range.start, *index))
+}
+
+/// start 마커와 그 뒤의 end 마커 사이 범위, 그리고 end 마커까지의 길이
+fn find_capture(start: &Regex, end: &Regex, text: &str) -> Option<(Range<usize>, usize)> {
+    let start = start.find(text)?;
+    let end = end.find_at(text, start.end())?;
+    Some((start.end()..end.start(), end.end()))
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// 입력에 정해진 응답을 출력하는 가짜 세션
+    struct MockTarget {
+        output: Mutex<Vec<u8>>,
+        replies: Vec<(&'static str, &'static str)>,
+    }
+
+    impl MockTarget {
+        fn new(initial: &str, replies: Vec<(&'static str, &'static str)>) -> Self {
+            Self {
+                output: Mutex::new(initial.as_bytes().to_vec()),
+                replies,
+            }
+        }
+    }
+
+    impl AutomationTarget for MockTarget {
+        fn session_id(&self) -> &str {
+            "mock"
+        }
+
+        async fn read(&self, from_offset: u64) -> Result<ScrollbackReplay, String> {
+            let output = self.output.lock().unwrap();
+            let start = (from_offset as usize).min(output.len());
+            Ok(ScrollbackReplay {
+                data: String::from_utf8_lossy(&output[start..]).into_owned(),
+                start_offset: start as u64,
+                end_offset: output.len() as u64,
+                next_seq: 0,
+                truncated: false,
+            })
+        }
+
+        async fn write(&self, data: &str) -> Result<(), String> {
+            if let Some((_, reply)) = self.replies.iter().find(|(input, _)| *input == data) {
+                self.output
+                    .lock()
+                    .unwrap()
+                    .extend_from_slice(reply.as_bytes());
+            }
+            Ok(())
+        }
+    }
+
+    fn script(json: &str) -> AutomationScript {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_expect_branch_and_capture() {
+        let target = MockTarget::new(
+            "\x1b[1mUsername:\x1b[0m ",
+            vec![
+                ("admin\r", "Password: "),
+                ("secret\r", "\r\nswitch# "),
+                ("show version\r", "show version\r\nVersion 1.2\r\nswitch# "),
+            ],
+        );
+        let script = script(
+            r#"{
+                "includeExistingOutput": true,
+                "steps": [
+                    {"type": "expect", "alternatives": [{"pattern": "Username:"}]},
+                    {"type": "send", "text": "admin\r"},
+                    {"type": "expect", "alternatives": [
+                        {"pattern": "denied", "goto": "failed"},
+                        {"pattern": "Pass(word)?:", "regex": true}
+                    ]},
+                    {"type": "send", "text": "secret\r"},
+                    {"type": "expect", "alternatives": [{"pattern": "switch# "}]},
+                    {"type": "send", "text": "show version\r"},
+                    {"type": "capture", "name": "version", "start": "show version\n", "end": "switch# "},
+                    {"type": "end"},
+                    {"type": "label", "name": "failed"},
+                    {"type": "fail", "message": "login denied"}
+                ]
+            }"#,
+        );
+
+        let result = run_script(&target, &script).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.captures["version"], "Version 1.2\n");
+        assert!(matches!(
+            &result.transcript[2],
+            TranscriptEntry::Matched { alternative: 1, matched, .. } if matched == "Password:"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_jumps_or_fails() {
+        let target = MockTarget::new("", vec![]);
+        let script = script(
+            r#"{
+                "defaultTimeoutMs": 30,
+                "steps": [
+                    {"type": "expect", "alternatives": [{"pattern": "ready"}], "onTimeout": "retry"},
+                    {"type": "end"},
+                    {"type": "label", "name": "retry"},
+                    {"type": "expect", "alternatives": [{"pattern": "ready"}]}
+                ]
+            }"#,
+        );
+
+        let result = run_script(&target, &script).await.unwrap();
+        assert!(!result.success);
+        let timeouts = result
+            .transcript
+            .iter()
+            .filter(|entry| matches!(entry, TranscriptEntry::TimedOut { .. }))
+            .count();
+        assert_eq!(timeouts, 2);
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        let unknown = script(r#"{"steps": [{"type": "goto", "label": "missing"}]}"#);
+        assert!(matches!(
+            CompiledScript::compile(&unknown),
+            Err(AutomationError::UnknownLabel(_))
+        ));
+
+        let invalid = script(
+            r#"{"steps": [{"type": "expect", "alternatives": [{"pattern": "(", "regex": true}]}]}"#,
+        );
+        assert!(matches!(
+            CompiledScript::compile(&invalid),
+            Err(AutomationError::InvalidPattern { .. })
+        ));
+    }
+
+    #[test]
+    fn test_find_first_prefers_earliest() {
+        let matchers = vec![Regex::new("prompt").unwrap(), Regex::new("error").unwrap()];
+        assert_eq!(
+            find_first(&matchers, "error before prompt"),
+            Some((1, 0..5))
+        );
+    }
+}
@@ -0,0 +1,7 @@
+mod engine;
+mod session;
+mod types;
+
+pub use engine::{run_script, AutomationTarget};
+pub use session::SessionTarget;
+pub use types::{AutomationResult, AutomationScript};
@@ -0,0 +1,63 @@
+use super::engine::AutomationTarget;
+use crate::pty::{PtyError, PtyManager};
+use crate::ssh::SshManager;
+use crate::terminal::ScrollbackReplay;
+
+/// PTY 또는 SSH 세션 대상 (PTY 세션을 먼저 찾고, 없으면 SSH 세션 사용)
+pub struct SessionTarget<'a> {
+    session_id: String,
+    pty_manager: &'a PtyManager,
+    ssh_manager: &'a SshManager,
+}
+
+impl<'a> SessionTarget<'a> {
+    pub fn new(
+        session_id: String,
+        pty_manager: &'a PtyManager,
+        ssh_manager: &'a SshManager,
+    ) -> Self {
+        Self {
+            session_id,
+            pty_manager,
+            ssh_manager,
+        }
+    }
+}
+
+impl AutomationTarget for SessionTarget<'_> {
+    fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    async fn read(&self, from_offset: u64) -> Result<ScrollbackReplay, String> {
+        match self
+            .pty_manager
+            .replay_session(&self.session_id, from_offset)
+            .await
+        {
+            Err(PtyError::SessionNotFound(_)) => {}
+            result => return result.map_err(|e| e.to_string()),
+        }
+
+        self.ssh_manager
+            .replay_session(&self.session_id, from_offset)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn write(&self, data: &str) -> Result<(), String> {
+        match self
+            .pty_manager
+            .write_to_session(&self.session_id, data)
+            .await
+        {
+            Err(PtyError::SessionNotFound(_)) => {}
+            result => return result.map_err(|e| e.to_string()),
+        }
+
+        self.ssh_manager
+            .write_to_session(&self.session_id, data)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
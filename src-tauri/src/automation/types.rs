@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// 자동화 스크립트
+///
+/// 단계는 순서대로 실행되며, expect의 대안별 `goto`나 `goto` 단계로 라벨에 분기할 수 있습니다.
+/// 출력은 ANSI 시퀀스와 `\r`을 제거한 텍스트로 매칭합니다.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationScript {
+    pub steps: Vec<AutomationStep>,
+    /// expect/capture의 기본 대기 시간 (생략 시 10초)
+    pub default_timeout_ms: Option<u64>,
+    /// 이미 출력된 내용도 매칭 대상에 포함 (기본값: 실행 시작 이후 출력만 매칭)
+    #[serde(default)]
+    pub include_existing_output: bool,
+}
+
+/// 스크립트 단계
+#[derive(Debug, Clone, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum AutomationStep {
+    /// 분기 대상 라벨 (실행 시 아무것도 하지 않음)
+    Label { name: String },
+    /// 입력 전송 (`\r` 등 제어 문자 포함 가능)
+    Send { text: String },
+    /// 대안 중 하나가 출력에 나타날 때까지 대기
+    ///
+    /// 가장 먼저 나타난 대안이 선택되며, 그 대안의 `goto` 라벨로 분기합니다.
+    /// 시간 초과 시 `on_timeout` 라벨로 분기하고, 없으면 스크립트가 실패합니다.
+    Expect {
+        alternatives: Vec<ExpectAlternative>,
+        timeout_ms: Option<u64>,
+        on_timeout: Option<String>,
+    },
+    /// start와 end 마커 사이의 출력을 name으로 수집
+    Capture {
+        name: String,
+        start: String,
+        end: String,
+        #[serde(default)]
+        regex: bool,
+        timeout_ms: Option<u64>,
+        on_timeout: Option<String>,
+    },
+    /// 라벨로 이동
+    Goto { label: String },
+    /// 지정한 시간 동안 대기
+    Sleep { ms: u64 },
+    /// 스크립트를 실패로 종료
+    Fail { message: String },
+    /// 스크립트를 성공으로 종료
+    End,
+}
+
+/// expect 대안
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpectAlternative {
+    pub pattern: String,
+    /// true면 정규식, false면 일반 텍스트로 매칭
+    #[serde(default)]
+    pub regex: bool,
+    /// 매칭 시 이동할 라벨 (생략 시 다음 단계)
+    pub goto: Option<String>,
+}
+
+/// 트랜스크립트 항목 (실행한 단계별 기록)
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TranscriptEntry {
+    Sent {
+        step: usize,
+        text: String,
+        elapsed_ms: u64,
+    },
+    Matched {
+        step: usize,
+        /// 선택된 대안의 인덱스
+        alternative: usize,
+        pattern: String,
+        /// 매칭된 텍스트
+        matched: String,
+        /// 이전 매칭 이후 매칭 직전까지의 출력 (ANSI 제거)
+        output: String,
+        elapsed_ms: u64,
+    },
+    Captured {
+        step: usize,
+        name: String,
+        text: String,
+        elapsed_ms: u64,
+    },
+    TimedOut {
+        step: usize,
+        /// 시간 초과 시점까지 매칭되지 않은 출력 (ANSI 제거)
+        output: String,
+        elapsed_ms: u64,
+    },
+    Jumped {
+        step: usize,
+        label: String,
+        elapsed_ms: u64,
+    },
+    Failed {
+        step: usize,
+        message: String,
+        elapsed_ms: u64,
+    },
+}
+
+/// 스크립트 실행 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct AutomationResult {
+    pub session_id: String,
+    pub success: bool,
+    /// 실패 사유 (시간 초과, fail 단계, 세션 에러 등)
+    pub error: Option<String>,
+    /// capture 단계로 수집한 출력
+    pub captures: BTreeMap<String, String>,
+    pub transcript: Vec<TranscriptEntry>,
+    pub duration_ms: u64,
+}
+
+/// 자동화 에러 타입 (스크립트 실행 전 검증 실패)
+#[derive(Debug, Error)]
+pub enum AutomationError {
+    #[error("Invalid pattern '{pattern}': {message}")]
+    InvalidPattern { pattern: String, message: String },
+
+    #[error("Unknown label: {0}")]
+    UnknownLabel(String),
+
+    #[error("Duplicate label: {0}")]
+    DuplicateLabel(String),
+
+    #[error("Invalid script: {0}")]
+    InvalidScript(String),
+}
+
+impl From<AutomationError> for String {
+    fn from(err: AutomationError) -> Self {
+        err.to_string()
+    }
+}
@@ -0,0 +1,123 @@
+use super::types::{BroadcastError, BroadcastGroupInfo, BroadcastMember};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// 브로드캐스트 그룹 (멤버는 추가한 순서대로 입력을 받음)
+pub struct BroadcastGroup {
+    group_id: String,
+    name: Option<String>,
+    members: Vec<BroadcastMember>,
+    /// 그룹 쓰기 순서 보장용 잠금 (여러 쓰기가 멤버 사이에서 섞이지 않도록)
+    write_lock: Arc<Mutex<()>>,
+}
+
+impl BroadcastGroup {
+    /// 새 그룹 생성
+    pub fn new(group_id: String, name: Option<String>) -> Self {
+        Self {
+            group_id,
+            name,
+            members: Vec::new(),
+            write_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// 멤버 추가 (이미 있는 세션은 무시)
+    pub fn add(&mut self, session_ids: &[String]) {
+        for session_id in session_ids {
+            if !self.contains(session_id) {
+                self.members.push(BroadcastMember {
+                    session_id: session_id.clone(),
+                    paused: false,
+                });
+            }
+        }
+    }
+
+    /// 멤버 제거 (없는 세션은 무시)
+    pub fn remove(&mut self, session_ids: &[String]) {
+        self.members
+            .retain(|member| !session_ids.contains(&member.session_id));
+    }
+
+    /// 멤버 일시 정지/재개
+    pub fn set_paused(&mut self, session_id: &str, paused: bool) -> Result<(), BroadcastError> {
+        let member = self
+            .members
+            .iter_mut()
+            .find(|member| member.session_id == session_id)
+            .ok_or_else(|| BroadcastError::MemberNotFound(session_id.to_string()))?;
+        member.paused = paused;
+        Ok(())
+    }
+
+    /// 멤버 여부
+    pub fn contains(&self, session_id: &str) -> bool {
+        self.members
+            .iter()
+            .any(|member| member.session_id == session_id)
+    }
+
+    /// 멤버 목록 (추가한 순서)
+    pub fn members(&self) -> &[BroadcastMember] {
+        &self.members
+    }
+
+    /// 그룹 쓰기 잠금
+    pub fn write_lock(&self) -> Arc<Mutex<()>> {
+        Arc::clone(&self.write_lock)
+    }
+
+    /// 그룹 정보
+    pub fn info(&self) -> BroadcastGroupInfo {
+        BroadcastGroupInfo {
+            group_id: self.group_id.clone(),
+            name: self.name.clone(),
+            members: self.members.clone(),
+        }
+    }
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_membership() {
+        let mut group = BroadcastGroup::new("g".to_string(), None);
+        group.add(&ids(&["a", "b", "a", "c"]));
+        assert_eq!(group.members().len(), 3);
+
+        group.remove(&ids(&["b", "x"]));
+        let members: Vec<&str> = group
+            .members()
+            .iter()
+            .map(|m| m.session_id.as_str())
+            .collect();
+        assert_eq!(members, ["a", "c"]);
+    }
+
+    #[test]
+    fn test_pause_member() {
+        let mut group = BroadcastGroup::new("g".to_string(), Some("web".to_string()));
+        group.add(&ids(&["a", "b"]));
+        group.set_paused("b", true).unwrap();
+        assert!(group.members()[1].paused);
+        assert!(!group.members()[0].paused);
+
+        group.set_paused("b", false).unwrap();
+        assert!(!group.members()[1].paused);
+        assert!(matches!(
+            group.set_paused("x", true),
+            Err(BroadcastError::MemberNotFound(_))
+        ));
+    }
+}
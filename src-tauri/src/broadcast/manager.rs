@@ -0,0 +1,232 @@
+use super::group::BroadcastGroup;
+use super::types::{
+    BroadcastError, BroadcastGroupInfo, BroadcastWriteFailure, BroadcastWriteResult,
+};
+use crate::pty::{self, PtyError, PtyManager};
+use crate::ssh::{self, SshManager};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+/// 멤버가 입력을 받아들일 때까지 기다리는 최대 시간
+///
+/// 넘으면 실패로 보고하지만, 이미 넣은 입력은 세션 입력 대기열에 남아 나중에 전달됩니다.
+const MEMBER_WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 브로드캐스트(동기 입력) 그룹 관리자
+///
+/// 그룹에 쓴 입력은 일시 정지되지 않은 모든 멤버 세션(PTY/SSH)에 전달됩니다.
+/// 같은 그룹의 쓰기는 그룹별 잠금으로 순서대로 수행되므로 여러 쓰기가 멤버 사이에서 섞이지 않고,
+/// 그룹 목록 잠금은 멤버를 확인하는 동안만 잡으므로 멈춘 멤버가 그룹 관리나 다른 그룹을 막지 않습니다.
+pub struct BroadcastManager {
+    groups: Arc<Mutex<HashMap<String, BroadcastGroup>>>,
+}
+
+impl BroadcastManager {
+    /// 새 Broadcast Manager 생성
+    pub fn new() -> Self {
+        BroadcastManager {
+            groups: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// 그룹 생성
+    pub async fn create_group(
+        &self,
+        name: Option<String>,
+        session_ids: &[String],
+    ) -> BroadcastGroupInfo {
+        let group_id = Uuid::new_v4().to_string();
+        let mut group = BroadcastGroup::new(group_id.clone(), name);
+        group.add(session_ids);
+
+        let info = group.info();
+        self.groups.lock().await.insert(group_id, group);
+        info
+    }
+
+    /// 그룹 삭제
+    pub async fn delete_group(&self, group_id: &str) -> Result<(), BroadcastError> {
+        self.groups
+            .lock()
+            .await
+            .remove(group_id)
+            .map(|_| ())
+            .ok_or_else(|| BroadcastError::GroupNotFound(group_id.to_string()))
+    }
+
+    /// 그룹 목록
+    pub async fn list_groups(&self) -> Vec<BroadcastGroupInfo> {
+        self.groups
+            .lock()
+            .await
+            .values()
+            .map(BroadcastGroup::info)
+            .collect()
+    }
+
+    /// 멤버 추가
+    pub async fn add_members(
+        &self,
+        group_id: &str,
+        session_ids: &[String],
+    ) -> Result<BroadcastGroupInfo, BroadcastError> {
+        self.with_group(group_id, |group| {
+            group.add(session_ids);
+            Ok(group.info())
+        })
+        .await
+    }
+
+    /// 멤버 제거
+    pub async fn remove_members(
+        &self,
+        group_id: &str,
+        session_ids: &[String],
+    ) -> Result<BroadcastGroupInfo, BroadcastError> {
+        self.with_group(group_id, |group| {
+            group.remove(session_ids);
+            Ok(group.info())
+        })
+        .await
+    }
+
+    /// 멤버 일시 정지/재개 (그룹에 남겨둔 채 입력만 받지 않음)
+    pub async fn set_member_paused(
+        &self,
+        group_id: &str,
+        session_id: &str,
+        paused: bool,
+    ) -> Result<BroadcastGroupInfo, BroadcastError> {
+        self.with_group(group_id, |group| {
+            group.set_paused(session_id, paused)?;
+            Ok(group.info())
+        })
+        .await
+    }
+
+    /// 세션 종료 시 모든 그룹에서 제거
+    pub async fn remove_session(&self, session_id: &str) {
+        let session_ids = [session_id.to_string()];
+        for group in self.groups.lock().await.values_mut() {
+            group.remove(&session_ids);
+        }
+    }
+
+    /// 그룹의 활성 멤버에게 입력 전달
+    ///
+    /// 모든 멤버에 입력을 넣은 뒤 함께 기다리며, 멤버별 실패(세션 종료, 시간 초과 등)는
+    /// 다른 멤버 전달을 막지 않고 결과에 담아 반환합니다.
+    pub async fn write(
+        &self,
+        group_id: &str,
+        data: &str,
+        pty_manager: &PtyManager,
+        ssh_manager: &SshManager,
+    ) -> Result<BroadcastWriteResult, BroadcastError> {
+        let write_lock = self
+            .with_group(group_id, |group| Ok(group.write_lock()))
+            .await?;
+        let _guard = write_lock.lock().await;
+
+        // 앞선 쓰기를 기다리는 동안 바뀐 멤버와 일시 정지 상태를 반영
+        let members = self
+            .with_group(group_id, |group| Ok(group.members().to_vec()))
+            .await?;
+
+        let mut result = BroadcastWriteResult {
+            written: Vec::new(),
+            skipped: Vec::new(),
+            failed: Vec::new(),
+        };
+
+        let mut pending = Vec::new();
+        for member in members {
+            if member.paused {
+                result.skipped.push(member.session_id);
+                continue;
+            }
+
+            match queue_write(&member.session_id, data, pty_manager, ssh_manager).await {
+                Ok(write) => pending.push((member.session_id, write)),
+                Err(error) => result.failed.push(BroadcastWriteFailure {
+                    session_id: member.session_id,
+                    error,
+                }),
+            }
+        }
+
+        let deadline = Instant::now() + MEMBER_WRITE_TIMEOUT;
+        for (session_id, write) in pending {
+            let error = match tokio::time::timeout_at(deadline, write.wait()).await {
+                Ok(Ok(())) => {
+                    result.written.push(session_id);
+                    continue;
+                }
+                Ok(Err(error)) => error,
+                Err(_) => "Timed out waiting for the session to accept input".to_string(),
+            };
+            result
+                .failed
+                .push(BroadcastWriteFailure { session_id, error });
+        }
+
+        Ok(result)
+    }
+
+    async fn with_group<T>(
+        &self,
+        group_id: &str,
+        f: impl FnOnce(&mut BroadcastGroup) -> Result<T, BroadcastError>,
+    ) -> Result<T, BroadcastError> {
+        let mut groups = self.groups.lock().await;
+        let group = groups
+            .get_mut(group_id)
+            .ok_or_else(|| BroadcastError::GroupNotFound(group_id.to_string()))?;
+        f(group)
+    }
+}
+
+/// 멤버 세션에 넣은 쓰기 요청
+enum MemberWrite {
+    Pty(pty::WriteDone),
+    Ssh(ssh::WriteDone),
+}
+
+impl MemberWrite {
+    /// 세션이 입력을 받아들일 때까지 대기
+    async fn wait(self) -> Result<(), String> {
+        match self {
+            MemberWrite::Pty(done) => pty::wait_write(done).await.map_err(|e| e.to_string()),
+            MemberWrite::Ssh(done) => ssh::wait_write(done).await.map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// PTY 세션에 먼저 쓰기 요청을 넣고, 없으면 SSH 세션에 넣음
+async fn queue_write(
+    session_id: &str,
+    data: &str,
+    pty_manager: &PtyManager,
+    ssh_manager: &SshManager,
+) -> Result<MemberWrite, String> {
+    match pty_manager.queue_write(session_id, data).await {
+        Err(PtyError::SessionNotFound(_)) => {}
+        result => return result.map(MemberWrite::Pty).map_err(|e| e.to_string()),
+    }
+
+    ssh_manager
+        .queue_write(session_id, data)
+        .await
+        .map(MemberWrite::Ssh)
+        .map_err(|e| e.to_string())
+}
+
+impl Default for BroadcastManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
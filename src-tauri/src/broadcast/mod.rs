@@ -0,0 +1,6 @@
+mod group;
+mod manager;
+mod types;
+
+pub use manager::BroadcastManager;
+pub use types::{BroadcastGroupInfo, BroadcastWriteResult};
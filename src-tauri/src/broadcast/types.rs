@@ -0,0 +1,52 @@
+use serde::Serialize;
+use thiserror::Error;
+
+/// 브로드캐스트 그룹 멤버
+#[derive(Debug, Clone, Serialize)]
+pub struct BroadcastMember {
+    /// PTY 또는 SSH 세션 ID
+    pub session_id: String,
+    /// 일시 정지된 멤버는 그룹 입력을 받지 않음
+    pub paused: bool,
+}
+
+/// 브로드캐스트 그룹 정보
+#[derive(Debug, Clone, Serialize)]
+pub struct BroadcastGroupInfo {
+    pub group_id: String,
+    pub name: Option<String>,
+    pub members: Vec<BroadcastMember>,
+}
+
+/// 멤버별 쓰기 실패
+#[derive(Debug, Clone, Serialize)]
+pub struct BroadcastWriteFailure {
+    pub session_id: String,
+    pub error: String,
+}
+
+/// 그룹 쓰기 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct BroadcastWriteResult {
+    /// 입력을 보낸 세션 ID
+    pub written: Vec<String>,
+    /// 일시 정지되어 건너뛴 세션 ID
+    pub skipped: Vec<String>,
+    pub failed: Vec<BroadcastWriteFailure>,
+}
+
+/// 브로드캐스트 에러 타입
+#[derive(Debug, Error)]
+pub enum BroadcastError {
+    #[error("Broadcast group not found: {0}")]
+    GroupNotFound(String),
+
+    #[error("Session is not a member of the group: {0}")]
+    MemberNotFound(String),
+}
+
+impl From<BroadcastError> for String {
+    fn from(err: BroadcastError) -> Self {
+        err.to_string()
+    }
+}
@@ -0,0 +1,20 @@
+use crate::automation::{self, AutomationResult, AutomationScript, SessionTarget};
+use crate::pty::PtyManager;
+use crate::ssh::SshManager;
+use tauri::State;
+
+/// 자동화 스크립트 실행 커맨드 (PTY/SSH 세션)
+///
+/// 스크립트가 끝날 때까지 대기한 뒤 트랜스크립트와 수집한 출력을 반환합니다.
+#[tauri::command]
+pub async fn run_automation_script(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+    script: AutomationScript,
+) -> Result<AutomationResult, String> {
+    let target = SessionTarget::new(session_id, &pty_manager, &ssh_manager);
+    automation::run_script(&target, &script)
+        .await
+        .map_err(|e| e.to_string())
+}
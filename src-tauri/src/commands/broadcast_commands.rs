@@ -0,0 +1,95 @@
+use crate::broadcast::{BroadcastGroupInfo, BroadcastManager, BroadcastWriteResult};
+use crate::pty::PtyManager;
+use crate::ssh::SshManager;
+use tauri::State;
+
+/// 브로드캐스트 그룹 생성 커맨드
+#[tauri::command]
+pub async fn create_broadcast_group(
+    state: State<'_, BroadcastManager>,
+    name: Option<String>,
+    session_ids: Option<Vec<String>>,
+) -> Result<BroadcastGroupInfo, String> {
+    Ok(state
+        .create_group(name, &session_ids.unwrap_or_default())
+        .await)
+}
+
+/// 브로드캐스트 그룹 삭제 커맨드
+#[tauri::command]
+pub async fn delete_broadcast_group(
+    state: State<'_, BroadcastManager>,
+    group_id: String,
+) -> Result<(), String> {
+    state
+        .delete_group(&group_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 브로드캐스트 그룹 목록 커맨드
+#[tauri::command]
+pub async fn list_broadcast_groups(
+    state: State<'_, BroadcastManager>,
+) -> Result<Vec<BroadcastGroupInfo>, String> {
+    Ok(state.list_groups().await)
+}
+
+/// 브로드캐스트 그룹 멤버 추가 커맨드 (PTY/SSH 세션 ID)
+#[tauri::command]
+pub async fn add_broadcast_members(
+    state: State<'_, BroadcastManager>,
+    group_id: String,
+    session_ids: Vec<String>,
+) -> Result<BroadcastGroupInfo, String> {
+    state
+        .add_members(&group_id, &session_ids)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 브로드캐스트 그룹 멤버 제거 커맨드
+#[tauri::command]
+pub async fn remove_broadcast_members(
+    state: State<'_, BroadcastManager>,
+    group_id: String,
+    session_ids: Vec<String>,
+) -> Result<BroadcastGroupInfo, String> {
+    state
+        .remove_members(&group_id, &session_ids)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 브로드캐스트 멤버 일시 정지/재개 커맨드
+///
+/// 일시 정지된 멤버는 그룹에 남아 있지만 그룹 입력을 받지 않습니다.
+#[tauri::command]
+pub async fn set_broadcast_member_paused(
+    state: State<'_, BroadcastManager>,
+    group_id: String,
+    session_id: String,
+    paused: bool,
+) -> Result<BroadcastGroupInfo, String> {
+    state
+        .set_member_paused(&group_id, &session_id, paused)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 브로드캐스트 그룹 입력 커맨드
+///
+/// 일시 정지되지 않은 모든 멤버 세션에 같은 입력을 보내고 멤버별 결과를 반환합니다.
+#[tauri::command]
+pub async fn write_to_broadcast_group(
+    state: State<'_, BroadcastManager>,
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    group_id: String,
+    data: String,
+) -> Result<BroadcastWriteResult, String> {
+    state
+        .write(&group_id, &data, &pty_manager, &ssh_manager)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,158 @@
+use crate::host::{
+    HostAttachResponse, HostClient, HostCommand, HostCreateParams, HostResizeParams,
+    HostSessionInfo, HostStatus, HostWriteParams,
+};
+use crate::ssh::SshConfig;
+use std::collections::HashMap;
+use tauri::ipc::JavaScriptChannelId;
+use tauri::{State, Webview};
+
+/// 세션 호스트 시작 커맨드 (이미 실행 중이면 상태만 반환)
+///
+/// 세션 호스트는 앱과 별개의 백그라운드 프로세스로 PTY와 SSH 세션을 소유하므로
+/// 앱을 재시작하거나 업그레이드해도 작업이 계속 실행됩니다.
+/// 호스트는 앱의 IPC 소켓에 연결해 등록하며, 앱이 다시 실행되면 자동으로 다시 연결합니다.
+#[tauri::command]
+pub async fn start_session_host(state: State<'_, HostClient>) -> Result<HostStatus, String> {
+    state.start().await.map_err(|e| e.to_string())
+}
+
+/// 세션 호스트 상태 조회 커맨드 (실행 중이 아니면 null)
+#[tauri::command]
+pub async fn get_session_host_status(
+    state: State<'_, HostClient>,
+) -> Result<Option<HostStatus>, String> {
+    Ok(state.status().await)
+}
+
+/// 세션 호스트 종료 커맨드 (호스트의 모든 세션도 종료)
+#[tauri::command]
+pub async fn stop_session_host(state: State<'_, HostClient>) -> Result<(), String> {
+    state
+        .request::<()>(HostCommand::Shutdown)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 호스트에 PTY 세션 생성 커맨드
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_host_session(
+    state: State<'_, HostClient>,
+    shell: Option<String>,
+    args: Option<Vec<String>>,
+    cwd: Option<String>,
+    env: Option<HashMap<String, String>>,
+    cols: u16,
+    rows: u16,
+) -> Result<HostSessionInfo, String> {
+    let command = HostCommand::CreateSession {
+        params: HostCreateParams {
+            shell,
+            args,
+            cwd,
+            env,
+            cols,
+            rows,
+        },
+    };
+    state.request(command).await.map_err(|e| e.to_string())
+}
+
+/// 세션 호스트에 SSH 세션 생성 커맨드
+///
+/// 호스트가 연결을 소유하며 스크롤백, 녹화, 세션 로그, 입력 감사, 트리거도 호스트에서 동작합니다.
+/// 세션 ID로 기존 SSH/세션 커맨드(write_to_ssh, ack_output, search_session 등)를 그대로 쓸 수 있고,
+/// 출력은 attach_host_session으로 받습니다.
+#[tauri::command]
+pub async fn create_host_ssh_session(
+    state: State<'_, HostClient>,
+    config: SshConfig,
+    cols: u16,
+    rows: u16,
+) -> Result<HostSessionInfo, String> {
+    state
+        .create_ssh_session(config, cols, rows)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 호스트의 세션 목록 커맨드 (앱 재시작 후 다시 attach할 세션 확인용)
+#[tauri::command]
+pub async fn list_host_sessions(
+    state: State<'_, HostClient>,
+) -> Result<Vec<HostSessionInfo>, String> {
+    state
+        .request(HostCommand::ListSessions)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 attach 커맨드
+///
+/// 응답에 보관 중인 출력이 원시 바이트의 base64로 담기고, 이후 출력은 on_output 채널로
+/// 원시 바이트(ArrayBuffer)가 전달됩니다. SSH 세션은 n번째 메시지의 seq가 next_seq + n입니다.
+/// 종료는 PTY 세션이면 `pty-exit-{session_id}`, SSH 세션이면 `ssh://exit/{session_id}` 이벤트로 알립니다.
+#[tauri::command]
+pub async fn attach_host_session(
+    state: State<'_, HostClient>,
+    webview: Webview,
+    session_id: String,
+    from_offset: Option<u64>,
+    on_output: JavaScriptChannelId,
+) -> Result<HostAttachResponse, String> {
+    let output_channel = on_output.channel_on(webview);
+    state
+        .attach(&session_id, from_offset, output_channel)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 detach 커맨드 (세션은 호스트에서 계속 실행)
+#[tauri::command]
+pub async fn detach_host_session(
+    state: State<'_, HostClient>,
+    session_id: String,
+) -> Result<(), String> {
+    state.detach(&session_id).await.map_err(|e| e.to_string())
+}
+
+/// 세션 호스트 세션 입력 커맨드
+#[tauri::command]
+pub async fn write_to_host_session(
+    state: State<'_, HostClient>,
+    session_id: String,
+    data: String,
+) -> Result<(), String> {
+    let command = HostCommand::Write {
+        params: HostWriteParams { session_id, data },
+    };
+    state.request(command).await.map_err(|e| e.to_string())
+}
+
+/// 세션 호스트 세션 크기 조정 커맨드
+#[tauri::command]
+pub async fn resize_host_session(
+    state: State<'_, HostClient>,
+    session_id: String,
+    cols: u16,
+    rows: u16,
+) -> Result<(), String> {
+    let command = HostCommand::Resize {
+        params: HostResizeParams {
+            session_id,
+            cols,
+            rows,
+        },
+    };
+    state.request(command).await.map_err(|e| e.to_string())
+}
+
+/// 세션 호스트 세션 종료 커맨드
+#[tauri::command]
+pub async fn kill_host_session(
+    state: State<'_, HostClient>,
+    session_id: String,
+) -> Result<(), String> {
+    state.kill(&session_id).await.map_err(|e| e.to_string())
+}
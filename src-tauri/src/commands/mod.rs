@@ -1,6 +1,11 @@
+pub mod automation_commands;
+pub mod broadcast_commands;
 pub mod fs_commands;
+pub mod host_commands;
 pub mod keyring_commands;
+pub mod playback_commands;
 pub mod pty_commands;
+pub mod session_commands;
 pub mod settings_commands;
 pub mod sftp_commands;
 pub mod ssh_commands;
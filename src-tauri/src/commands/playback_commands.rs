@@ -0,0 +1,98 @@
+use crate::playback::{CreatePlaybackResponse, PlaybackManager, PlaybackStatus};
+use tauri::ipc::JavaScriptChannelId;
+use tauri::{AppHandle, State, Webview};
+
+/// 녹화 파일(.cast) 재생 세션 생성 커맨드
+///
+/// 출력은 create_pty와 같이 on_output 채널로 원시 바이트(ArrayBuffer)가 전달되고
+/// (채널이 없으면 `pty-output-{playback_id}` 이벤트), 끝나면 `pty-exit-{playback_id}` 이벤트가 발행되므로
+/// 기존 터미널 컴포넌트에 playback_id를 pty_id로 넘기면 됩니다.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_playback(
+    state: State<'_, PlaybackManager>,
+    app_handle: AppHandle,
+    webview: Webview,
+    path: String,
+    speed: Option<f64>,
+    idle_time_limit: Option<f64>,
+    autoplay: Option<bool>,
+    on_output: Option<JavaScriptChannelId>,
+) -> Result<CreatePlaybackResponse, String> {
+    state
+        .create_session(
+            &path,
+            speed.unwrap_or(1.0),
+            idle_time_limit,
+            autoplay.unwrap_or(true),
+            on_output.map(|id| id.channel_on(webview)),
+            app_handle,
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 재생 상태 조회 커맨드
+#[tauri::command]
+pub async fn get_playback_status(
+    state: State<'_, PlaybackManager>,
+    playback_id: String,
+) -> Result<PlaybackStatus, String> {
+    state.status(&playback_id).await.map_err(|e| e.to_string())
+}
+
+/// 재생 일시정지 커맨드
+#[tauri::command]
+pub async fn pause_playback(
+    state: State<'_, PlaybackManager>,
+    playback_id: String,
+) -> Result<(), String> {
+    state.pause(&playback_id).await.map_err(|e| e.to_string())
+}
+
+/// 재생 재개 커맨드
+#[tauri::command]
+pub async fn resume_playback(
+    state: State<'_, PlaybackManager>,
+    playback_id: String,
+) -> Result<(), String> {
+    state.resume(&playback_id).await.map_err(|e| e.to_string())
+}
+
+/// 재생 위치 이동 커맨드 (초)
+#[tauri::command]
+pub async fn seek_playback(
+    state: State<'_, PlaybackManager>,
+    playback_id: String,
+    position: f64,
+) -> Result<(), String> {
+    state
+        .seek(&playback_id, position)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 재생 속도 배율 변경 커맨드
+#[tauri::command]
+pub async fn set_playback_speed(
+    state: State<'_, PlaybackManager>,
+    playback_id: String,
+    speed: f64,
+) -> Result<(), String> {
+    state
+        .set_speed(&playback_id, speed)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 재생 세션 종료 커맨드
+#[tauri::command]
+pub async fn close_playback(
+    state: State<'_, PlaybackManager>,
+    playback_id: String,
+) -> Result<(), String> {
+    state
+        .close_session(&playback_id)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,21 +1,81 @@
-use crate::pty::{CreatePtyResponse, PtyManager};
+use super::session_commands::raw_input;
+use crate::broadcast::BroadcastManager;
+use crate::pty::{
+    self, CreatePtyResponse, LaunchSpec, PtyManager, PtyOutputMode, PtyProcessInfo,
+    PtyResourceUsage, ShellInfo,
+};
+use crate::settings::SettingsManager;
+use crate::terminal::SessionLogOptions;
 use std::collections::HashMap;
-use tauri::{AppHandle, State};
+use std::time::Duration;
+use tauri::ipc::{JavaScriptChannelId, Request};
+use tauri::{AppHandle, State, Webview};
 
 /// PTY 세션 생성 커맨드
+///
+/// on_output 채널을 넘기면 출력이 전역 이벤트 대신 해당 채널로 원시 바이트(ArrayBuffer)로 전달됩니다.
+/// logging을 넘기면 (연결 프로필 설정 등) 전역 세션 로그 설정 대신 해당 옵션으로 로그를 남깁니다.
+/// profile을 넘기면 Settings.launch_profiles의 실행 프로필을 적용합니다 (직접 넘긴 인자가 우선).
+/// encoding은 셸의 문자 인코딩입니다 (예: "euc-kr", 생략하면 UTF-8, set_session_encoding으로 변경 가능).
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn create_pty(
     state: State<'_, PtyManager>,
+    settings: State<'_, SettingsManager>,
     app_handle: AppHandle,
+    webview: Webview,
     shell: Option<String>,
     args: Option<Vec<String>>,
     cwd: Option<String>,
     env: Option<HashMap<String, String>>,
     cols: u16,
     rows: u16,
+    output_mode: Option<PtyOutputMode>,
+    on_output: Option<JavaScriptChannelId>,
+    logging: Option<SessionLogOptions>,
+    profile: Option<String>,
+    encoding: Option<String>,
 ) -> Result<CreatePtyResponse, String> {
+    let settings = settings.get_settings();
+    let log_options = logging.or_else(|| settings.session_logging.auto_options(false));
+
+    let mut launch = LaunchSpec {
+        shell,
+        args,
+        cwd,
+        env,
+        encoding,
+    };
+    if let Some(name) = profile {
+        let profile = settings
+            .launch_profile(&name)
+            .ok_or_else(|| format!("Launch profile not found: {}", name))?;
+        launch = launch.with_profile(profile);
+    }
+
     state
-        .create_session(shell, args, cwd, env, cols, rows, app_handle)
+        .create_session(
+            launch.shell,
+            launch.args,
+            launch.cwd,
+            launch.env,
+            cols,
+            rows,
+            output_mode.unwrap_or_default(),
+            on_output.map(|id| id.channel_on(webview)),
+            settings.scrollback_limit,
+            log_options,
+            launch.encoding,
+            app_handle,
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 설치된 셸 목록 조회 커맨드 (/etc/shells와 PATH의 bash, zsh, fish, nu, pwsh)
+#[tauri::command]
+pub async fn list_available_shells() -> Result<Vec<ShellInfo>, String> {
+    tauri::async_runtime::spawn_blocking(pty::list_available_shells)
         .await
         .map_err(|e| e.to_string())
 }
@@ -33,6 +93,23 @@ pub async fn write_to_pty(
         .map_err(|e| e.to_string())
 }
 
+/// PTY에 바이트 쓰기 커맨드
+///
+/// 제어 문자, UTF-8이 아닌 데이터, 큰 붙여넣기용으로 세션 인코딩 변환 없이 그대로 씁니다.
+/// 본문은 원시 바이트(Uint8Array)이고, PTY ID는 `pty-id` 헤더로 받습니다.
+/// `paste` 헤더가 "true"이고 셸이 bracketed paste 모드(2004)를 켰으면 붙여넣기 시퀀스로 감쌉니다.
+#[tauri::command]
+pub async fn write_bytes_to_pty(
+    state: State<'_, PtyManager>,
+    request: Request<'_>,
+) -> Result<(), String> {
+    let (pty_id, data, paste) = raw_input(&request, "pty-id")?;
+    state
+        .write_bytes_to_session(&pty_id, data, paste)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// PTY 크기 조정 커맨드
 #[tauri::command]
 pub async fn resize_pty(
@@ -47,9 +124,67 @@ pub async fn resize_pty(
         .map_err(|e| e.to_string())
 }
 
+/// PTY 시그널 전송 커맨드 (예: "SIGINT", "TERM", "9")
+#[tauri::command]
+pub async fn signal_pty(
+    state: State<'_, PtyManager>,
+    pty_id: String,
+    signal: String,
+) -> Result<(), String> {
+    state
+        .signal_session(&pty_id, &signal)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// PTY 포그라운드 프로세스 정보 조회 커맨드
+///
+/// 탭 제목("vim main.rs"), 실행 중인 작업이 있는 탭 닫기 경고, 같은 디렉토리에서 새 탭 열기에 사용합니다.
+#[tauri::command]
+pub async fn get_pty_process_info(
+    state: State<'_, PtyManager>,
+    pty_id: String,
+) -> Result<PtyProcessInfo, String> {
+    state.process_info(&pty_id).await.map_err(|e| e.to_string())
+}
+
+/// PTY 프로세스 트리 자원 사용량 조회 커맨드
+///
+/// CPU 사용률은 같은 세션의 직전 조회와의 차이로 계산하므로 첫 조회에서는 None입니다 (주기적 이벤트와 따로 계산).
+#[tauri::command]
+pub async fn get_pty_resource_usage(
+    state: State<'_, PtyManager>,
+    pty_id: String,
+) -> Result<PtyResourceUsage, String> {
+    state
+        .resource_usage(&pty_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 주기적 자원 사용량 이벤트 설정 커맨드
+///
+/// interval_ms마다 모든 PTY 세션의 사용량을 `pty-resource-usage` 이벤트로 보냅니다 (None이면 중지, 최소 250ms).
+#[tauri::command]
+pub async fn set_pty_resource_interval(
+    state: State<'_, PtyManager>,
+    app_handle: AppHandle,
+    interval_ms: Option<u64>,
+) -> Result<(), String> {
+    state
+        .set_resource_interval(interval_ms.map(Duration::from_millis), app_handle)
+        .await;
+    Ok(())
+}
+
 /// PTY 세션 종료 커맨드
 #[tauri::command]
-pub async fn close_pty(state: State<'_, PtyManager>, pty_id: String) -> Result<(), String> {
+pub async fn close_pty(
+    state: State<'_, PtyManager>,
+    broadcast: State<'_, BroadcastManager>,
+    pty_id: String,
+) -> Result<(), String> {
+    broadcast.remove_session(&pty_id).await;
     state
         .close_session(&pty_id)
         .await
@@ -0,0 +1,395 @@
+use crate::pty::{PtyError, PtyManager};
+use crate::settings::SettingsManager;
+use crate::ssh::SshManager;
+use crate::terminal::{
+    export_host_audit, load_host_history, CommandHistoryEntry, MonitorConfig, RecordingInfo,
+    ScreenSnapshot, ScrollbackReplay, SearchOptions, SearchResult, SessionLogInfo,
+    SessionLogOptions, ShellKind, ShellState,
+};
+use std::path::PathBuf;
+use tauri::ipc::{InvokeBody, Request};
+use tauri::State;
+
+/// 바이트 쓰기 커맨드 요청 해석 (PTY/SSH 공통)
+///
+/// 본문은 원시 바이트(Uint8Array)이고, 세션 ID는 id_header 헤더, 붙여넣기 여부는 `paste` 헤더("true")로 받습니다.
+pub(super) fn raw_input(
+    request: &Request<'_>,
+    id_header: &str,
+) -> Result<(String, Vec<u8>, bool), String> {
+    let InvokeBody::Raw(data) = request.body() else {
+        return Err("Request body must be raw bytes".to_string());
+    };
+    let header = |name: &str| {
+        request
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+
+    let id = header(id_header)
+        .ok_or_else(|| format!("Missing {} header", id_header))?
+        .to_string();
+    let paste = header("paste") == Some("true");
+    Ok((id, data.clone(), paste))
+}
+
+/// 세션 스크롤백 재생 커맨드 (PTY/SSH 공통)
+///
+/// 프론트엔드 재로드나 새 창에서 터미널을 다시 구성할 때 사용합니다.
+/// 응답의 next_seq보다 작은 seq의 출력 이벤트는 이미 data에 포함되어 있습니다.
+#[tauri::command]
+pub async fn replay_session(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+    from_offset: Option<u64>,
+) -> Result<ScrollbackReplay, String> {
+    let from_offset = from_offset.unwrap_or(0);
+
+    if let Ok(replay) = pty_manager.replay_session(&session_id, from_offset).await {
+        return Ok(replay);
+    }
+
+    ssh_manager
+        .replay_session(&session_id, from_offset)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 문자 인코딩 변경 커맨드 (PTY/SSH 공통)
+///
+/// encoding은 WHATWG 인코딩 레이블입니다 (예: "utf-8", "euc-kr", "shift_jis", "gb18030", "latin1").
+/// 이후 출력과 입력에 바로 적용되며, 변경된 인코딩 이름을 반환합니다.
+#[tauri::command]
+pub async fn set_session_encoding(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+    encoding: String,
+) -> Result<String, String> {
+    match pty_manager.set_encoding(&session_id, &encoding).await {
+        Err(PtyError::SessionNotFound(_)) => {}
+        result => return result.map_err(|e| e.to_string()),
+    }
+
+    ssh_manager
+        .set_encoding(&session_id, &encoding)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 녹화 시작 커맨드 (PTY/SSH 공통, asciicast v2)
+///
+/// path를 생략하면 설정 디렉토리의 recordings 폴더에 저장합니다.
+#[tauri::command]
+pub async fn start_recording(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+    path: Option<String>,
+    title: Option<String>,
+    record_input: Option<bool>,
+) -> Result<RecordingInfo, String> {
+    let record_input = record_input.unwrap_or(false);
+
+    match pty_manager
+        .start_recording(&session_id, path.clone(), title.clone(), record_input)
+        .await
+    {
+        Err(PtyError::SessionNotFound(_)) => {}
+        result => return result.map_err(|e| e.to_string()),
+    }
+
+    ssh_manager
+        .start_recording(&session_id, path, title, record_input)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 녹화 종료 커맨드 (PTY/SSH 공통)
+#[tauri::command]
+pub async fn stop_recording(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+) -> Result<RecordingInfo, String> {
+    match pty_manager.stop_recording(&session_id).await {
+        Err(PtyError::SessionNotFound(_)) => {}
+        result => return result.map_err(|e| e.to_string()),
+    }
+
+    ssh_manager
+        .stop_recording(&session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 로그 시작 커맨드 (PTY/SSH 공통)
+///
+/// options를 생략하면 설정의 전역 세션 로그 옵션을 사용합니다.
+#[tauri::command]
+pub async fn start_session_log(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    settings: State<'_, SettingsManager>,
+    session_id: String,
+    options: Option<SessionLogOptions>,
+) -> Result<SessionLogInfo, String> {
+    let options = options.unwrap_or_else(|| settings.get_settings().session_logging.options);
+
+    match pty_manager
+        .start_logging(&session_id, options.clone())
+        .await
+    {
+        Err(PtyError::SessionNotFound(_)) => {}
+        result => return result.map_err(|e| e.to_string()),
+    }
+
+    ssh_manager
+        .start_logging(&session_id, options)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 로그 종료 커맨드 (PTY/SSH 공통)
+#[tauri::command]
+pub async fn stop_session_log(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+) -> Result<SessionLogInfo, String> {
+    match pty_manager.stop_logging(&session_id).await {
+        Err(PtyError::SessionNotFound(_)) => {}
+        result => return result.map_err(|e| e.to_string()),
+    }
+
+    ssh_manager
+        .stop_logging(&session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 출력 이벤트 처리 확인 커맨드 (PTY/SSH 공통)
+///
+/// 프론트엔드가 seq까지의 출력을 터미널에 반영했음을 알립니다.
+/// 한 번이라도 호출하면 흐름 제어가 켜지고, 확인이 밀리면 세션 출력 읽기가 멈춥니다.
+#[tauri::command]
+pub async fn ack_output(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+    seq: u64,
+) -> Result<(), String> {
+    if pty_manager.ack_output(&session_id, seq).await.is_ok() {
+        return Ok(());
+    }
+
+    ssh_manager
+        .ack_output(&session_id, seq)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 화면 텍스트 조회 커맨드 (PTY/SSH 공통)
+///
+/// 백엔드 화면 모델 기준의 현재 화면 내용을 줄 단위 텍스트로 반환합니다.
+#[tauri::command]
+pub async fn get_screen_text(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+) -> Result<String, String> {
+    if let Ok(text) = pty_manager.screen_text(&session_id).await {
+        return Ok(text);
+    }
+
+    ssh_manager
+        .screen_text(&session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 화면 셀 조회 커맨드 (PTY/SSH 공통)
+///
+/// 셀별 문자와 속성(색상, 굵게 등), 커서 위치를 반환합니다.
+#[tauri::command]
+pub async fn get_screen_cells(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+) -> Result<ScreenSnapshot, String> {
+    if let Ok(snapshot) = pty_manager.screen_snapshot(&session_id).await {
+        return Ok(snapshot);
+    }
+
+    ssh_manager
+        .screen_snapshot(&session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 스크롤백 검색 커맨드 (PTY/SSH 공통)
+///
+/// 보관 중인 출력에서 ANSI 시퀀스를 제거한 뒤 줄 단위로 검색합니다.
+/// options를 생략하면 대소문자를 구분하지 않는 일반 텍스트 검색입니다.
+#[tauri::command]
+pub async fn search_session(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+    pattern: String,
+    options: Option<SearchOptions>,
+) -> Result<SearchResult, String> {
+    let options = options.unwrap_or_default();
+
+    match pty_manager
+        .search_session(&session_id, &pattern, &options)
+        .await
+    {
+        Err(PtyError::SessionNotFound(_)) => {}
+        result => return result.map_err(|e| e.to_string()),
+    }
+
+    ssh_manager
+        .search_session(&session_id, &pattern, &options)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 셸 통합 상태 조회 커맨드 (PTY/SSH 공통)
+///
+/// 셸 통합 스크립트가 보낸 OSC 133 / OSC 7 마커로 추적한
+/// 작업 디렉토리, 실행 중인 명령, 마지막 종료 코드를 반환합니다.
+#[tauri::command]
+pub async fn get_shell_state(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+) -> Result<ShellState, String> {
+    if let Ok(state) = pty_manager.shell_state(&session_id).await {
+        return Ok(state);
+    }
+
+    ssh_manager
+        .shell_state(&session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 셸 통합 스크립트 조회 커맨드 (bash, zsh, fish)
+///
+/// 사용자가 rc 파일에 직접 추가할 수 있도록 스크립트 원문을 반환합니다.
+#[tauri::command]
+pub fn get_shell_integration_script(shell: ShellKind) -> String {
+    shell.script().to_string()
+}
+
+/// 셸 통합 스크립트 주입 커맨드 (PTY/SSH 공통)
+///
+/// 실행 중인 셸에 스크립트를 로드하는 명령을 입력으로 보냅니다.
+/// shell을 생략하면 PTY는 실행한 셸로 판별하고, SSH는 bash로 간주합니다.
+#[tauri::command]
+pub async fn inject_shell_integration(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+    shell: Option<ShellKind>,
+) -> Result<ShellKind, String> {
+    match pty_manager
+        .inject_shell_integration(&session_id, shell)
+        .await
+    {
+        Err(PtyError::SessionNotFound(_)) => {}
+        result => return result.map_err(|e| e.to_string()),
+    }
+
+    ssh_manager
+        .inject_shell_integration(&session_id, shell)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 명령 히스토리 조회 커맨드 (PTY/SSH 공통)
+///
+/// 셸 통합으로 감지한 명령의 텍스트, 시작 시각, 소요 시간, 종료 코드,
+/// 출력 범위(replay_session의 from_offset으로 사용 가능)를 오래된 순으로 반환합니다.
+#[tauri::command]
+pub async fn list_command_history(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+) -> Result<Vec<CommandHistoryEntry>, String> {
+    if let Ok(history) = pty_manager.command_history(&session_id).await {
+        return Ok(history);
+    }
+
+    ssh_manager
+        .command_history(&session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 호스트별 저장된 명령 히스토리 조회 커맨드
+///
+/// 종료된 세션을 포함해 해당 호스트에서 실행된 명령을 디스크에서 읽습니다.
+/// 로컬 PTY 세션의 호스트는 "localhost"이며, limit을 지정하면 최근 limit개만 반환합니다.
+#[tauri::command]
+pub async fn list_host_command_history(
+    host: String,
+    limit: Option<usize>,
+) -> Result<Vec<CommandHistoryEntry>, String> {
+    load_host_history(&host, limit).map_err(|e| e.to_string())
+}
+
+/// 호스트 입력 감사 로그 내보내기 커맨드
+///
+/// 설정의 auditSshInput이 켜진 SSH 세션에서 기록한 입력을 path에 JSON Lines로 씁니다.
+/// since(Unix epoch 밀리초)를 지정하면 그 이후 기록만 내보내며, 내보낸 기록 수를 반환합니다.
+#[tauri::command]
+pub async fn export_input_audit(
+    host: String,
+    path: String,
+    since: Option<u64>,
+) -> Result<usize, String> {
+    export_host_audit(&host, &PathBuf::from(path), since).map_err(|e| e.to_string())
+}
+
+/// 세션 활동/무출력 감시 설정 커맨드 (PTY/SSH 공통)
+///
+/// silenceMs 동안 출력이 없으면 `session-silence`, activityMs 이상 조용하다가 출력이 오면
+/// `session-activity` 이벤트가 발행됩니다. BEL은 설정과 관계없이 `session-bell`로 발행됩니다.
+#[tauri::command]
+pub async fn set_session_monitor(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+    config: MonitorConfig,
+) -> Result<(), String> {
+    match pty_manager.set_monitor(&session_id, config.clone()).await {
+        Err(PtyError::SessionNotFound(_)) => {}
+        result => return result.map_err(|e| e.to_string()),
+    }
+
+    ssh_manager
+        .set_monitor(&session_id, config)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 세션 활동/무출력 감시 설정 조회 커맨드 (PTY/SSH 공통)
+#[tauri::command]
+pub async fn get_session_monitor(
+    pty_manager: State<'_, PtyManager>,
+    ssh_manager: State<'_, SshManager>,
+    session_id: String,
+) -> Result<MonitorConfig, String> {
+    if let Ok(config) = pty_manager.monitor_config(&session_id).await {
+        return Ok(config);
+    }
+
+    ssh_manager
+        .monitor_config(&session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
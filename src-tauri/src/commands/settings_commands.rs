@@ -1,5 +1,6 @@
 use tauri::State;
 
+use crate::host::HostClient;
 use crate::settings::{Settings, SettingsManager};
 
 /// Load settings from file
@@ -12,14 +13,24 @@ pub async fn load_settings(manager: State<'_, SettingsManager>) -> Result<Settin
 #[tauri::command]
 pub async fn save_settings(
     manager: State<'_, SettingsManager>,
+    host: State<'_, HostClient>,
     settings: Settings,
 ) -> Result<(), String> {
-    manager.update_settings(settings).map_err(|e| e.to_string())
+    manager
+        .update_settings(settings)
+        .map_err(|e| e.to_string())?;
+    // Hosted SSH sessions use the session host's copy for triggers and input audit
+    host.reload_settings();
+    Ok(())
 }
 
 /// Reset settings to default values
 #[tauri::command]
-pub async fn reset_settings(manager: State<'_, SettingsManager>) -> Result<Settings, String> {
+pub async fn reset_settings(
+    manager: State<'_, SettingsManager>,
+    host: State<'_, HostClient>,
+) -> Result<Settings, String> {
     manager.reset_to_default().map_err(|e| e.to_string())?;
+    host.reload_settings();
     Ok(manager.get_settings())
 }
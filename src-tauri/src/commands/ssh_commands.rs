@@ -1,17 +1,41 @@
+use super::session_commands::raw_input;
+use crate::broadcast::BroadcastManager;
+use crate::settings::SettingsManager;
 use crate::ssh::{CreateSshResponse, SshConfig, SshManager};
-use tauri::{AppHandle, State};
+use crate::terminal::SessionLogOptions;
+use tauri::ipc::{JavaScriptChannelId, Request};
+use tauri::{AppHandle, State, Webview};
 
 /// SSH 세션 생성 커맨드
+///
+/// on_output 채널을 넘기면 출력이 전역 이벤트 대신 해당 채널로 원시 바이트(ArrayBuffer)로 전달됩니다.
+/// logging을 넘기면 (연결 프로필 설정 등) 전역 세션 로그 설정 대신 해당 옵션으로 로그를 남깁니다.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn create_ssh_session(
     state: State<'_, SshManager>,
+    settings: State<'_, SettingsManager>,
     app_handle: AppHandle,
+    webview: Webview,
     config: SshConfig,
     cols: u16,
     rows: u16,
+    on_output: Option<JavaScriptChannelId>,
+    logging: Option<SessionLogOptions>,
 ) -> Result<CreateSshResponse, String> {
+    let settings = settings.get_settings();
+    let log_options = logging.or_else(|| settings.session_logging.auto_options(true));
+
     state
-        .create_session(config, cols, rows, app_handle)
+        .create_session(
+            config,
+            cols,
+            rows,
+            on_output.map(|id| id.channel_on(webview)),
+            settings.scrollback_limit,
+            log_options,
+            app_handle,
+        )
         .await
         .map_err(|e| e.to_string())
 }
@@ -29,6 +53,24 @@ pub async fn write_to_ssh(
         .map_err(|e| e.to_string())
 }
 
+/// SSH 세션에 바이트 쓰기 커맨드
+///
+/// 제어 문자, UTF-8이 아닌 데이터, 큰 붙여넣기용으로 세션 인코딩 변환 없이 그대로 씁니다.
+/// 본문은 원시 바이트(Uint8Array)이고, 세션 ID는 `session-id` 헤더로 받습니다.
+/// `paste` 헤더가 "true"이고 원격 셸이 bracketed paste 모드(2004)를 켰으면 붙여넣기 시퀀스로 감쌉니다.
+/// 원격이 입력을 읽지 않아 보내지 못한 입력이 쌓이면 자리가 날 때까지 기다립니다.
+#[tauri::command]
+pub async fn write_bytes_to_ssh(
+    state: State<'_, SshManager>,
+    request: Request<'_>,
+) -> Result<(), String> {
+    let (session_id, data, paste) = raw_input(&request, "session-id")?;
+    state
+        .write_bytes_to_session(&session_id, data, paste)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// SSH 세션 크기 조정 커맨드
 #[tauri::command]
 pub async fn resize_ssh_session(
@@ -47,8 +89,10 @@ pub async fn resize_ssh_session(
 #[tauri::command]
 pub async fn close_ssh_session(
     state: State<'_, SshManager>,
+    broadcast: State<'_, BroadcastManager>,
     session_id: String,
 ) -> Result<(), String> {
+    broadcast.remove_session(&session_id).await;
     state
         .close_session(&session_id)
         .await
@@ -0,0 +1,454 @@
+use super::protocol::{
+    HostAttachParams, HostAttachResponse, HostCommand, HostDetachedEvent, HostError, HostMessage,
+    HostRequest, HostSessionInfo, HostSessionKind, HostSessionParams, HostSshCallParams,
+    HostSshParams, HostStatus, SshCall,
+};
+use crate::ipc::IpcResponse;
+use crate::pty::PtyExitEvent;
+use crate::ssh::{SshConfig, SshExitEvent};
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::ipc::{Channel, InvokeResponseBody};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::{mpsc, oneshot};
+
+/// 세션 호스트 시작 대기 시간
+const START_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 세션 호스트 등록 확인 주기
+const START_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 세션 호스트 클라이언트 (GUI 프로세스 쪽)
+///
+/// 세션 호스트는 앱의 IPC 소켓에 연결해 `register_session_host`로 등록하며,
+/// IPC 서버가 그 연결(링크)을 넘겨주면 요청과 출력이 모두 링크로 오갑니다.
+/// attach한 세션의 출력은 채널로 전달하고, detach하거나 앱이 종료되면
+/// 출력 전달만 멈추고 세션은 호스트에서 계속 실행됩니다.
+#[derive(Clone)]
+pub struct HostClient {
+    inner: Arc<ClientState>,
+}
+
+struct ClientState {
+    link: Mutex<Option<HostLink>>,
+    next_generation: AtomicU64,
+    /// attach된 세션별 출력 채널
+    attachments: Mutex<HashMap<String, Channel>>,
+    /// 호스트가 소유한 SSH 세션 ID (SshManager가 이 프로세스에 없는 세션을 호스트로 넘김)
+    ssh_sessions: Mutex<HashSet<String>>,
+}
+
+/// 등록된 링크
+struct HostLink {
+    generation: u64,
+    /// writer 태스크로 보내는 요청 (보낸 순서대로 호스트에 전달)
+    requests: mpsc::UnboundedSender<HostRequest>,
+    /// 응답을 기다리는 요청
+    pending: HashMap<u64, oneshot::Sender<IpcResponse>>,
+    next_id: u64,
+}
+
+impl HostClient {
+    /// 새 Host Client 생성
+    pub fn new() -> Self {
+        HostClient {
+            inner: Arc::new(ClientState {
+                link: Mutex::new(None),
+                next_generation: AtomicU64::new(0),
+                attachments: Mutex::new(HashMap::new()),
+                ssh_sessions: Mutex::new(HashSet::new()),
+            }),
+        }
+    }
+
+    /// 세션 호스트 상태 조회 (등록된 호스트가 없으면 None)
+    pub async fn status(&self) -> Option<HostStatus> {
+        self.request(HostCommand::Ping).await.ok()
+    }
+
+    /// 세션 호스트 시작 (이미 실행 중이면 그대로 사용)
+    ///
+    /// 현재 실행 파일을 `--session-host` 인자로 분리된 프로세스로 실행하므로
+    /// 앱을 종료하거나 업그레이드해도 호스트와 세션은 유지됩니다.
+    pub async fn start(&self) -> Result<HostStatus, HostError> {
+        if let Some(status) = self.status().await {
+            return Ok(status);
+        }
+
+        let exe = std::env::current_exe().map_err(|e| HostError::StartFailed(e.to_string()))?;
+        let mut command = Command::new(exe);
+        command
+            .arg("--session-host")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+
+        // GUI 프로세스 그룹/콘솔과 분리해 앱 종료 시그널이 전달되지 않게 함
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            command.process_group(0);
+        }
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            const DETACHED_PROCESS: u32 = 0x0000_0008;
+            const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+            command.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+        }
+
+        // 이미 실행 중인 호스트가 다시 연결하는 중이면 새 프로세스는 시작 잠금에 막혀 바로 종료됨
+        command
+            .spawn()
+            .map_err(|e| HostError::StartFailed(e.to_string()))?;
+
+        let deadline = tokio::time::Instant::now() + START_TIMEOUT;
+        while tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(START_POLL_INTERVAL).await;
+            if let Some(status) = self.status().await {
+                return Ok(status);
+            }
+        }
+
+        Err(HostError::StartFailed(
+            "Timed out waiting for session host".to_string(),
+        ))
+    }
+
+    /// 요청을 보내고 응답 데이터 반환
+    pub async fn request<T: DeserializeOwned>(&self, command: HostCommand) -> Result<T, HostError> {
+        Self::wait(self.send(command)?).await
+    }
+
+    /// 요청을 링크에 넣고 응답 수신자 반환 (보낸 순서대로 호스트가 처리)
+    pub fn send(&self, command: HostCommand) -> Result<oneshot::Receiver<IpcResponse>, HostError> {
+        let mut link = self.inner.link.lock().unwrap();
+        let link = link
+            .as_mut()
+            .ok_or_else(|| HostError::NotRunning("No session host is connected".to_string()))?;
+
+        let id = link.next_id;
+        link.next_id += 1;
+        let (reply_tx, reply_rx) = oneshot::channel();
+        link.pending.insert(id, reply_tx);
+        link.requests
+            .send(HostRequest { id, command })
+            .map_err(|_| HostError::NotRunning("Session host link closed".to_string()))?;
+        Ok(reply_rx)
+    }
+
+    /// 응답을 기다려 데이터 반환
+    pub async fn wait<T: DeserializeOwned>(
+        reply: oneshot::Receiver<IpcResponse>,
+    ) -> Result<T, HostError> {
+        let response = reply
+            .await
+            .map_err(|_| HostError::NotRunning("Session host link closed".to_string()))?;
+        if !response.success {
+            return Err(HostError::RequestFailed(response.error.unwrap_or_default()));
+        }
+
+        serde_json::from_value(response.data.unwrap_or(serde_json::Value::Null))
+            .map_err(|e| HostError::Protocol(e.to_string()))
+    }
+
+    /// 세션 호스트에 SSH 세션 생성
+    pub async fn create_ssh_session(
+        &self,
+        config: SshConfig,
+        cols: u16,
+        rows: u16,
+    ) -> Result<HostSessionInfo, HostError> {
+        let command = HostCommand::CreateSshSession {
+            params: HostSshParams { config, cols, rows },
+        };
+        let info: HostSessionInfo = self.request(command).await?;
+        self.inner
+            .ssh_sessions
+            .lock()
+            .unwrap()
+            .insert(info.session_id.clone());
+        Ok(info)
+    }
+
+    /// 세션에 attach해 출력을 채널로 받음
+    ///
+    /// 응답에는 from_offset 이후 보관 중인 출력이 base64로 담기고, 이후 출력은 채널로 원시 바이트가 전달됩니다.
+    /// 종료는 PTY 세션이면 `pty-exit-{session_id}`, SSH 세션이면 `ssh://exit/{session_id}` 이벤트로,
+    /// 종료 전에 출력 전달이 끊기면 (출력을 따라오지 못했거나 링크가 끊긴 경우) `host-detached` 이벤트로 알립니다.
+    pub async fn attach(
+        &self,
+        session_id: &str,
+        from_offset: Option<u64>,
+        output_channel: Channel,
+    ) -> Result<HostAttachResponse, HostError> {
+        // 응답 직후부터 오는 출력을 놓치지 않도록 먼저 등록
+        self.inner
+            .attachments
+            .lock()
+            .unwrap()
+            .insert(session_id.to_string(), output_channel);
+
+        let command = HostCommand::Attach {
+            params: HostAttachParams {
+                session_id: session_id.to_string(),
+                from_offset,
+            },
+        };
+        let result = self.request(command).await;
+        if result.is_err() {
+            self.inner.attachments.lock().unwrap().remove(session_id);
+        }
+        result
+    }
+
+    /// 세션에서 detach (세션은 호스트에서 계속 실행)
+    pub async fn detach(&self, session_id: &str) -> Result<(), HostError> {
+        self.inner
+            .attachments
+            .lock()
+            .unwrap()
+            .remove(session_id)
+            .ok_or_else(|| HostError::SessionNotFound(session_id.to_string()))?;
+
+        let command = HostCommand::Detach {
+            params: HostSessionParams {
+                session_id: session_id.to_string(),
+            },
+        };
+        self.request(command).await
+    }
+
+    /// 세션 종료
+    pub async fn kill(&self, session_id: &str) -> Result<(), HostError> {
+        let _ = self.detach(session_id).await;
+        let command = HostCommand::Kill {
+            params: HostSessionParams {
+                session_id: session_id.to_string(),
+            },
+        };
+        self.request::<()>(command).await?;
+        self.inner.ssh_sessions.lock().unwrap().remove(session_id);
+        Ok(())
+    }
+
+    /// 호스트가 소유한 SSH 세션인지 확인
+    pub fn has_ssh_session(&self, session_id: &str) -> bool {
+        self.inner.ssh_sessions.lock().unwrap().contains(session_id)
+    }
+
+    /// 호스트 SSH 세션 호출
+    pub async fn ssh_call<T: DeserializeOwned>(
+        &self,
+        session_id: &str,
+        call: SshCall,
+    ) -> Result<T, HostError> {
+        let close = matches!(call, SshCall::Close);
+        let result = Self::wait(self.queue_ssh_call(session_id, call)?).await;
+        if close && result.is_ok() {
+            let _ = self.detach(session_id).await;
+            self.inner.ssh_sessions.lock().unwrap().remove(session_id);
+        }
+        result
+    }
+
+    /// 호스트 SSH 세션 호출을 링크에 넣고 응답 수신자 반환 (입력 쓰기 순서 유지용)
+    pub fn queue_ssh_call(
+        &self,
+        session_id: &str,
+        call: SshCall,
+    ) -> Result<oneshot::Receiver<IpcResponse>, HostError> {
+        self.send(HostCommand::Ssh {
+            params: HostSshCallParams {
+                session_id: session_id.to_string(),
+                call,
+            },
+        })
+    }
+
+    /// 호스트가 설정 파일을 다시 읽게 함 (연결된 호스트가 없으면 무시)
+    pub fn reload_settings(&self) {
+        let _ = self.send(HostCommand::ReloadSettings);
+    }
+
+    /// 세션 호스트가 등록한 연결을 링크로 사용
+    ///
+    /// 이전 링크는 대체되며, 응답을 기다리던 요청은 실패합니다.
+    pub fn link<R, W>(&self, reader: BufReader<R>, mut writer: W, app_handle: AppHandle)
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        let (requests, mut request_rx) = mpsc::unbounded_channel::<HostRequest>();
+        let generation = self.inner.next_generation.fetch_add(1, Ordering::Relaxed);
+        let previous = self.inner.link.lock().unwrap().replace(HostLink {
+            generation,
+            requests,
+            pending: HashMap::new(),
+            next_id: 1,
+        });
+        drop(previous);
+
+        tauri::async_runtime::spawn(async move {
+            while let Some(request) = request_rx.recv().await {
+                let Ok(mut line) = serde_json::to_vec(&request) else {
+                    continue;
+                };
+                line.push(b'\n');
+                if writer.write_all(&line).await.is_err() || writer.flush().await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let client = self.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut lines = reader.lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                match serde_json::from_str::<HostMessage>(&line) {
+                    Ok(message) => client.dispatch(message, &app_handle),
+                    Err(e) => eprintln!("Invalid session host message: {}", e),
+                }
+            }
+            client.unlink(generation, &app_handle);
+        });
+
+        // 앱을 다시 시작한 경우 호스트에 남아 있는 SSH 세션을 SshManager가 다룰 수 있도록 갱신
+        let client = self.clone();
+        tauri::async_runtime::spawn(async move {
+            match client
+                .request::<Vec<HostSessionInfo>>(HostCommand::ListSessions)
+                .await
+            {
+                Ok(sessions) => client.inner.ssh_sessions.lock().unwrap().extend(
+                    sessions
+                        .into_iter()
+                        .filter(|info| info.kind == HostSessionKind::Ssh)
+                        .map(|info| info.session_id),
+                ),
+                Err(e) => eprintln!("Failed to list session host sessions: {}", e),
+            }
+        });
+    }
+
+    /// 링크 메시지 처리
+    fn dispatch(&self, message: HostMessage, app_handle: &AppHandle) {
+        match message {
+            HostMessage::Reply { id, response } => {
+                let reply = self
+                    .inner
+                    .link
+                    .lock()
+                    .unwrap()
+                    .as_mut()
+                    .and_then(|link| link.pending.remove(&id));
+                if let Some(reply) = reply {
+                    let _ = reply.send(response);
+                }
+            }
+            HostMessage::Output { session_id, data } => {
+                let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(data) else {
+                    return;
+                };
+                let channel = self
+                    .inner
+                    .attachments
+                    .lock()
+                    .unwrap()
+                    .get(&session_id)
+                    .cloned();
+                if let Some(channel) = channel {
+                    if let Err(e) = channel.send(InvokeResponseBody::Raw(bytes)) {
+                        eprintln!("Failed to send host output to channel: {}", e);
+                    }
+                }
+            }
+            HostMessage::Exit {
+                session_id,
+                kind,
+                exit_code,
+                signal,
+                reason,
+            } => {
+                self.inner.attachments.lock().unwrap().remove(&session_id);
+                match kind {
+                    HostSessionKind::Pty => {
+                        let _ = app_handle.emit(
+                            &format!("pty-exit-{}", session_id),
+                            PtyExitEvent {
+                                pty_id: session_id,
+                                exit_code,
+                                signal,
+                            },
+                        );
+                    }
+                    // 세션을 닫아 끝난 경우(reason 없음)는 GUI의 SSH 세션과 같이 알리지 않음
+                    HostSessionKind::Ssh => {
+                        if let Some(reason) = reason {
+                            let _ = app_handle.emit(
+                                &format!("ssh://exit/{}", session_id),
+                                SshExitEvent { session_id, reason },
+                            );
+                        }
+                    }
+                }
+            }
+            HostMessage::Detached { session_id } => {
+                self.inner.attachments.lock().unwrap().remove(&session_id);
+                let _ = app_handle.emit("host-detached", HostDetachedEvent { session_id });
+            }
+            HostMessage::Emit { event, payload } => {
+                let _ = app_handle.emit(&event, payload);
+            }
+            HostMessage::Notify { title, body } => {
+                if let Err(e) = app_handle
+                    .notification()
+                    .builder()
+                    .title(title)
+                    .body(body)
+                    .show()
+                {
+                    eprintln!("Failed to show notification: {}", e);
+                }
+            }
+        }
+    }
+
+    /// 링크가 끊긴 경우 정리 (더 새 링크로 대체된 경우 제외)
+    fn unlink(&self, generation: u64, app_handle: &AppHandle) {
+        {
+            let mut link = self.inner.link.lock().unwrap();
+            if link.as_ref().map(|link| link.generation) != Some(generation) {
+                return;
+            }
+            // 응답을 기다리던 요청은 수신자가 닫혀 실패함
+            *link = None;
+        }
+
+        let attachments: Vec<String> = self
+            .inner
+            .attachments
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(session_id, _)| session_id)
+            .collect();
+        for session_id in attachments {
+            let _ = app_handle.emit("host-detached", HostDetachedEvent { session_id });
+        }
+        self.inner.ssh_sessions.lock().unwrap().clear();
+    }
+}
+
+impl Default for HostClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
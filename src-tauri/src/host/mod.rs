@@ -0,0 +1,11 @@
+mod client;
+mod protocol;
+mod server;
+mod session;
+
+pub use client::HostClient;
+pub use protocol::{
+    HostAttachResponse, HostCommand, HostCreateParams, HostError, HostResizeParams,
+    HostSessionInfo, HostStatus, HostWriteParams, SshCall,
+};
+pub use server::run_session_host;
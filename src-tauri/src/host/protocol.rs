@@ -0,0 +1,494 @@
+use crate::ipc::IpcResponse;
+use crate::ssh::SshConfig;
+use crate::terminal::{MonitorConfig, SearchOptions, SessionLogOptions, ShellKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// 세션 호스트 요청 (IPC 커맨드와 같은 `{"command": ..., "params": ...}` 형식에 요청 ID를 붙임)
+///
+/// 세션 호스트가 앱의 IPC 소켓에 `register_session_host`로 등록한 연결(링크)에서
+/// GUI가 보내며, 응답은 같은 ID의 HostMessage::Reply로 돌아옵니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostRequest {
+    pub id: u64,
+    #[serde(flatten)]
+    pub command: HostCommand,
+}
+
+/// 세션 호스트 커맨드
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum HostCommand {
+    Ping,
+    CreateSession {
+        params: HostCreateParams,
+    },
+    /// SSH 세션 생성 (I/O 스레드, 스크롤백, 녹화, 입력 감사, 트리거를 호스트가 소유)
+    CreateSshSession {
+        params: HostSshParams,
+    },
+    ListSessions,
+    /// 링크로 출력 전달 시작 (detach하거나 링크가 끊기면 중단)
+    Attach {
+        params: HostAttachParams,
+    },
+    Detach {
+        params: HostSessionParams,
+    },
+    Write {
+        params: HostWriteParams,
+    },
+    Resize {
+        params: HostResizeParams,
+    },
+    Kill {
+        params: HostSessionParams,
+    },
+    /// 호스트 SSH 세션 기능 호출 (GUI의 SshManager가 로컬에 없는 세션을 넘김)
+    Ssh {
+        params: HostSshCallParams,
+    },
+    /// 설정 파일 다시 읽기 (GUI에서 설정을 저장한 뒤)
+    ReloadSettings,
+    Shutdown,
+}
+
+/// create_session 파라미터
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostCreateParams {
+    pub shell: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub cwd: Option<String>,
+    pub env: Option<HashMap<String, String>>,
+    pub cols: u16,
+    pub rows: u16,
+}
+
+/// create_ssh_session 파라미터
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostSshParams {
+    pub config: SshConfig,
+    pub cols: u16,
+    pub rows: u16,
+}
+
+/// attach 파라미터
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostAttachParams {
+    pub session_id: String,
+    /// 이 오프셋 이후의 출력부터 재생 (생략 시 보관 중인 전체 출력)
+    pub from_offset: Option<u64>,
+}
+
+/// write 파라미터
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostWriteParams {
+    pub session_id: String,
+    pub data: String,
+}
+
+/// resize 파라미터
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostResizeParams {
+    pub session_id: String,
+    pub cols: u16,
+    pub rows: u16,
+}
+
+/// 세션 ID만 받는 커맨드 파라미터
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostSessionParams {
+    pub session_id: String,
+}
+
+/// ssh 파라미터
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostSshCallParams {
+    pub session_id: String,
+    pub call: SshCall,
+}
+
+/// 호스트 SSH 세션에 대한 SshManager 호출
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SshCall {
+    Write {
+        data: String,
+    },
+    /// data는 원시 바이트의 base64
+    WriteBytes {
+        data: String,
+        paste: bool,
+    },
+    SetEncoding {
+        encoding: String,
+    },
+    Resize {
+        cols: u16,
+        rows: u16,
+    },
+    Ack {
+        seq: u64,
+    },
+    Search {
+        pattern: String,
+        options: SearchOptions,
+    },
+    ScreenText,
+    ScreenSnapshot,
+    CommandHistory,
+    SetMonitor {
+        config: MonitorConfig,
+    },
+    MonitorConfig,
+    ShellState,
+    InjectShellIntegration {
+        shell: Option<ShellKind>,
+    },
+    Replay {
+        from_offset: u64,
+    },
+    StartRecording {
+        path: Option<String>,
+        title: Option<String>,
+        record_input: bool,
+    },
+    StopRecording,
+    StartLogging {
+        options: SessionLogOptions,
+    },
+    StopLogging,
+    Close,
+}
+
+/// ping 응답
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostStatus {
+    pub version: String,
+    pub pid: u32,
+    pub sessions: usize,
+}
+
+/// 호스트 세션 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HostSessionKind {
+    /// 로컬 PTY
+    Pty,
+    /// ssh2 SSH 세션 (GUI에서는 SSH 커맨드로도 다룰 수 있음)
+    Ssh,
+}
+
+/// 호스트 세션 정보
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostSessionInfo {
+    pub session_id: String,
+    pub kind: HostSessionKind,
+    /// PTY는 셸 경로, SSH는 "user@host"
+    pub shell: String,
+    /// PTY 프로세스 ID (SSH는 None)
+    pub pid: Option<u32>,
+    pub cols: u16,
+    pub rows: u16,
+    /// 생성 시각 (Unix epoch 밀리초)
+    pub created_at: u64,
+    /// 현재 attach된 링크 수
+    pub attached: usize,
+    /// 프로세스 종료 또는 연결 종료 여부 (종료된 세션은 kill할 때까지 출력을 보관)
+    pub exited: bool,
+    pub exit_code: Option<i32>,
+    pub signal: Option<String>,
+    /// SSH 연결 종료 사유
+    pub reason: Option<String>,
+}
+
+/// attach 응답 (이후 출력은 링크로 HostMessage::Output이 전달됨)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostAttachResponse {
+    pub session_id: String,
+    pub kind: HostSessionKind,
+    /// from_offset 이후 보관 중인 출력 (출력 메시지와 같은 원시 바이트의 base64)
+    pub data: String,
+    pub start_offset: u64,
+    /// 다음 출력의 스트림 오프셋 (다시 attach할 때 from_offset으로 사용)
+    pub end_offset: u64,
+    /// 이후 첫 출력 메시지의 시퀀스 번호 (SSH 세션의 ack_output에 사용)
+    pub next_seq: u64,
+    /// 요청한 오프셋이 이미 버퍼에서 밀려나 일부 출력이 누락된 경우 true
+    pub truncated: bool,
+}
+
+/// 세션 호스트가 링크로 보내는 메시지
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HostMessage {
+    /// 요청 응답
+    Reply {
+        id: u64,
+        #[serde(flatten)]
+        response: IpcResponse,
+    },
+    /// attach한 세션의 출력 (원시 바이트의 base64)
+    Output { session_id: String, data: String },
+    /// attach한 세션의 프로세스 또는 연결 종료
+    Exit {
+        session_id: String,
+        kind: HostSessionKind,
+        exit_code: Option<i32>,
+        signal: Option<String>,
+        reason: Option<String>,
+    },
+    /// 출력을 따라오지 못해 전달 중단 (end_offset부터 다시 attach)
+    Detached { session_id: String },
+    /// 호스트 세션의 프론트엔드 이벤트 (셸 통합, 트리거, 활동 감시)
+    Emit {
+        event: String,
+        payload: serde_json::Value,
+    },
+    /// 트리거 알림
+    Notify { title: String, body: String },
+}
+
+/// host-detached 이벤트 페이로드 (프로세스 종료 전에 출력 전달이 끊긴 경우)
+#[derive(Debug, Clone, Serialize)]
+pub struct HostDetachedEvent {
+    pub session_id: String,
+}
+
+/// 세션 호스트 에러 타입
+#[derive(Debug, Error)]
+pub enum HostError {
+    #[error("Session host is not running: {0}")]
+    NotRunning(String),
+
+    #[error("Session host is already running")]
+    AlreadyRunning,
+
+    #[error("Failed to start session host: {0}")]
+    StartFailed(String),
+
+    #[error("Session not found: {0}")]
+    SessionNotFound(String),
+
+    #[error("Failed to create session: {0}")]
+    CreationFailed(String),
+
+    #[error("Session host request failed: {0}")]
+    RequestFailed(String),
+
+    #[error("Invalid session host message: {0}")]
+    Protocol(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<HostError> for String {
+    fn from(err: HostError) -> Self {
+        err.to_string()
+    }
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 직렬화 후 다시 역직렬화
+    fn round_trip<T: Serialize + serde::de::DeserializeOwned>(value: &T) -> T {
+        serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_ping_request() {
+        let json = r#"{"id":1,"command":"ping"}"#;
+        let request: HostRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.id, 1);
+        assert!(matches!(request.command, HostCommand::Ping));
+        assert_eq!(serde_json::to_string(&request).unwrap(), json);
+    }
+
+    #[test]
+    fn test_create_session_command() {
+        let json = r#"{
+            "id": 2,
+            "command": "create_session",
+            "params": {
+                "shell": "/bin/zsh",
+                "args": ["-l"],
+                "cwd": null,
+                "env": {"TERM": "xterm-256color"},
+                "cols": 120,
+                "rows": 40
+            }
+        }"#;
+        let request: HostRequest = serde_json::from_str(json).unwrap();
+        match round_trip(&request).command {
+            HostCommand::CreateSession { params } => {
+                assert_eq!(params.shell.as_deref(), Some("/bin/zsh"));
+                assert_eq!(params.args, Some(vec!["-l".to_string()]));
+                assert_eq!(params.cwd, None);
+                assert_eq!(params.env.unwrap()["TERM"], "xterm-256color");
+                assert_eq!((params.cols, params.rows), (120, 40));
+            }
+            _ => panic!("Expected CreateSession command"),
+        }
+    }
+
+    #[test]
+    fn test_create_ssh_session_command() {
+        let json = r#"{
+            "id": 3,
+            "command": "create_ssh_session",
+            "params": {
+                "config": {"host": "example.com", "port": 22, "username": "user"},
+                "cols": 80,
+                "rows": 24
+            }
+        }"#;
+        let request: HostRequest = serde_json::from_str(json).unwrap();
+        match round_trip(&request).command {
+            HostCommand::CreateSshSession { params } => {
+                assert_eq!(params.config.host, "example.com");
+                assert_eq!(params.config.username, "user");
+                assert_eq!((params.cols, params.rows), (80, 24));
+            }
+            _ => panic!("Expected CreateSshSession command"),
+        }
+    }
+
+    #[test]
+    fn test_session_commands() {
+        let cmd: HostCommand = serde_json::from_str(
+            r#"{"command":"attach","params":{"session_id":"s1","from_offset":42}}"#,
+        )
+        .unwrap();
+        match round_trip(&cmd) {
+            HostCommand::Attach { params } => {
+                assert_eq!(params.session_id, "s1");
+                assert_eq!(params.from_offset, Some(42));
+            }
+            _ => panic!("Expected Attach command"),
+        }
+
+        // from_offset 생략 가능
+        let cmd: HostCommand =
+            serde_json::from_str(r#"{"command":"attach","params":{"session_id":"s1"}}"#).unwrap();
+        assert!(matches!(cmd, HostCommand::Attach { params } if params.from_offset.is_none()));
+
+        let cmd = HostCommand::Write {
+            params: HostWriteParams {
+                session_id: "s1".to_string(),
+                data: "ls\r".to_string(),
+            },
+        };
+        assert!(matches!(round_trip(&cmd), HostCommand::Write { params } if params.data == "ls\r"));
+
+        let cmd: HostCommand = serde_json::from_str(
+            r#"{"command":"resize","params":{"session_id":"s1","cols":100,"rows":30}}"#,
+        )
+        .unwrap();
+        assert!(matches!(cmd, HostCommand::Resize { params } if params.cols == 100));
+
+        for json in [
+            r#"{"command":"list_sessions"}"#,
+            r#"{"command":"detach","params":{"session_id":"s1"}}"#,
+            r#"{"command":"kill","params":{"session_id":"s1"}}"#,
+            r#"{"command":"reload_settings"}"#,
+            r#"{"command":"shutdown"}"#,
+        ] {
+            let cmd: HostCommand = serde_json::from_str(json).unwrap();
+            assert_eq!(serde_json::to_string(&cmd).unwrap(), json);
+        }
+
+        assert!(serde_json::from_str::<HostCommand>(r#"{"command":"unknown"}"#).is_err());
+    }
+
+    #[test]
+    fn test_ssh_call_command() {
+        let json = r#"{"id":4,"command":"ssh","params":{"session_id":"s1","call":{"type":"ack","seq":7}}}"#;
+        let request: HostRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&request).unwrap(), json);
+        assert!(matches!(
+            request.command,
+            HostCommand::Ssh { params } if matches!(params.call, SshCall::Ack { seq: 7 })
+        ));
+
+        let cmd: HostCommand = serde_json::from_str(
+            r#"{"command":"ssh","params":{"session_id":"s1","call":{"type":"search","pattern":"err","options":{"regex":true}}}}"#,
+        )
+        .unwrap();
+        match cmd {
+            HostCommand::Ssh { params } => match params.call {
+                SshCall::Search { pattern, options } => {
+                    assert_eq!(pattern, "err");
+                    assert!(options.regex);
+                    assert_eq!(options.max_results, SearchOptions::default().max_results);
+                }
+                _ => panic!("Expected Search call"),
+            },
+            _ => panic!("Expected Ssh command"),
+        }
+    }
+
+    #[test]
+    fn test_messages() {
+        let reply = HostMessage::Reply {
+            id: 5,
+            response: IpcResponse::error("Session not found: s1"),
+        };
+        assert_eq!(
+            serde_json::to_string(&reply).unwrap(),
+            r#"{"type":"reply","id":5,"success":false,"error":"Session not found: s1"}"#
+        );
+        match round_trip(&reply) {
+            HostMessage::Reply { id, response } => {
+                assert_eq!(id, 5);
+                assert!(!response.success);
+                assert_eq!(response.error.as_deref(), Some("Session not found: s1"));
+            }
+            _ => panic!("Expected Reply message"),
+        }
+
+        let output = HostMessage::Output {
+            session_id: "s1".to_string(),
+            data: "/w==".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_string(&output).unwrap(),
+            r#"{"type":"output","session_id":"s1","data":"/w=="}"#
+        );
+
+        let exit: HostMessage = serde_json::from_str(
+            r#"{"type":"exit","session_id":"s1","kind":"ssh","exit_code":null,"signal":null,"reason":"Connection closed"}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            exit,
+            HostMessage::Exit { kind: HostSessionKind::Ssh, reason: Some(reason), .. } if reason == "Connection closed"
+        ));
+    }
+
+    #[test]
+    fn test_attach_response() {
+        let response = HostAttachResponse {
+            session_id: "s1".to_string(),
+            kind: HostSessionKind::Pty,
+            data: "YWJj".to_string(),
+            start_offset: 10,
+            end_offset: 13,
+            next_seq: 2,
+            truncated: true,
+        };
+        let decoded = round_trip(&response);
+        assert_eq!(decoded.kind, HostSessionKind::Pty);
+        assert_eq!(decoded.data, "YWJj");
+        assert_eq!((decoded.start_offset, decoded.end_offset), (10, 13));
+        assert_eq!(decoded.next_seq, 2);
+        assert!(decoded.truncated);
+    }
+}
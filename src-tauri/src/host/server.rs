@@ -0,0 +1,796 @@
+use super::protocol::{
+    HostCommand, HostError, HostMessage, HostRequest, HostSessionInfo, HostSshCallParams,
+    HostStatus, SshCall,
+};
+use super::session::{HostAttachment, HostSession, HostStreamEvent};
+use crate::ipc::{platform, IpcResponse};
+use crate::settings::SettingsManager;
+use crate::ssh::{self, SshError, SshManager};
+use crate::terminal::SessionSink;
+use base64::Engine;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{mpsc, Notify};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// 링크로 보내기 전에 쌓아둘 수 있는 메시지 수
+const LINK_CAPACITY: usize = 1024;
+
+/// 앱의 IPC 소켓에 다시 연결을 시도하는 주기 (GUI가 실행 중이 아닐 때)
+const RECONNECT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 요청 응답 대기
+type PendingReply = Pin<Box<dyn Future<Output = IpcResponse> + Send>>;
+
+/// 세션 호스트 상태
+///
+/// GUI와 별개의 프로세스(`rusterm --session-host`)에서 실행되며 로컬 PTY와
+/// ssh2 SSH 세션(I/O 스레드, 스크롤백, 녹화, 입력 감사, 트리거 포함)을 소유합니다.
+/// 앱의 IPC 소켓에 연결해 `register_session_host`로 등록하면 그 연결이
+/// GUI와의 링크가 되고, 앱이 종료되면 다음 GUI가 실행될 때까지 다시 연결을 시도합니다.
+struct SessionHost {
+    sessions: Mutex<HashMap<String, Arc<HostSession>>>,
+    /// SSH 세션 (HostSession과 같은 ID)
+    ssh: SshManager,
+    /// GUI와 같은 설정 파일 (트리거, 하이라이트, 입력 감사, 스크롤백 한도)
+    settings: Option<Arc<SettingsManager>>,
+    /// 현재 링크 (세션 이벤트와 알림을 보낼 곳)
+    link: Arc<Mutex<Option<LinkSender>>>,
+    next_link: AtomicU64,
+    shutdown: Notify,
+}
+
+/// 링크 송신자 (링크가 끝나면 채널이 닫히도록 약한 참조로 보관)
+#[derive(Clone)]
+struct LinkSender {
+    generation: u64,
+    sender: mpsc::WeakSender<HostMessage>,
+}
+
+/// 세션 호스트 실행 (shutdown 요청을 받을 때까지 블로킹)
+pub fn run_session_host() -> Result<(), HostError> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        // 세션 호스트는 사용자당 하나만 실행 (프로세스 종료까지 유지)
+        let Some(_lock) = platform::lock_host()? else {
+            return Err(HostError::AlreadyRunning);
+        };
+
+        let settings = match SettingsManager::new() {
+            Ok(settings) => Some(Arc::new(settings)),
+            Err(e) => {
+                eprintln!("Failed to load settings for session host: {}", e);
+                None
+            }
+        };
+        let host = Arc::new(SessionHost::new(settings));
+
+        loop {
+            tokio::select! {
+                _ = host.shutdown.notified() => break,
+                result = register(host.clone()) => {
+                    if let Err(e) = result {
+                        // GUI가 실행 중이 아니면 연결이 실패하므로 조용히 다시 시도
+                        if !matches!(e, HostError::Io(_)) {
+                            eprintln!("Session host link failed: {}", e);
+                        }
+                    }
+                    tokio::time::sleep(RECONNECT_INTERVAL).await;
+                }
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// 앱의 IPC 소켓에 연결해 등록한 뒤 연결이 끊길 때까지 링크 처리
+async fn register(host: Arc<SessionHost>) -> Result<(), HostError> {
+    let stream = platform::connect().await?;
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut reader = BufReader::new(reader);
+
+    writer
+        .write_all(b"{\"command\":\"register_session_host\"}\n")
+        .await?;
+    writer.flush().await?;
+
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Err(HostError::Protocol("Connection closed".to_string()));
+    }
+    let response: IpcResponse =
+        serde_json::from_str(&line).map_err(|e| HostError::Protocol(e.to_string()))?;
+    if !response.success {
+        return Err(HostError::RequestFailed(response.error.unwrap_or_default()));
+    }
+
+    // 호스트가 떠 있는 동안 GUI에서 바뀐 설정 반영
+    host.reload_settings();
+
+    serve_link(reader, writer, host).await;
+    Ok(())
+}
+
+/// 링크 처리 (요청마다 같은 ID의 응답, attach한 세션의 출력과 세션 이벤트 전달)
+///
+/// 쓰기 요청은 받은 순서대로 세션에 넣고, 완료와 나머지 요청은 별도 태스크에서 기다려
+/// 느린 요청이 다른 요청이나 출력 전달을 막지 않습니다.
+async fn serve_link<R, W>(reader: BufReader<R>, writer: W, host: Arc<SessionHost>)
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let (tx, mut rx) = mpsc::channel::<HostMessage>(LINK_CAPACITY);
+    let writer_task = tokio::spawn(async move {
+        let mut writer = writer;
+        while let Some(message) = rx.recv().await {
+            if write_line(&mut writer, &message).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let generation = host.next_link.fetch_add(1, Ordering::Relaxed);
+    *host.link.lock().unwrap() = Some(LinkSender {
+        generation,
+        sender: tx.downgrade(),
+    });
+
+    let mut attachments: HashMap<String, JoinHandle<()>> = HashMap::new();
+    let mut shutdown = false;
+    let mut lines = reader.lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        let request = match serde_json::from_str::<HostRequest>(&line) {
+            Ok(request) => request,
+            Err(e) => {
+                // 응답을 맞출 수 있도록 읽을 수 있으면 요청 ID를 그대로 돌려줌
+                let id = serde_json::from_str::<serde_json::Value>(&line)
+                    .ok()
+                    .and_then(|value| value.get("id")?.as_u64())
+                    .unwrap_or(0);
+                let response = IpcResponse::error(format!("Invalid request: {}", e));
+                let _ = tx.send(HostMessage::Reply { id, response }).await;
+                continue;
+            }
+        };
+
+        let id = request.id;
+        match request.command {
+            HostCommand::Attach { params } => {
+                let attached = match host.session_or_err(&params.session_id) {
+                    Ok(session) => session
+                        .attach(params.from_offset)
+                        .await
+                        .map(|attachment| (session, attachment)),
+                    Err(e) => Err(e),
+                };
+                match attached {
+                    Ok((session, attachment)) => {
+                        // 응답을 먼저 넣어 GUI가 재생 데이터를 출력보다 먼저 받음
+                        let response = IpcResponse::success(&attachment.response);
+                        let _ = tx.send(HostMessage::Reply { id, response }).await;
+
+                        let task =
+                            tokio::spawn(forward_output(session, attachment, tx.downgrade()));
+                        if let Some(previous) = attachments.insert(params.session_id, task) {
+                            previous.abort();
+                        }
+                    }
+                    Err(e) => {
+                        let response = respond::<()>(Err(e));
+                        let _ = tx.send(HostMessage::Reply { id, response }).await;
+                    }
+                }
+            }
+            HostCommand::Detach { params } => {
+                let response = match attachments.remove(&params.session_id) {
+                    Some(task) => {
+                        task.abort();
+                        if let Some(session) = host.session(&params.session_id) {
+                            session.release_output().await;
+                        }
+                        IpcResponse::success(())
+                    }
+                    None => IpcResponse::error(
+                        HostError::SessionNotFound(params.session_id).to_string(),
+                    ),
+                };
+                let _ = tx.send(HostMessage::Reply { id, response }).await;
+            }
+            HostCommand::Shutdown => {
+                host.kill_all().await;
+                let response = IpcResponse::success(());
+                let _ = tx.send(HostMessage::Reply { id, response }).await;
+                shutdown = true;
+                break;
+            }
+            command => {
+                let pending = host.start(command).await;
+                let link = tx.downgrade();
+                tokio::spawn(async move {
+                    let response = pending.await;
+                    send(&link, HostMessage::Reply { id, response }).await;
+                });
+            }
+        }
+    }
+
+    for (session_id, task) in attachments {
+        task.abort();
+        if let Some(session) = host.session(&session_id) {
+            session.release_output().await;
+        }
+    }
+
+    {
+        let mut link = host.link.lock().unwrap();
+        if link.as_ref().is_some_and(|l| l.generation == generation) {
+            *link = None;
+        }
+    }
+
+    // 남은 메시지(종료 응답 포함)를 모두 쓴 뒤 writer가 끝남
+    drop(tx);
+    let _ = writer_task.await;
+
+    if shutdown {
+        host.shutdown.notify_one();
+    }
+}
+
+/// attach한 세션의 출력과 종료를 링크로 전달
+///
+/// 링크가 출력을 따라오지 못하면 Detached를 보내고 중단합니다 (GUI는 end_offset부터 다시 attach).
+async fn forward_output(
+    session: Arc<HostSession>,
+    attachment: HostAttachment,
+    link: mpsc::WeakSender<HostMessage>,
+) {
+    let HostAttachment {
+        response,
+        mut receiver,
+        exit,
+    } = attachment;
+    let session_id = response.session_id;
+
+    let exit = match exit {
+        Some(exit) => exit,
+        None => loop {
+            match receiver.recv().await {
+                Ok(HostStreamEvent::Output { seq, data }) => {
+                    // 재생 데이터에 이미 포함된 출력
+                    if seq < response.next_seq {
+                        continue;
+                    }
+                    let message = HostMessage::Output {
+                        session_id: session_id.clone(),
+                        data: base64::engine::general_purpose::STANDARD.encode(data),
+                    };
+                    if !send(&link, message).await {
+                        return;
+                    }
+                }
+                Ok(exit) => break exit,
+                Err(RecvError::Lagged(_)) => {
+                    send(&link, HostMessage::Detached { session_id }).await;
+                    session.release_output().await;
+                    return;
+                }
+                Err(RecvError::Closed) => return,
+            }
+        },
+    };
+
+    if let HostStreamEvent::Exit {
+        exit_code,
+        signal,
+        reason,
+    } = exit
+    {
+        let message = HostMessage::Exit {
+            session_id,
+            kind: response.kind,
+            exit_code,
+            signal,
+            reason,
+        };
+        send(&link, message).await;
+    }
+}
+
+/// 링크가 살아 있으면 메시지 전송
+async fn send(link: &mpsc::WeakSender<HostMessage>, message: HostMessage) -> bool {
+    match link.upgrade() {
+        Some(sender) => sender.send(message).await.is_ok(),
+        None => false,
+    }
+}
+
+async fn write_line<W, T>(writer: &mut W, value: &T) -> std::io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let mut line = serde_json::to_vec(value)?;
+    line.push(b'\n');
+    writer.write_all(&line).await?;
+    writer.flush().await
+}
+
+/// 요청 결과를 응답으로 변환 (세션 요청 실패는 세션의 에러 메시지 그대로)
+fn respond<T: Serialize>(result: Result<T, HostError>) -> IpcResponse {
+    match result {
+        Ok(data) => IpcResponse::success(data),
+        Err(HostError::RequestFailed(message)) => IpcResponse::error(message),
+        Err(e) => IpcResponse::error(e.to_string()),
+    }
+}
+
+fn ready(response: IpcResponse) -> PendingReply {
+    Box::pin(std::future::ready(response))
+}
+
+fn ssh_failed(err: SshError) -> HostError {
+    HostError::RequestFailed(err.to_string())
+}
+
+fn ssh_value<T: Serialize>(result: Result<T, SshError>) -> Result<serde_json::Value, HostError> {
+    serde_json::to_value(result.map_err(ssh_failed)?)
+        .map_err(|e| HostError::Protocol(e.to_string()))
+}
+
+impl SessionHost {
+    fn new(settings: Option<Arc<SettingsManager>>) -> Self {
+        Self {
+            sessions: Mutex::new(HashMap::new()),
+            ssh: SshManager::new(),
+            settings,
+            link: Arc::new(Mutex::new(None)),
+            next_link: AtomicU64::new(0),
+            shutdown: Notify::new(),
+        }
+    }
+
+    fn session(&self, session_id: &str) -> Option<Arc<HostSession>> {
+        self.sessions.lock().unwrap().get(session_id).cloned()
+    }
+
+    fn session_or_err(&self, session_id: &str) -> Result<Arc<HostSession>, HostError> {
+        self.session(session_id)
+            .ok_or_else(|| HostError::SessionNotFound(session_id.to_string()))
+    }
+
+    /// 세션 스트림이 이벤트와 알림을 보낼 곳
+    fn sink(&self) -> Arc<dyn SessionSink> {
+        Arc::new(HostSink {
+            link: self.link.clone(),
+            settings: self.settings.clone(),
+            ssh: self.ssh.clone(),
+            runtime: tokio::runtime::Handle::current(),
+        })
+    }
+
+    fn reload_settings(&self) {
+        if let Some(settings) = &self.settings {
+            if let Err(e) = settings.reload() {
+                eprintln!("Failed to reload settings: {}", e);
+            }
+        }
+    }
+
+    /// 요청 시작 (쓰기는 여기서 순서대로 넣고, 응답은 반환한 future에서 기다림)
+    async fn start(self: &Arc<Self>, command: HostCommand) -> PendingReply {
+        match command {
+            HostCommand::Write { params } => {
+                let wait = match self.session_or_err(&params.session_id) {
+                    Ok(session) => session.write(params.data).await,
+                    Err(e) => Err(e),
+                };
+                match wait {
+                    Ok(wait) => Box::pin(async move { respond(wait.await) }),
+                    Err(e) => ready(respond::<()>(Err(e))),
+                }
+            }
+            HostCommand::Ssh { params } => self.start_ssh(params).await,
+            command => {
+                let host = self.clone();
+                Box::pin(async move { respond(host.handle(command).await) })
+            }
+        }
+    }
+
+    /// SSH 호출 시작 (입력 쓰기는 순서대로 넣음)
+    async fn start_ssh(self: &Arc<Self>, params: HostSshCallParams) -> PendingReply {
+        let HostSshCallParams { session_id, call } = params;
+        let queued = match call {
+            SshCall::Write { data } => self
+                .ssh
+                .queue_write(&session_id, &data)
+                .await
+                .map(|done| (done, serde_json::Value::Null)),
+            SshCall::WriteBytes { data, paste } => {
+                match base64::engine::general_purpose::STANDARD.decode(data) {
+                    Ok(data) => self
+                        .ssh
+                        .queue_write_bytes(&session_id, data, paste)
+                        .await
+                        .map(|done| (done, serde_json::Value::Null)),
+                    Err(e) => return ready(IpcResponse::error(format!("Invalid data: {}", e))),
+                }
+            }
+            SshCall::InjectShellIntegration { shell } => self
+                .ssh
+                .queue_shell_integration(&session_id, shell)
+                .await
+                .map(|(shell, done)| (done, serde_json::json!(shell))),
+            call => {
+                let host = self.clone();
+                return Box::pin(async move { respond(host.handle_ssh(&session_id, call).await) });
+            }
+        };
+
+        match queued {
+            Ok((done, data)) => Box::pin(async move {
+                respond(
+                    ssh::wait_write(done)
+                        .await
+                        .map(|_| data)
+                        .map_err(ssh_failed),
+                )
+            }),
+            Err(e) => ready(IpcResponse::error(e.to_string())),
+        }
+    }
+
+    /// 링크에서 처리하는 요청(attach, detach, shutdown)과 쓰기를 제외한 요청 처리
+    async fn handle(&self, command: HostCommand) -> Result<serde_json::Value, HostError> {
+        let value = match command {
+            HostCommand::Ping => serde_json::to_value(HostStatus {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                pid: std::process::id(),
+                sessions: self.sessions.lock().unwrap().len(),
+            }),
+            HostCommand::CreateSession { params } => {
+                let session_id = Uuid::new_v4().to_string();
+                let session = HostSession::spawn(session_id.clone(), params)?;
+                serde_json::to_value(self.insert(session_id, session))
+            }
+            HostCommand::CreateSshSession { params } => {
+                let session_id = Uuid::new_v4().to_string();
+                let session =
+                    HostSession::connect(session_id.clone(), params, self.ssh.clone(), self.sink())
+                        .await?;
+                serde_json::to_value(self.insert(session_id, session))
+            }
+            HostCommand::ListSessions => {
+                let sessions: Vec<Arc<HostSession>> =
+                    self.sessions.lock().unwrap().values().cloned().collect();
+                let mut infos: Vec<HostSessionInfo> = sessions.iter().map(|s| s.info()).collect();
+                infos.sort_by_key(|info| info.created_at);
+                serde_json::to_value(infos)
+            }
+            HostCommand::Resize { params } => {
+                self.session_or_err(&params.session_id)?
+                    .resize(params.cols, params.rows)
+                    .await?;
+                Ok(serde_json::Value::Null)
+            }
+            HostCommand::Kill { params } => {
+                self.kill(&params.session_id).await?;
+                Ok(serde_json::Value::Null)
+            }
+            HostCommand::ReloadSettings => {
+                self.reload_settings();
+                Ok(serde_json::Value::Null)
+            }
+            HostCommand::Write { .. }
+            | HostCommand::Ssh { .. }
+            | HostCommand::Attach { .. }
+            | HostCommand::Detach { .. }
+            | HostCommand::Shutdown => {
+                return Err(HostError::Protocol(
+                    "request must be handled by the link".to_string(),
+                ))
+            }
+        };
+
+        value.map_err(|e| HostError::Protocol(e.to_string()))
+    }
+
+    /// 쓰기를 제외한 SSH 호출 처리
+    async fn handle_ssh(
+        &self,
+        session_id: &str,
+        call: SshCall,
+    ) -> Result<serde_json::Value, HostError> {
+        let ssh = &self.ssh;
+        match call {
+            SshCall::SetEncoding { encoding } => {
+                ssh_value(ssh.set_encoding(session_id, &encoding).await)
+            }
+            SshCall::Resize { cols, rows } => {
+                // 세션 정보의 크기도 함께 갱신
+                self.session_or_err(session_id)?.resize(cols, rows).await?;
+                Ok(serde_json::Value::Null)
+            }
+            SshCall::Ack { seq } => ssh_value(ssh.ack_output(session_id, seq).await),
+            SshCall::Search { pattern, options } => {
+                ssh_value(ssh.search_session(session_id, &pattern, &options).await)
+            }
+            SshCall::ScreenText => ssh_value(ssh.screen_text(session_id).await),
+            SshCall::ScreenSnapshot => ssh_value(ssh.screen_snapshot(session_id).await),
+            SshCall::CommandHistory => ssh_value(ssh.command_history(session_id).await),
+            SshCall::SetMonitor { config } => ssh_value(ssh.set_monitor(session_id, config).await),
+            SshCall::MonitorConfig => ssh_value(ssh.monitor_config(session_id).await),
+            SshCall::ShellState => ssh_value(ssh.shell_state(session_id).await),
+            SshCall::Replay { from_offset } => {
+                ssh_value(ssh.replay_session(session_id, from_offset).await)
+            }
+            SshCall::StartRecording {
+                path,
+                title,
+                record_input,
+            } => ssh_value(
+                ssh.start_recording(session_id, path, title, record_input)
+                    .await,
+            ),
+            SshCall::StopRecording => ssh_value(ssh.stop_recording(session_id).await),
+            SshCall::StartLogging { options } => {
+                ssh_value(ssh.start_logging(session_id, options).await)
+            }
+            SshCall::StopLogging => ssh_value(ssh.stop_logging(session_id).await),
+            SshCall::Close => {
+                self.kill(session_id).await?;
+                Ok(serde_json::Value::Null)
+            }
+            SshCall::Write { .. }
+            | SshCall::WriteBytes { .. }
+            | SshCall::InjectShellIntegration { .. } => Err(HostError::Protocol(
+                "write must be queued by the link".to_string(),
+            )),
+        }
+    }
+
+    fn insert(&self, session_id: String, session: HostSession) -> HostSessionInfo {
+        let info = session.info();
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(session_id, Arc::new(session));
+        info
+    }
+
+    /// 세션을 목록에서 빼고 종료
+    async fn kill(&self, session_id: &str) -> Result<(), HostError> {
+        let session = self
+            .sessions
+            .lock()
+            .unwrap()
+            .remove(session_id)
+            .ok_or_else(|| HostError::SessionNotFound(session_id.to_string()))?;
+        session.kill().await;
+        Ok(())
+    }
+
+    async fn kill_all(&self) {
+        let sessions: Vec<Arc<HostSession>> = self
+            .sessions
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(_, s)| s)
+            .collect();
+        for session in sessions {
+            session.kill().await;
+        }
+    }
+}
+
+/// 호스트 세션의 이벤트, 알림, 트리거 자동 응답 처리
+///
+/// 이벤트와 알림은 링크로 GUI에 보내며, 연결된 GUI가 없으면 버립니다.
+struct HostSink {
+    link: Arc<Mutex<Option<LinkSender>>>,
+    settings: Option<Arc<SettingsManager>>,
+    ssh: SshManager,
+    runtime: tokio::runtime::Handle,
+}
+
+impl HostSink {
+    /// flusher 스레드를 막지 않도록 가득 찬 링크에는 보내지 않음
+    fn try_send(&self, message: HostMessage) -> Result<(), String> {
+        let link = self.link.lock().unwrap().clone();
+        let Some(sender) = link.and_then(|link| link.sender.upgrade()) else {
+            return Ok(());
+        };
+        sender.try_send(message).map_err(|e| e.to_string())
+    }
+}
+
+impl SessionSink for HostSink {
+    fn emit_value(&self, event: &str, payload: serde_json::Value) -> Result<(), String> {
+        self.try_send(HostMessage::Emit {
+            event: event.to_string(),
+            payload,
+        })
+    }
+
+    fn notify(&self, title: &str, body: &str) -> Result<(), String> {
+        self.try_send(HostMessage::Notify {
+            title: title.to_string(),
+            body: body.to_string(),
+        })
+    }
+
+    fn settings(&self) -> Option<&SettingsManager> {
+        self.settings.as_deref()
+    }
+
+    fn respond(&self, session_id: String, data: String) {
+        // 호스트에서 트리거가 동작하는 세션은 SSH 세션뿐
+        let ssh = self.ssh.clone();
+        self.runtime.spawn(async move {
+            if let Err(e) = ssh.write_to_session(&session_id, &data).await {
+                eprintln!("Failed to send trigger response: {}", e);
+            }
+        });
+    }
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::protocol::{HostAttachResponse, HostSessionKind};
+    use tokio::io::DuplexStream;
+
+    /// 링크 처리 태스크를 시작하고 GUI 쪽 스트림 반환
+    fn connect(host: Arc<SessionHost>) -> BufReader<DuplexStream> {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(async move {
+            let (reader, writer) = tokio::io::split(server);
+            serve_link(BufReader::new(reader), writer, host).await;
+        });
+        BufReader::new(client)
+    }
+
+    async fn send_line(client: &mut BufReader<DuplexStream>, line: &str) -> HostMessage {
+        client
+            .get_mut()
+            .write_all(format!("{}\n", line).as_bytes())
+            .await
+            .unwrap();
+        read_message(client).await
+    }
+
+    async fn read_message(client: &mut BufReader<DuplexStream>) -> HostMessage {
+        let mut message = String::new();
+        client.read_line(&mut message).await.unwrap();
+        serde_json::from_str(&message).unwrap()
+    }
+
+    async fn request(client: &mut BufReader<DuplexStream>, line: &str) -> (u64, IpcResponse) {
+        match send_line(client, line).await {
+            HostMessage::Reply { id, response } => (id, response),
+            other => panic!("Expected reply, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_errors_keep_link() {
+        let mut client = connect(Arc::new(SessionHost::new(None)));
+
+        let (id, response) = request(&mut client, r#"{"id":1,"command":"ping"}"#).await;
+        assert_eq!(id, 1);
+        let status: HostStatus = serde_json::from_value(response.data.unwrap()).unwrap();
+        assert_eq!(status.sessions, 0);
+        assert_eq!(status.pid, std::process::id());
+
+        let (id, response) = request(&mut client, r#"{"id":2,"command":"unknown"}"#).await;
+        assert_eq!(id, 2);
+        assert!(response.error.unwrap().starts_with("Invalid request"));
+
+        let (id, response) = request(&mut client, "not json").await;
+        assert_eq!(id, 0);
+        assert!(!response.success);
+
+        for line in [
+            r#"{"id":3,"command":"write","params":{"session_id":"missing","data":"x"}}"#,
+            r#"{"id":3,"command":"resize","params":{"session_id":"missing","cols":80,"rows":24}}"#,
+            r#"{"id":3,"command":"kill","params":{"session_id":"missing"}}"#,
+            r#"{"id":3,"command":"attach","params":{"session_id":"missing"}}"#,
+            r#"{"id":3,"command":"detach","params":{"session_id":"missing"}}"#,
+        ] {
+            let (id, response) = request(&mut client, line).await;
+            assert_eq!(id, 3);
+            assert_eq!(
+                response.error.as_deref(),
+                Some("Session not found: missing"),
+                "{}",
+                line
+            );
+        }
+
+        // 호스트에 없는 SSH 세션은 SshManager의 에러 그대로
+        let (_, response) = request(
+            &mut client,
+            r#"{"id":4,"command":"ssh","params":{"session_id":"missing","call":{"type":"screen_text"}}}"#,
+        )
+        .await;
+        assert_eq!(
+            response.error.as_deref(),
+            Some("Session not found: missing")
+        );
+
+        let (_, response) = request(&mut client, r#"{"id":5,"command":"list_sessions"}"#).await;
+        assert_eq!(response.data, Some(serde_json::json!([])));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_attach_replays_raw_output() {
+        let host = Arc::new(SessionHost::new(None));
+        let mut client = connect(host.clone());
+
+        // UTF-8이 아닌 바이트(0xFF)를 출력하고 종료
+        let (_, response) = request(
+            &mut client,
+            r#"{"id":1,"command":"create_session","params":{"shell":"/bin/sh","args":["-c","printf 'a\\377b'"],"cols":80,"rows":24}}"#,
+        )
+        .await;
+        assert!(response.success, "{:?}", response.error);
+        let info: HostSessionInfo = serde_json::from_value(response.data.unwrap()).unwrap();
+        assert_eq!(info.kind, HostSessionKind::Pty);
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while !host.session(&info.session_id).unwrap().info().exited {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "session did not exit"
+            );
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        let line = format!(
+            r#"{{"id":2,"command":"attach","params":{{"session_id":"{}"}}}}"#,
+            info.session_id
+        );
+        let (id, response) = request(&mut client, &line).await;
+        assert_eq!(id, 2);
+        let response: HostAttachResponse = serde_json::from_value(response.data.unwrap()).unwrap();
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(response.data)
+            .unwrap();
+        assert_eq!(data, b"a\xffb");
+        assert_eq!(response.end_offset, 3);
+        assert!(!response.truncated);
+
+        // 이미 종료된 세션은 응답 뒤에 종료 메시지를 보냄
+        match read_message(&mut client).await {
+            HostMessage::Exit {
+                session_id,
+                kind,
+                exit_code,
+                ..
+            } => {
+                assert_eq!(session_id, info.session_id);
+                assert_eq!(kind, HostSessionKind::Pty);
+                assert_eq!(exit_code, Some(0));
+            }
+            other => panic!("Expected exit, got {:?}", other),
+        }
+    }
+}
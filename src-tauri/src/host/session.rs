@@ -0,0 +1,456 @@
+use super::protocol::{
+    HostAttachResponse, HostCreateParams, HostError, HostSessionInfo, HostSessionKind,
+    HostSshParams,
+};
+use crate::pty::PtySession;
+use crate::ssh::{self, SshManager, SshOutput};
+use crate::terminal::{Scrollback, ScrollbackLimit, SessionSink, INPUT_CHUNK_SIZE};
+use base64::Engine;
+use portable_pty::{ChildKiller, CommandBuilder, MasterPty, PtySize};
+use std::future::Future;
+use std::io::{Read, Write};
+use std::pin::Pin;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::{broadcast, oneshot};
+
+/// 링크별로 쌓아둘 수 있는 출력 이벤트 수
+/// (링크가 따라오지 못하면 전달을 중단하고, GUI는 end_offset부터 다시 attach)
+const STREAM_CAPACITY: usize = 1024;
+
+/// 세션 출력 이벤트 (attach된 링크들로 브로드캐스트)
+#[derive(Debug, Clone)]
+pub enum HostStreamEvent {
+    /// 출력 (seq는 스크롤백 청크 시퀀스 번호)
+    Output { seq: u64, data: Vec<u8> },
+    /// 프로세스 또는 연결 종료
+    Exit {
+        exit_code: Option<i32>,
+        signal: Option<String>,
+        reason: Option<String>,
+    },
+}
+
+/// 입력 쓰기 완료 대기 (요청 순서대로 넣은 뒤 링크를 막지 않고 기다림)
+pub type WriteWait = Pin<Box<dyn Future<Output = Result<(), HostError>> + Send>>;
+
+/// attach 결과
+pub struct HostAttachment {
+    pub response: HostAttachResponse,
+    pub receiver: broadcast::Receiver<HostStreamEvent>,
+    /// 이미 종료된 세션이면 종료 이벤트 (재생 데이터에 모든 출력이 포함됨)
+    pub exit: Option<HostStreamEvent>,
+}
+
+/// 세션 호스트가 소유하는 세션
+///
+/// GUI 프로세스와 무관하게 살아 있으며, 출력은 스크롤백에 보관하고
+/// attach된 링크들로 브로드캐스트합니다.
+pub struct HostSession {
+    /// 세션 정보 (종료 상태는 reader 스레드 또는 SSH I/O 스레드가 기록)
+    info: Arc<Mutex<HostSessionInfo>>,
+    events: broadcast::Sender<HostStreamEvent>,
+    backend: Backend,
+}
+
+enum Backend {
+    Pty(PtyBackend),
+    /// 세션은 같은 ID로 SshManager에 있음 (I/O 스레드, 스크롤백, 녹화, 입력 감사, 트리거 포함)
+    Ssh(SshManager),
+}
+
+struct PtyBackend {
+    master: Mutex<Box<dyn MasterPty + Send>>,
+    /// writer 스레드로 보내는 입력 (받은 순서대로 씀)
+    input_tx: mpsc::Sender<HostWriteJob>,
+    killer: Mutex<Box<dyn ChildKiller + Send + Sync>>,
+    scrollback: Arc<Mutex<Scrollback>>,
+}
+
+/// PTY 입력 쓰기 요청
+struct HostWriteJob {
+    data: Vec<u8>,
+    done: oneshot::Sender<std::io::Result<()>>,
+}
+
+impl HostSession {
+    /// PTY 세션 생성 및 reader/writer 스레드 시작
+    pub fn spawn(session_id: String, params: HostCreateParams) -> Result<Self, HostError> {
+        let pty_system = portable_pty::native_pty_system();
+        let pty_pair = pty_system
+            .openpty(PtySize {
+                rows: params.rows,
+                cols: params.cols,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .map_err(|e| HostError::CreationFailed(e.to_string()))?;
+
+        let shell = params.shell.unwrap_or_else(PtySession::default_shell);
+        let mut cmd = CommandBuilder::new(&shell);
+        for arg in params.args.unwrap_or_default() {
+            cmd.arg(arg);
+        }
+        if let Some(cwd) = params.cwd {
+            cmd.cwd(cwd);
+        }
+        for (key, value) in params.env.unwrap_or_default() {
+            cmd.env(key, value);
+        }
+
+        let mut child = pty_pair
+            .slave
+            .spawn_command(cmd)
+            .map_err(|e| HostError::CreationFailed(e.to_string()))?;
+        let pid = child
+            .process_id()
+            .ok_or_else(|| HostError::CreationFailed("Failed to get PID".to_string()))?;
+        let killer = child.clone_killer();
+
+        let mut reader = pty_pair
+            .master
+            .try_clone_reader()
+            .map_err(|e| HostError::CreationFailed(e.to_string()))?;
+        let mut writer = pty_pair
+            .master
+            .take_writer()
+            .map_err(|e| HostError::CreationFailed(e.to_string()))?;
+
+        let (events, _) = broadcast::channel(STREAM_CAPACITY);
+        let scrollback = Arc::new(Mutex::new(Scrollback::new(ScrollbackLimit::default())));
+        let info = Arc::new(Mutex::new(new_info(
+            session_id,
+            HostSessionKind::Pty,
+            shell,
+            Some(pid),
+            params.cols,
+            params.rows,
+        )));
+
+        // Writer 스레드: 자식 프로세스가 입력을 읽지 않아도 링크와 세션 맵을 막지 않음
+        let (input_tx, input_rx) = mpsc::channel::<HostWriteJob>();
+        thread::spawn(move || {
+            for job in input_rx {
+                let result = job
+                    .data
+                    .chunks(INPUT_CHUNK_SIZE)
+                    .try_for_each(|chunk| writer.write_all(chunk).and_then(|_| writer.flush()));
+                let _ = job.done.send(result);
+            }
+        });
+
+        // Reader 스레드: 출력 보관/브로드캐스트, 종료 시 자식 프로세스 회수
+        {
+            let info = info.clone();
+            let events = events.clone();
+            let scrollback = scrollback.clone();
+            thread::spawn(move || {
+                let mut buffer = [0u8; 8192];
+                loop {
+                    match reader.read(&mut buffer) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => {
+                            let seq = scrollback.lock().unwrap().push(&buffer[..n]);
+                            // 수신자가 없으면 에러지만 스크롤백에는 남아 있음
+                            let _ = events.send(HostStreamEvent::Output {
+                                seq,
+                                data: buffer[..n].to_vec(),
+                            });
+                        }
+                    }
+                }
+
+                let (exit_code, signal) = match child.wait() {
+                    Ok(status) => PtySession::exit_status_info(&status),
+                    Err(_) => (None, None),
+                };
+                mark_exited(&info, &events, exit_code, signal, None);
+            });
+        }
+
+        Ok(Self {
+            info,
+            events,
+            backend: Backend::Pty(PtyBackend {
+                master: Mutex::new(pty_pair.master),
+                input_tx,
+                killer: Mutex::new(killer),
+                scrollback,
+            }),
+        })
+    }
+
+    /// SSH 세션 연결 (연결과 인증이 끝날 때까지 블로킹)
+    ///
+    /// 세션은 호스트 설정의 스크롤백 한도와 자동 로그 설정을 따르고,
+    /// 트리거/셸 통합 이벤트는 sink로 보냅니다.
+    pub async fn connect(
+        session_id: String,
+        params: HostSshParams,
+        ssh: SshManager,
+        sink: Arc<dyn SessionSink>,
+    ) -> Result<Self, HostError> {
+        let (events, _) = broadcast::channel(STREAM_CAPACITY);
+        let info = Arc::new(Mutex::new(new_info(
+            session_id.clone(),
+            HostSessionKind::Ssh,
+            format!("{}@{}", params.config.username, params.config.host),
+            None,
+            params.cols,
+            params.rows,
+        )));
+
+        let output = Arc::new(HostSshOutput {
+            info: info.clone(),
+            events: events.clone(),
+        });
+        let settings = sink
+            .settings()
+            .map(|settings| settings.get_settings())
+            .unwrap_or_default();
+        ssh.open_session(
+            session_id,
+            params.config,
+            params.cols,
+            params.rows,
+            settings.scrollback_limit,
+            settings.session_logging.auto_options(true),
+            sink,
+            output,
+        )
+        .await
+        .map_err(|e| HostError::CreationFailed(e.to_string()))?;
+
+        Ok(Self {
+            info,
+            events,
+            backend: Backend::Ssh(ssh),
+        })
+    }
+
+    fn session_id(&self) -> String {
+        self.info.lock().unwrap().session_id.clone()
+    }
+
+    /// 세션 정보
+    pub fn info(&self) -> HostSessionInfo {
+        let mut info = self.info.lock().unwrap().clone();
+        info.attached = self.events.receiver_count();
+        info
+    }
+
+    /// 출력 구독 시작
+    ///
+    /// 구독을 먼저 만든 뒤 재생 데이터를 읽으므로 그 사이의 출력은 구독으로 받고,
+    /// seq가 response.next_seq보다 작은 출력은 재생 데이터에 이미 포함되어 있습니다.
+    pub async fn attach(&self, from_offset: Option<u64>) -> Result<HostAttachment, HostError> {
+        let from_offset = from_offset.unwrap_or(0);
+        let receiver = self.events.subscribe();
+
+        // 종료 상태를 재생보다 먼저 확인 (종료 전에 모든 출력이 스크롤백에 들어감)
+        let exit = {
+            let info = self.info.lock().unwrap();
+            info.exited.then(|| HostStreamEvent::Exit {
+                exit_code: info.exit_code,
+                signal: info.signal.clone(),
+                reason: info.reason.clone(),
+            })
+        };
+
+        let (kind, start_offset, data, end_offset, next_seq) = match &self.backend {
+            Backend::Pty(pty) => {
+                let scrollback = pty.scrollback.lock().unwrap();
+                let (start_offset, data) = scrollback.read_bytes(from_offset);
+                (
+                    HostSessionKind::Pty,
+                    start_offset,
+                    data,
+                    scrollback.end_offset(),
+                    scrollback.next_seq(),
+                )
+            }
+            Backend::Ssh(ssh) => {
+                let replay = ssh
+                    .replay_session(&self.session_id(), from_offset)
+                    .await
+                    .map_err(|e| HostError::RequestFailed(e.to_string()))?;
+                (
+                    HostSessionKind::Ssh,
+                    replay.start_offset,
+                    replay.data.into_bytes(),
+                    replay.end_offset,
+                    replay.next_seq,
+                )
+            }
+        };
+
+        Ok(HostAttachment {
+            response: HostAttachResponse {
+                session_id: self.session_id(),
+                kind,
+                data: base64::engine::general_purpose::STANDARD.encode(data),
+                start_offset,
+                end_offset,
+                next_seq,
+                truncated: start_offset > from_offset,
+            },
+            receiver,
+            exit,
+        })
+    }
+
+    /// 입력 쓰기 요청을 넣고 완료 대기 future 반환
+    ///
+    /// 쓰기는 받은 순서대로 처리되며, 프로세스나 원격이 입력을 읽지 않아도 요청은 막히지 않습니다.
+    pub async fn write(&self, data: String) -> Result<WriteWait, HostError> {
+        match &self.backend {
+            Backend::Pty(pty) => {
+                let (done, done_rx) = oneshot::channel();
+                pty.input_tx
+                    .send(HostWriteJob {
+                        data: data.into_bytes(),
+                        done,
+                    })
+                    .map_err(|_| HostError::RequestFailed("PTY writer stopped".to_string()))?;
+                Ok(Box::pin(async move {
+                    done_rx
+                        .await
+                        .map_err(|_| HostError::RequestFailed("PTY writer stopped".to_string()))?
+                        .map_err(|e| HostError::RequestFailed(e.to_string()))
+                }))
+            }
+            Backend::Ssh(ssh) => {
+                let done = ssh
+                    .queue_write(&self.session_id(), &data)
+                    .await
+                    .map_err(|e| HostError::RequestFailed(e.to_string()))?;
+                Ok(Box::pin(async move {
+                    ssh::wait_write(done)
+                        .await
+                        .map_err(|e| HostError::RequestFailed(e.to_string()))
+                }))
+            }
+        }
+    }
+
+    /// 크기 조정
+    pub async fn resize(&self, cols: u16, rows: u16) -> Result<(), HostError> {
+        match &self.backend {
+            Backend::Pty(pty) => pty
+                .master
+                .lock()
+                .unwrap()
+                .resize(PtySize {
+                    rows,
+                    cols,
+                    pixel_width: 0,
+                    pixel_height: 0,
+                })
+                .map_err(|e| HostError::RequestFailed(e.to_string()))?,
+            Backend::Ssh(ssh) => ssh
+                .resize_session(&self.session_id(), cols, rows)
+                .await
+                .map_err(|e| HostError::RequestFailed(e.to_string()))?,
+        }
+
+        let mut info = self.info.lock().unwrap();
+        info.cols = cols;
+        info.rows = rows;
+        Ok(())
+    }
+
+    /// 프로세스 종료 또는 연결 종료 (PTY가 이미 종료된 경우 무시)
+    pub async fn kill(&self) {
+        match &self.backend {
+            Backend::Pty(pty) => {
+                if self.info.lock().unwrap().exited {
+                    return;
+                }
+                if let Err(e) = pty.killer.lock().unwrap().kill() {
+                    eprintln!("Failed to kill host session process: {}", e);
+                }
+            }
+            Backend::Ssh(ssh) => {
+                // 녹화/로그를 마무리하고 세션을 닫음 (I/O 스레드가 종료 이벤트를 보냄)
+                if let Err(e) = ssh.close_session(&self.session_id()).await {
+                    eprintln!("Failed to close host SSH session: {}", e);
+                }
+            }
+        }
+    }
+
+    /// 출력 흐름 제어 해제 (ack하던 링크가 detach한 경우, SSH만 해당)
+    pub async fn release_output(&self) {
+        if let Backend::Ssh(ssh) = &self.backend {
+            let _ = ssh.release_output(&self.session_id()).await;
+        }
+    }
+}
+
+/// SSH I/O 스레드의 출력을 링크들로 브로드캐스트
+struct HostSshOutput {
+    info: Arc<Mutex<HostSessionInfo>>,
+    events: broadcast::Sender<HostStreamEvent>,
+}
+
+impl SshOutput for HostSshOutput {
+    fn frame(&self, seq: u64, frame: Vec<u8>) {
+        let _ = self
+            .events
+            .send(HostStreamEvent::Output { seq, data: frame });
+    }
+
+    fn exit(&self, reason: Option<String>) {
+        mark_exited(&self.info, &self.events, None, None, reason);
+    }
+}
+
+fn new_info(
+    session_id: String,
+    kind: HostSessionKind,
+    shell: String,
+    pid: Option<u32>,
+    cols: u16,
+    rows: u16,
+) -> HostSessionInfo {
+    HostSessionInfo {
+        session_id,
+        kind,
+        shell,
+        pid,
+        cols,
+        rows,
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        attached: 0,
+        exited: false,
+        exit_code: None,
+        signal: None,
+        reason: None,
+    }
+}
+
+/// 종료 상태 기록 후 종료 이벤트 발행 (attach는 구독 후 종료 상태를 확인하므로 누락되지 않음)
+fn mark_exited(
+    info: &Mutex<HostSessionInfo>,
+    events: &broadcast::Sender<HostStreamEvent>,
+    exit_code: Option<i32>,
+    signal: Option<String>,
+    reason: Option<String>,
+) {
+    {
+        let mut info = info.lock().unwrap();
+        info.exited = true;
+        info.exit_code = exit_code;
+        info.signal = signal.clone();
+        info.reason = reason.clone();
+    }
+    let _ = events.send(HostStreamEvent::Exit {
+        exit_code,
+        signal,
+        reason,
+    });
+}
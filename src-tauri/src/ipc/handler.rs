@@ -1,9 +1,11 @@
 use crate::ipc::protocol::{
     AddLocalTabParams, AddSshTabParams, CloseTabParams, IpcCommand, IpcResponse, ListTabsResponse,
-    PingResponse, TabInfo,
+    PingResponse, RunScriptParams, ScreenParams, TabInfo,
 };
+use crate::automation::{self, SessionTarget};
 use crate::ipc::events::{TabCreatedPayload, TabClosedPayload};
-use crate::pty::PtyManager;
+use crate::pty::{LaunchSpec, PtyManager, PtyOutputMode};
+use crate::settings::SettingsManager;
 use crate::ssh::{SshManager, SshOutputEvent};
 use tauri::{AppHandle, Manager, Emitter};
 use uuid::Uuid;
@@ -16,6 +18,13 @@ pub async fn handle_request(command: IpcCommand, app_handle: &AppHandle) -> IpcR
         IpcCommand::AddLocalTab { params } => handle_add_local_tab(params, app_handle).await,
         IpcCommand::CloseTab { params } => handle_close_tab(params, app_handle).await,
         IpcCommand::ListTabs => handle_list_tabs(app_handle).await,
+        IpcCommand::GetScreenText { params } => handle_get_screen_text(params, app_handle).await,
+        IpcCommand::GetScreenCells { params } => handle_get_screen_cells(params, app_handle).await,
+        IpcCommand::RunScript { params } => handle_run_script(params, app_handle).await,
+        // 연결 처리에서 링크로 전환하므로 여기까지 오지 않음
+        IpcCommand::RegisterSessionHost => {
+            IpcResponse::error("register_session_host must be handled by the connection")
+        }
     }
 }
 
@@ -64,6 +73,9 @@ async fn handle_add_ssh_tab(
     let config = params.config.clone();
     let cols = params.cols;
     let rows = params.rows;
+    let settings = app_handle.state::<SettingsManager>().get_settings();
+    let scrollback_limit = settings.scrollback_limit;
+    let log_options = settings.session_logging.auto_options(true);
     let app_handle_clone = app_handle.clone();
     let session_id_clone = session_id.clone();
 
@@ -75,6 +87,9 @@ async fn handle_add_ssh_tab(
                 config.clone(),
                 cols,
                 rows,
+                None,
+                scrollback_limit,
+                log_options,
                 app_handle_clone.clone(),
             )
             .await
@@ -95,6 +110,7 @@ async fn handle_add_ssh_tab(
                     SshOutputEvent {
                         session_id: session_id_clone.clone(),
                         data: error_msg,
+                        seq: 0,
                     },
                 );
             }
@@ -112,16 +128,34 @@ async fn handle_add_local_tab(
 ) -> IpcResponse {
     // PtyManager 가져오기
     let pty_manager = app_handle.state::<PtyManager>();
+    let settings = app_handle.state::<SettingsManager>().get_settings();
+
+    // 실행 프로필 적용 (cwd는 직접 넘긴 값이 우선)
+    let mut launch = LaunchSpec {
+        cwd: params.cwd,
+        ..Default::default()
+    };
+    if let Some(name) = params.profile {
+        match settings.launch_profile(&name) {
+            Some(profile) => launch = launch.with_profile(profile),
+            None => return IpcResponse::error(format!("Launch profile not found: {}", name)),
+        }
+    }
 
     // PTY 세션 생성 (기본 터미널 크기: 80x24)
     match pty_manager
         .create_session(
-            None,  // shell: None (use default)
-            None,  // args: None
-            params.cwd,
-            None,  // env: None
+            launch.shell,  // None이면 기본 셸
+            launch.args,
+            launch.cwd,
+            launch.env,
             80,    // cols: default 80
             24,    // rows: default 24
+            PtyOutputMode::Text,
+            None,  // output_channel: 전역 이벤트 사용
+            settings.scrollback_limit,
+            settings.session_logging.auto_options(false),
+            launch.encoding,
             app_handle.clone(),
         )
         .await
@@ -214,6 +248,50 @@ async fn handle_list_tabs(app_handle: &AppHandle) -> IpcResponse {
     IpcResponse::success(response)
 }
 
+/// get_screen_text 커맨드 처리
+async fn handle_get_screen_text(params: ScreenParams, app_handle: &AppHandle) -> IpcResponse {
+    let tab_id = &params.tab_id;
+
+    let pty_manager = app_handle.state::<PtyManager>();
+    if let Ok(text) = pty_manager.screen_text(tab_id).await {
+        return IpcResponse::success(serde_json::json!({ "text": text }));
+    }
+
+    let ssh_manager = app_handle.state::<SshManager>();
+    match ssh_manager.screen_text(tab_id).await {
+        Ok(text) => IpcResponse::success(serde_json::json!({ "text": text })),
+        Err(_) => IpcResponse::error(format!("Tab not found: {}", tab_id)),
+    }
+}
+
+/// get_screen_cells 커맨드 처리
+async fn handle_get_screen_cells(params: ScreenParams, app_handle: &AppHandle) -> IpcResponse {
+    let tab_id = &params.tab_id;
+
+    let pty_manager = app_handle.state::<PtyManager>();
+    if let Ok(snapshot) = pty_manager.screen_snapshot(tab_id).await {
+        return IpcResponse::success(snapshot);
+    }
+
+    let ssh_manager = app_handle.state::<SshManager>();
+    match ssh_manager.screen_snapshot(tab_id).await {
+        Ok(snapshot) => IpcResponse::success(snapshot),
+        Err(_) => IpcResponse::error(format!("Tab not found: {}", tab_id)),
+    }
+}
+
+/// run_script 커맨드 처리 (스크립트가 끝날 때까지 대기 후 트랜스크립트 반환)
+async fn handle_run_script(params: RunScriptParams, app_handle: &AppHandle) -> IpcResponse {
+    let pty_manager = app_handle.state::<PtyManager>();
+    let ssh_manager = app_handle.state::<SshManager>();
+    let target = SessionTarget::new(params.tab_id, &pty_manager, &ssh_manager);
+
+    match automation::run_script(&target, &params.script).await {
+        Ok(result) => IpcResponse::success(result),
+        Err(e) => IpcResponse::error(e.to_string()),
+    }
+}
+
 // ============================================
 // 단위 테스트
 // ============================================
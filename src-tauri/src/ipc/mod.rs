@@ -1,7 +1,7 @@
 mod protocol;
 mod server;
 mod handler;
-mod platform;
+pub(crate) mod platform;
 mod events;
 
 pub use protocol::{IpcResponse, IpcError, IpcCommand};
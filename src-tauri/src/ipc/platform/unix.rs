@@ -1,6 +1,8 @@
-use std::path::PathBuf;
+use std::io;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, OpenOptionsExt, PermissionsExt};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
 use tokio::net::{UnixListener, UnixStream};
-use std::os::unix::fs::PermissionsExt;
 
 use crate::ipc::IpcError;
 
@@ -10,31 +12,76 @@ pub fn get_socket_path() -> PathBuf {
     PathBuf::from(format!("/tmp/rusterm-{}.sock", uid))
 }
 
+/// 세션 호스트 런타임 디렉터리 ($XDG_RUNTIME_DIR/rusterm, 없으면 /tmp/rusterm-{uid})
+///
+/// 잠금 파일을 다른 사용자가 미리 만들거나 열 수 없도록
+/// 현재 사용자 소유의 0700 디렉터리만 사용합니다.
+fn get_host_runtime_dir() -> io::Result<PathBuf> {
+    let uid = unsafe { libc::getuid() };
+    let dir = match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(runtime_dir) if !runtime_dir.is_empty() => PathBuf::from(runtime_dir).join("rusterm"),
+        _ => PathBuf::from(format!("/tmp/rusterm-{}", uid)),
+    };
+
+    match std::fs::DirBuilder::new().mode(0o700).create(&dir) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e),
+    }
+
+    // 심볼릭 링크나 다른 사용자의 디렉터리는 사용하지 않음
+    let metadata = std::fs::symlink_metadata(&dir)?;
+    if !metadata.is_dir() || metadata.uid() != uid {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "Session host directory is not owned by uid {}: {}",
+                uid,
+                dir.display()
+            ),
+        ));
+    }
+    if metadata.mode() & 0o077 != 0 {
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700))?;
+    }
+
+    Ok(dir)
+}
+
 /// Unix Socket 리스너 생성
 pub async fn create_listener() -> Result<UnixListener, IpcError> {
-    let socket_path = get_socket_path();
+    bind_listener(&get_socket_path()).await
+}
+
+/// 앱의 IPC 소켓에 연결 (같은 사용자가 실행한 앱인지 확인)
+pub async fn connect() -> io::Result<UnixStream> {
+    let stream = UnixStream::connect(get_socket_path()).await?;
+    verify_peer(&stream)?;
+    Ok(stream)
+}
 
+async fn bind_listener(socket_path: &Path) -> Result<UnixListener, IpcError> {
     // 기존 socket 파일 확인 및 제거
     if socket_path.exists() {
         // TODO: 향후 프로세스 존재 여부 확인 로직 추가
-        std::fs::remove_file(&socket_path).map_err(|e| {
+        std::fs::remove_file(socket_path).map_err(|e| {
             IpcError::BindFailed(format!("Failed to remove existing socket: {}", e))
         })?;
     }
 
     // Unix socket 생성
-    let listener = UnixListener::bind(&socket_path).map_err(|e| {
+    let listener = UnixListener::bind(socket_path).map_err(|e| {
         IpcError::BindFailed(format!("Failed to bind to {}: {}", socket_path.display(), e))
     })?;
 
     // 권한 설정: 0600 (소유자만 읽기/쓰기)
-    let metadata = std::fs::metadata(&socket_path).map_err(|e| {
+    let metadata = std::fs::metadata(socket_path).map_err(|e| {
         IpcError::BindFailed(format!("Failed to get socket metadata: {}", e))
     })?;
 
     let mut perms = metadata.permissions();
     perms.set_mode(0o600);
-    std::fs::set_permissions(&socket_path, perms).map_err(|e| {
+    std::fs::set_permissions(socket_path, perms).map_err(|e| {
         IpcError::BindFailed(format!("Failed to set socket permissions: {}", e))
     })?;
 
@@ -53,11 +100,50 @@ pub async fn accept_connection(listener: &UnixListener) -> Result<UnixStream, Ip
     Ok(stream)
 }
 
+/// 상대 프로세스의 uid가 현재 사용자와 같은지 확인 (SO_PEERCRED / getpeereid)
+fn verify_peer(stream: &UnixStream) -> io::Result<()> {
+    let uid = unsafe { libc::getuid() };
+    let peer_uid = stream.peer_cred()?.uid();
+    if peer_uid != uid {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("IPC peer belongs to uid {}", peer_uid),
+        ));
+    }
+    Ok(())
+}
+
+/// 세션 호스트 시작 잠금 (다른 호스트가 이미 잡고 있으면 None)
+///
+/// 반환한 파일을 닫을 때까지 잠금이 유지되고, 프로세스가 종료되면 OS가 해제합니다.
+pub fn lock_host() -> io::Result<Option<std::fs::File>> {
+    let lock_path = get_host_runtime_dir()?.join("host.lock");
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .mode(0o600)
+        .open(lock_path)?;
+
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+        let error = io::Error::last_os_error();
+        if error.kind() == io::ErrorKind::WouldBlock {
+            return Ok(None);
+        }
+        return Err(error);
+    }
+
+    Ok(Some(file))
+}
+
 /// Socket 파일 정리
 pub fn cleanup_socket() {
-    let socket_path = get_socket_path();
+    remove_socket_file(&get_socket_path());
+}
+
+fn remove_socket_file(socket_path: &Path) {
     if socket_path.exists() {
-        let _ = std::fs::remove_file(&socket_path);
+        let _ = std::fs::remove_file(socket_path);
         println!("Cleaned up socket file: {}", socket_path.display());
     }
 }
@@ -1,4 +1,4 @@
-use tokio::net::windows::named_pipe::{NamedPipeServer, ServerOptions};
+use tokio::net::windows::named_pipe::{ClientOptions, NamedPipeClient, NamedPipeServer, ServerOptions};
 use crate::ipc::IpcError;
 
 /// Named Pipe 이름 생성
@@ -29,6 +29,11 @@ pub async fn create_listener() -> Result<NamedPipeServer, IpcError> {
     Ok(server)
 }
 
+/// 앱의 IPC Named Pipe에 연결
+pub async fn connect() -> std::io::Result<NamedPipeClient> {
+    ClientOptions::new().open(get_pipe_path())
+}
+
 /// 연결 수락 (async)
 pub async fn accept_connection(
     server: &mut NamedPipeServer,
@@ -54,6 +59,35 @@ pub async fn create_next_instance() -> Result<NamedPipeServer, IpcError> {
     Ok(server)
 }
 
+/// 세션 호스트 시작 잠금 (다른 호스트가 이미 잡고 있으면 None)
+///
+/// 공유 없이 연 파일을 닫을 때까지 잠금이 유지되고, 프로세스가 종료되면 OS가 해제합니다.
+pub fn lock_host() -> std::io::Result<Option<std::fs::File>> {
+    use std::os::windows::fs::OpenOptionsExt;
+
+    // ERROR_SHARING_VIOLATION
+    const SHARING_VIOLATION: i32 = 32;
+
+    let dir = dirs::data_local_dir()
+        .ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, "No local data directory")
+        })?
+        .join("rusterm");
+    std::fs::create_dir_all(&dir)?;
+
+    match std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .share_mode(0)
+        .open(dir.join("host.lock"))
+    {
+        Ok(file) => Ok(Some(file)),
+        Err(e) if e.raw_os_error() == Some(SHARING_VIOLATION) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
 /// Cleanup (Windows는 프로세스 종료 시 자동 정리)
 pub fn cleanup_socket() {
     // Windows Named Pipe는 OS가 자동으로 정리
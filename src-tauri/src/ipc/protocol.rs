@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use crate::automation::AutomationScript;
 use crate::ssh::SshConfig;
 
 /// IPC 응답 구조
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct IpcResponse {
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -41,6 +42,11 @@ pub enum IpcCommand {
     AddLocalTab { params: AddLocalTabParams },
     CloseTab { params: CloseTabParams },
     ListTabs,
+    GetScreenText { params: ScreenParams },
+    GetScreenCells { params: ScreenParams },
+    RunScript { params: RunScriptParams },
+    /// 세션 호스트 등록 (이후 이 연결은 세션 호스트 링크로 전환됨)
+    RegisterSessionHost,
 }
 
 /// Ping 응답 데이터
@@ -66,6 +72,9 @@ pub struct AddSshTabParams {
 pub struct AddLocalTabParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cwd: Option<String>,
+    /// Settings.launch_profiles의 실행 프로필 이름
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
 }
 
 /// close_tab 파라미터
@@ -75,6 +84,21 @@ pub struct CloseTabParams {
     pub tab_id: String,
 }
 
+/// get_screen_text / get_screen_cells 파라미터
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenParams {
+    pub tab_id: String,
+}
+
+/// run_script 파라미터
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunScriptParams {
+    pub tab_id: String,
+    pub script: AutomationScript,
+}
+
 /// Tab 정보 (list_tabs 응답용)
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -141,6 +165,13 @@ mod tests {
         assert!(matches!(cmd, IpcCommand::Ping));
     }
 
+    #[test]
+    fn test_register_session_host_command() {
+        let json = r#"{"command":"register_session_host"}"#;
+        let cmd: IpcCommand = serde_json::from_str(json).unwrap();
+        assert!(matches!(cmd, IpcCommand::RegisterSessionHost));
+    }
+
     #[test]
     fn test_success_response_serialization() {
         let resp = IpcResponse::success(PingResponse {
@@ -177,6 +208,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_add_local_tab_with_profile() {
+        let json = r#"{
+            "command": "add_local_tab",
+            "params": {
+                "profile": "work"
+            }
+        }"#;
+        let cmd: IpcCommand = serde_json::from_str(json).unwrap();
+        match cmd {
+            IpcCommand::AddLocalTab { params } => {
+                assert_eq!(params.cwd, None);
+                assert_eq!(params.profile, Some("work".to_string()));
+            }
+            _ => panic!("Expected AddLocalTab command"),
+        }
+    }
+
     #[test]
     fn test_add_ssh_tab_command() {
         let json = r#"{
@@ -202,4 +251,28 @@ mod tests {
             _ => panic!("Expected AddSshTab command"),
         }
     }
+
+    #[test]
+    fn test_run_script_command() {
+        let json = r##"{
+            "command": "run_script",
+            "params": {
+                "tabId": "tab-1",
+                "script": {
+                    "steps": [
+                        {"type": "send", "text": "show version\r"},
+                        {"type": "expect", "alternatives": [{"pattern": "# "}], "timeoutMs": 5000}
+                    ]
+                }
+            }
+        }"##;
+        let cmd: IpcCommand = serde_json::from_str(json).unwrap();
+        match cmd {
+            IpcCommand::RunScript { params } => {
+                assert_eq!(params.tab_id, "tab-1");
+                assert_eq!(params.script.steps.len(), 2);
+            }
+            _ => panic!("Expected RunScript command"),
+        }
+    }
 }
@@ -1,7 +1,9 @@
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
 use tokio::sync::oneshot;
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 
-use crate::ipc::{IpcError, handler, platform};
+use crate::host::HostClient;
+use crate::ipc::{IpcCommand, IpcError, IpcResponse, handler, platform};
 
 /// IPC 서버 구조체
 pub struct IpcServer {
@@ -129,40 +131,26 @@ async fn run_server(mut shutdown_rx: oneshot::Receiver<()>, app_handle: AppHandl
 /// Unix 연결 처리 (async)
 #[cfg(unix)]
 async fn handle_connection_unix(stream: tokio::net::UnixStream, app_handle: AppHandle) {
-    use tokio::io::{AsyncWriteExt, BufReader};
-
-    let (reader, mut writer) = stream.into_split();
-    let mut reader = BufReader::new(reader);
-    let mut line = String::new();
-
-    loop {
-        line.clear();
-
-        match reader.read_line(&mut line).await {
-            Ok(0) => break, // EOF
-            Ok(_) => {
-                let response = process_request(&line, &app_handle).await;
-                let response_json = serde_json::to_string(&response).unwrap() + "\n";
-
-                if let Err(e) = writer.write_all(response_json.as_bytes()).await {
-                    eprintln!("Failed to write response: {}", e);
-                    break;
-                }
-            }
-            Err(e) => {
-                eprintln!("Failed to read from connection: {}", e);
-                break;
-            }
-        }
-    }
+    let (reader, writer) = stream.into_split();
+    handle_connection(reader, writer, app_handle).await;
 }
 
 /// Windows 연결 처리 (async)
 #[cfg(windows)]
 async fn handle_connection_windows(server: tokio::net::windows::named_pipe::NamedPipeServer, app_handle: AppHandle) {
-    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    let (reader, writer) = tokio::io::split(server);
+    handle_connection(reader, writer, app_handle).await;
+}
 
-    let mut reader = BufReader::new(server);
+/// 연결 처리 (공통)
+///
+/// 세션 호스트가 register_session_host를 보내면 응답 후 연결을 HostClient에 넘깁니다.
+async fn handle_connection<R, W>(reader: R, mut writer: W, app_handle: AppHandle)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let mut reader = BufReader::new(reader);
     let mut line = String::new();
 
     loop {
@@ -171,13 +159,25 @@ async fn handle_connection_windows(server: tokio::net::windows::named_pipe::Name
         match reader.read_line(&mut line).await {
             Ok(0) => break, // EOF
             Ok(_) => {
-                let response = process_request(&line, &app_handle).await;
+                let command = parse_request(&line);
+                let register = matches!(command, Ok(IpcCommand::RegisterSessionHost));
+                let response = match command {
+                    Ok(IpcCommand::RegisterSessionHost) => IpcResponse::success(()),
+                    Ok(command) => handler::handle_request(command, &app_handle).await,
+                    Err(response) => response,
+                };
                 let response_json = serde_json::to_string(&response).unwrap() + "\n";
 
-                if let Err(e) = reader.get_mut().write_all(response_json.as_bytes()).await {
+                if let Err(e) = writer.write_all(response_json.as_bytes()).await {
                     eprintln!("Failed to write response: {}", e);
                     break;
                 }
+
+                if register {
+                    let host = app_handle.state::<HostClient>();
+                    host.link(reader, writer, app_handle.clone());
+                    return;
+                }
             }
             Err(e) => {
                 eprintln!("Failed to read from connection: {}", e);
@@ -187,17 +187,13 @@ async fn handle_connection_windows(server: tokio::net::windows::named_pipe::Name
     }
 }
 
-/// 요청 처리 (공통)
-async fn process_request(line: &str, app_handle: &AppHandle) -> crate::ipc::IpcResponse {
-    use crate::ipc::{IpcCommand, IpcResponse};
-
+/// 요청 파싱 (실패하면 에러 응답)
+fn parse_request(line: &str) -> Result<IpcCommand, IpcResponse> {
     let line = line.trim();
     if line.is_empty() {
-        return IpcResponse::error("Empty request");
+        return Err(IpcResponse::error("Empty request"));
     }
 
-    match serde_json::from_str::<IpcCommand>(line) {
-        Ok(command) => handler::handle_request(command, app_handle).await,
-        Err(e) => IpcResponse::error(format!("Invalid JSON: {}", e)),
-    }
+    serde_json::from_str::<IpcCommand>(line)
+        .map_err(|e| IpcResponse::error(format!("Invalid JSON: {}", e)))
 }
@@ -1,11 +1,19 @@
+mod automation;
+mod broadcast;
 mod commands;
 mod fs;
+mod host;
+mod playback;
 mod pty;
 mod settings;
 mod sftp;
 mod ssh;
+mod terminal;
 mod ipc;
 
+use broadcast::BroadcastManager;
+use host::HostClient;
+use playback::PlaybackManager;
 use pty::PtyManager;
 use settings::SettingsManager;
 use sftp::SftpManager;
@@ -13,12 +21,20 @@ use ssh::SshManager;
 use ipc::IpcServer;
 use std::sync::{Arc, Mutex};
 
+/// 백그라운드 세션 호스트 실행 (`rusterm --session-host`, 창 없이 PTY/SSH 세션 소유)
+pub fn run_session_host() -> Result<(), String> {
+    host::run_session_host().map_err(|e| e.to_string())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Initialize settings manager
     let settings_manager =
         SettingsManager::new().expect("Failed to initialize settings manager");
 
+    // 세션 호스트 클라이언트 (호스트의 SSH 세션은 SshManager를 통해서도 다룸)
+    let host_client = HostClient::new();
+
     // IPC 서버 상태 관리
     let ipc_server: Arc<Mutex<Option<IpcServer>>> = Arc::new(Mutex::new(None));
     let ipc_server_clone = ipc_server.clone();
@@ -27,9 +43,13 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .manage(PtyManager::new())
-        .manage(SshManager::new())
+        .manage(SshManager::with_host(host_client.clone()))
         .manage(SftpManager::new())
+        .manage(PlaybackManager::new())
+        .manage(BroadcastManager::new())
+        .manage(host_client)
         .manage(settings_manager)
         .setup(move |app| {
             // IPC 서버 시작 (비동기 실행)
@@ -63,9 +83,15 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             commands::pty_commands::create_pty,
+            commands::pty_commands::list_available_shells,
             commands::pty_commands::write_to_pty,
+            commands::pty_commands::write_bytes_to_pty,
             commands::pty_commands::resize_pty,
             commands::pty_commands::close_pty,
+            commands::pty_commands::signal_pty,
+            commands::pty_commands::get_pty_process_info,
+            commands::pty_commands::get_pty_resource_usage,
+            commands::pty_commands::set_pty_resource_interval,
             commands::settings_commands::load_settings,
             commands::settings_commands::save_settings,
             commands::settings_commands::reset_settings,
@@ -75,8 +101,58 @@ pub fn run() {
             // SSH commands
             commands::ssh_commands::create_ssh_session,
             commands::ssh_commands::write_to_ssh,
+            commands::ssh_commands::write_bytes_to_ssh,
             commands::ssh_commands::resize_ssh_session,
             commands::ssh_commands::close_ssh_session,
+            // Session commands (PTY/SSH 공통)
+            commands::session_commands::replay_session,
+            commands::session_commands::set_session_encoding,
+            commands::session_commands::start_recording,
+            commands::session_commands::stop_recording,
+            commands::session_commands::start_session_log,
+            commands::session_commands::stop_session_log,
+            commands::session_commands::ack_output,
+            commands::session_commands::get_screen_text,
+            commands::session_commands::get_screen_cells,
+            commands::session_commands::search_session,
+            commands::session_commands::get_shell_state,
+            commands::session_commands::get_shell_integration_script,
+            commands::session_commands::inject_shell_integration,
+            commands::session_commands::list_command_history,
+            commands::session_commands::list_host_command_history,
+            commands::session_commands::export_input_audit,
+            commands::session_commands::set_session_monitor,
+            commands::session_commands::get_session_monitor,
+            // Automation commands
+            commands::automation_commands::run_automation_script,
+            // Broadcast commands
+            commands::broadcast_commands::create_broadcast_group,
+            commands::broadcast_commands::delete_broadcast_group,
+            commands::broadcast_commands::list_broadcast_groups,
+            commands::broadcast_commands::add_broadcast_members,
+            commands::broadcast_commands::remove_broadcast_members,
+            commands::broadcast_commands::set_broadcast_member_paused,
+            commands::broadcast_commands::write_to_broadcast_group,
+            // Session host commands
+            commands::host_commands::start_session_host,
+            commands::host_commands::get_session_host_status,
+            commands::host_commands::stop_session_host,
+            commands::host_commands::create_host_session,
+            commands::host_commands::create_host_ssh_session,
+            commands::host_commands::list_host_sessions,
+            commands::host_commands::attach_host_session,
+            commands::host_commands::detach_host_session,
+            commands::host_commands::write_to_host_session,
+            commands::host_commands::resize_host_session,
+            commands::host_commands::kill_host_session,
+            // Playback commands
+            commands::playback_commands::create_playback,
+            commands::playback_commands::get_playback_status,
+            commands::playback_commands::pause_playback,
+            commands::playback_commands::resume_playback,
+            commands::playback_commands::seek_playback,
+            commands::playback_commands::set_playback_speed,
+            commands::playback_commands::close_playback,
             // Local file system commands
             commands::fs_commands::get_user_home_dir,
             commands::fs_commands::list_local_directory,
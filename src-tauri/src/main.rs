@@ -2,5 +2,14 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // 백그라운드 세션 호스트 모드 (GUI 없이 세션만 유지)
+    if std::env::args().any(|arg| arg == "--session-host") {
+        if let Err(e) = rusterm_lib::run_session_host() {
+            eprintln!("Session host error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     rusterm_lib::run()
 }
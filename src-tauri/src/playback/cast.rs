@@ -0,0 +1,153 @@
+use super::types::PlaybackError;
+use serde::Deserialize;
+
+/// 이벤트 시각과 idle 시간 제한의 최대값 (초, 1년)
+const MAX_EVENT_TIME: f64 = 365.0 * 24.0 * 3600.0;
+
+/// asciicast v2 헤더 (재생에 필요한 필드만)
+#[derive(Debug, Deserialize)]
+pub struct CastHeader {
+    pub version: u32,
+    pub width: u16,
+    pub height: u16,
+    #[serde(default)]
+    pub idle_time_limit: Option<f64>,
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+/// 재생할 출력 프레임
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastFrame {
+    /// 재생 시각 (초, idle 시간 제한 적용 후)
+    pub time: f64,
+    pub data: String,
+}
+
+/// 파싱된 녹화 파일
+#[derive(Debug)]
+pub struct Cast {
+    pub header: CastHeader,
+    pub frames: Vec<CastFrame>,
+}
+
+impl Cast {
+    /// asciicast v2 텍스트 파싱
+    ///
+    /// 출력("o") 이벤트만 프레임으로 만들고, idle_time_limit(인자 우선, 없으면 헤더 값)보다
+    /// 긴 이벤트 간격은 그 값으로 줄입니다.
+    pub fn parse(content: &str, idle_time_limit: Option<f64>) -> Result<Self, PlaybackError> {
+        let mut lines = content
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty());
+
+        let (_, header_line) = lines
+            .next()
+            .ok_or_else(|| PlaybackError::InvalidFormat("Empty file".to_string()))?;
+        let header: CastHeader = serde_json::from_str(header_line)
+            .map_err(|e| PlaybackError::InvalidFormat(format!("Invalid header: {}", e)))?;
+        if header.version != 2 {
+            return Err(PlaybackError::InvalidFormat(format!(
+                "Unsupported version: {}",
+                header.version
+            )));
+        }
+
+        let idle_limit = idle_time_limit.or(header.idle_time_limit);
+        if let Some(limit) = idle_limit.filter(|limit| !valid_time(*limit)) {
+            return Err(PlaybackError::InvalidFormat(format!(
+                "Invalid idle time limit: {}",
+                limit
+            )));
+        }
+        let mut frames = Vec::new();
+        let mut last_raw_time = 0.0;
+        let mut time = 0.0;
+
+        for (index, line) in lines {
+            let (raw_time, kind, data): (f64, String, String) = serde_json::from_str(line)
+                .map_err(|e| PlaybackError::InvalidFormat(format!("Line {}: {}", index + 1, e)))?;
+            if !valid_time(raw_time) {
+                return Err(PlaybackError::InvalidFormat(format!(
+                    "Line {}: invalid time {}",
+                    index + 1,
+                    raw_time
+                )));
+            }
+
+            let gap = (raw_time - last_raw_time).max(0.0);
+            time += match idle_limit {
+                Some(limit) => gap.min(limit),
+                None => gap,
+            };
+            last_raw_time = raw_time;
+
+            if kind == "o" {
+                frames.push(CastFrame { time, data });
+            }
+        }
+
+        Ok(Self { header, frames })
+    }
+
+    /// 전체 재생 시간 (초)
+    pub fn duration(&self) -> f64 {
+        self.frames.last().map(|f| f.time).unwrap_or(0.0)
+    }
+}
+
+/// 0 이상 MAX_EVENT_TIME 이하의 유한한 시각인지
+fn valid_time(time: f64) -> bool {
+    (0.0..=MAX_EVENT_TIME).contains(&time)
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAST: &str = r#"{"version": 2, "width": 80, "height": 24, "idle_time_limit": 2.0}
+[0.5, "o", "hello "]
+[0.6, "i", "x"]
+[10.0, "o", "world"]
+"#;
+
+    #[test]
+    fn test_parse_with_header_idle_limit() {
+        let cast = Cast::parse(CAST, None).unwrap();
+        assert_eq!(cast.header.width, 80);
+        assert_eq!(cast.frames.len(), 2); // 입력 이벤트는 제외
+        assert_eq!(cast.frames[0].time, 0.5);
+        // 0.6 -> 10.0 간격(9.4초)이 2초로 제한됨
+        assert!((cast.frames[1].time - 2.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_idle_limit_override() {
+        let cast = Cast::parse(CAST, Some(0.1)).unwrap();
+        assert!((cast.duration() - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rejects_invalid_times() {
+        let header = r#"{"version": 2, "width": 80, "height": 24}"#;
+        for event in [r#"[1e300, "o", "x"]"#, r#"[-1.0, "o", "x"]"#] {
+            let result = Cast::parse(&format!("{}\n{}\n", header, event), None);
+            assert!(matches!(result, Err(PlaybackError::InvalidFormat(_))));
+        }
+        assert!(matches!(
+            Cast::parse(header, Some(f64::INFINITY)),
+            Err(PlaybackError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_other_versions() {
+        let result = Cast::parse(r#"{"version": 1, "width": 80, "height": 24}"#, None);
+        assert!(matches!(result, Err(PlaybackError::InvalidFormat(_))));
+    }
+}
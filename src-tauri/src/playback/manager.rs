@@ -0,0 +1,142 @@
+use super::cast::Cast;
+use super::session::{validate_speed, PlaybackSession};
+use super::types::{CreatePlaybackResponse, PlaybackError, PlaybackStatus};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tauri::ipc::Channel;
+use tauri::AppHandle;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// 녹화 재생 세션 관리자
+pub struct PlaybackManager {
+    sessions: Arc<Mutex<HashMap<String, PlaybackSession>>>,
+}
+
+impl PlaybackManager {
+    /// 새 Playback Manager 생성
+    pub fn new() -> Self {
+        PlaybackManager {
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// .cast 파일을 읽어 재생 세션 생성
+    ///
+    /// idle_time_limit을 생략하면 파일 헤더의 값을 사용합니다.
+    pub async fn create_session(
+        &self,
+        path: &str,
+        speed: f64,
+        idle_time_limit: Option<f64>,
+        autoplay: bool,
+        output_channel: Option<Channel>,
+        app_handle: AppHandle,
+    ) -> Result<CreatePlaybackResponse, PlaybackError> {
+        validate_speed(speed)?;
+        if let Some(limit) = idle_time_limit {
+            if !limit.is_finite() || limit <= 0.0 {
+                return Err(PlaybackError::InvalidOption(format!(
+                    "Invalid idle time limit: {}",
+                    limit
+                )));
+            }
+        }
+
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| PlaybackError::LoadFailed(format!("{}: {}", path, e)))?;
+        let cast = Cast::parse(&content, idle_time_limit)?;
+
+        // 고유 재생 ID 생성
+        let playback_id = Uuid::new_v4().to_string();
+
+        let response = CreatePlaybackResponse {
+            playback_id: playback_id.clone(),
+            width: cast.header.width,
+            height: cast.header.height,
+            duration: cast.duration(),
+            title: cast.header.title.clone(),
+        };
+
+        let session = PlaybackSession::new(
+            playback_id.clone(),
+            cast,
+            speed,
+            autoplay,
+            output_channel,
+            app_handle,
+        );
+
+        let mut sessions = self.sessions.lock().await;
+        sessions.insert(playback_id, session);
+
+        Ok(response)
+    }
+
+    /// 재생 상태 조회
+    pub async fn status(&self, playback_id: &str) -> Result<PlaybackStatus, PlaybackError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(playback_id)
+            .ok_or_else(|| PlaybackError::SessionNotFound(playback_id.to_string()))?;
+
+        Ok(session.status())
+    }
+
+    /// 일시정지
+    pub async fn pause(&self, playback_id: &str) -> Result<(), PlaybackError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(playback_id)
+            .ok_or_else(|| PlaybackError::SessionNotFound(playback_id.to_string()))?;
+
+        session.pause()
+    }
+
+    /// 재생 재개
+    pub async fn resume(&self, playback_id: &str) -> Result<(), PlaybackError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(playback_id)
+            .ok_or_else(|| PlaybackError::SessionNotFound(playback_id.to_string()))?;
+
+        session.resume()
+    }
+
+    /// 재생 위치 이동 (초)
+    pub async fn seek(&self, playback_id: &str, position: f64) -> Result<(), PlaybackError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(playback_id)
+            .ok_or_else(|| PlaybackError::SessionNotFound(playback_id.to_string()))?;
+
+        session.seek(position)
+    }
+
+    /// 재생 속도 배율 변경
+    pub async fn set_speed(&self, playback_id: &str, speed: f64) -> Result<(), PlaybackError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(playback_id)
+            .ok_or_else(|| PlaybackError::SessionNotFound(playback_id.to_string()))?;
+
+        session.set_speed(speed)
+    }
+
+    /// 재생 세션 종료 (재생 스레드도 함께 종료)
+    pub async fn close_session(&self, playback_id: &str) -> Result<(), PlaybackError> {
+        let mut sessions = self.sessions.lock().await;
+        sessions
+            .remove(playback_id)
+            .ok_or_else(|| PlaybackError::SessionNotFound(playback_id.to_string()))?;
+
+        Ok(())
+    }
+}
+
+impl Default for PlaybackManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,7 @@
+mod cast;
+mod manager;
+mod session;
+mod types;
+
+pub use manager::PlaybackManager;
+pub use types::{CreatePlaybackResponse, PlaybackStatus};
@@ -0,0 +1,280 @@
+use super::cast::{Cast, CastFrame};
+use super::types::{PlaybackError, PlaybackStatus};
+use crate::pty::{PtyExitEvent, PtyOutputEvent};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::ipc::{Channel, InvokeResponseBody};
+use tauri::{AppHandle, Emitter};
+
+/// 다음 프레임을 한 번에 기다리는 최대 시간 (느린 속도로 긴 간격을 재생할 때 나눠 기다림)
+const MAX_FRAME_WAIT: Duration = Duration::from_secs(3600);
+
+/// 탐색(seek) 시 화면을 초기화하는 시퀀스 (RIS)
+const RESET_SEQUENCE: &str = "\x1bc";
+
+/// 재생 스레드 제어 명령
+enum PlaybackCommand {
+    Pause,
+    Resume,
+    Seek(f64),
+    SetSpeed(f64),
+}
+
+/// 녹화 파일 재생 세션
+///
+/// 가상의 PTY 세션처럼 동작하며, 녹화된 출력을 원래 타이밍대로 출력 채널
+/// (채널이 없으면 `pty-output-{playback_id}` 이벤트)로 보내고 끝나면 `pty-exit-{playback_id}`를 보냅니다.
+/// 세션이 drop되면 제어 채널이 끊겨 재생 스레드도 종료됩니다.
+pub struct PlaybackSession {
+    command_tx: Sender<PlaybackCommand>,
+    status: Arc<Mutex<PlaybackStatus>>,
+}
+
+impl PlaybackSession {
+    /// 재생 세션 생성 및 재생 스레드 시작
+    pub fn new(
+        playback_id: String,
+        cast: Cast,
+        speed: f64,
+        autoplay: bool,
+        output_channel: Option<Channel>,
+        app_handle: AppHandle,
+    ) -> Self {
+        let (command_tx, command_rx) = mpsc::channel();
+        let status = Arc::new(Mutex::new(PlaybackStatus {
+            playback_id: playback_id.clone(),
+            position: 0.0,
+            duration: cast.duration(),
+            playing: autoplay,
+            speed,
+            finished: cast.frames.is_empty(),
+        }));
+
+        let player = Player {
+            playback_id,
+            frames: cast.frames,
+            index: 0,
+            position: 0.0,
+            seq: 0,
+            status: status.clone(),
+            output_channel,
+            app_handle,
+        };
+        thread::spawn(move || player.run(command_rx));
+
+        Self { command_tx, status }
+    }
+
+    /// 현재 재생 상태
+    pub fn status(&self) -> PlaybackStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// 일시정지
+    pub fn pause(&self) -> Result<(), PlaybackError> {
+        self.send(PlaybackCommand::Pause)
+    }
+
+    /// 재생 (끝까지 재생한 상태면 처음부터 다시 재생)
+    pub fn resume(&self) -> Result<(), PlaybackError> {
+        self.send(PlaybackCommand::Resume)
+    }
+
+    /// 지정한 위치(초)로 이동
+    pub fn seek(&self, position: f64) -> Result<(), PlaybackError> {
+        if !position.is_finite() {
+            return Err(PlaybackError::InvalidOption(format!(
+                "Invalid position: {}",
+                position
+            )));
+        }
+        self.send(PlaybackCommand::Seek(position))
+    }
+
+    /// 재생 속도 배율 변경
+    pub fn set_speed(&self, speed: f64) -> Result<(), PlaybackError> {
+        validate_speed(speed)?;
+        self.send(PlaybackCommand::SetSpeed(speed))
+    }
+
+    fn send(&self, command: PlaybackCommand) -> Result<(), PlaybackError> {
+        self.command_tx
+            .send(command)
+            .map_err(|_| PlaybackError::ControlFailed("Playback thread has stopped".to_string()))
+    }
+}
+
+/// 재생 속도 배율 검증 (0보다 큰 유한한 값)
+pub fn validate_speed(speed: f64) -> Result<(), PlaybackError> {
+    if speed.is_finite() && speed > 0.0 {
+        Ok(())
+    } else {
+        Err(PlaybackError::InvalidOption(format!(
+            "Invalid speed: {}",
+            speed
+        )))
+    }
+}
+
+/// 재생 스레드 상태
+struct Player {
+    playback_id: String,
+    frames: Vec<CastFrame>,
+    /// 다음에 보낼 프레임 인덱스
+    index: usize,
+    /// 현재 재생 위치 (녹화 기준 초)
+    position: f64,
+    /// 다음 출력 이벤트 시퀀스 번호
+    seq: u64,
+    status: Arc<Mutex<PlaybackStatus>>,
+    /// PTY 세션과 같은 출력 채널 (원시 바이트, n번째 메시지가 seq n)
+    output_channel: Option<Channel>,
+    app_handle: AppHandle,
+}
+
+impl Player {
+    /// 재생 루프
+    ///
+    /// 재생 중에는 다음 프레임 시각까지 제어 명령을 기다리고,
+    /// 시간이 되면 그 시각까지의 프레임을 한 번에 보냅니다.
+    fn run(mut self, command_rx: Receiver<PlaybackCommand>) {
+        loop {
+            let (playing, speed) = {
+                let status = self.status.lock().unwrap();
+                (status.playing, status.speed)
+            };
+
+            let command = if playing && !self.finished() {
+                let wait = (self.frames[self.index].time - self.position).max(0.0) / speed;
+                let timeout = Duration::try_from_secs_f64(wait)
+                    .map_or(MAX_FRAME_WAIT, |wait| wait.min(MAX_FRAME_WAIT));
+                let started_at = Instant::now();
+
+                match command_rx.recv_timeout(timeout) {
+                    Ok(command) => {
+                        // 명령을 받기 전까지 흐른 시간만큼 위치 이동
+                        let elapsed = started_at.elapsed().as_secs_f64() * speed;
+                        self.position = (self.position + elapsed).min(self.frames[self.index].time);
+                        command
+                    }
+                    Err(RecvTimeoutError::Timeout) if timeout == MAX_FRAME_WAIT => {
+                        // 프레임 시각 전이면 기다린 만큼만 위치를 옮기고 다시 대기
+                        let elapsed = timeout.as_secs_f64() * speed;
+                        self.position = (self.position + elapsed).min(self.frames[self.index].time);
+                        continue;
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        self.position = self.frames[self.index].time;
+                        self.emit_until(self.position);
+                        if self.finished() {
+                            self.emit_exit();
+                        }
+                        self.update_status(|status| status.playing = !self.finished());
+                        continue;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            } else {
+                match command_rx.recv() {
+                    Ok(command) => command,
+                    Err(_) => return,
+                }
+            };
+
+            match command {
+                PlaybackCommand::Pause => self.update_status(|status| status.playing = false),
+                PlaybackCommand::Resume => {
+                    if self.finished() {
+                        self.seek(0.0);
+                    }
+                    self.update_status(|status| status.playing = true);
+                }
+                PlaybackCommand::Seek(position) => {
+                    self.seek(position);
+                    self.update_status(|_| {});
+                }
+                PlaybackCommand::SetSpeed(speed) => {
+                    self.update_status(|status| status.speed = speed)
+                }
+            }
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.index >= self.frames.len()
+    }
+
+    /// 화면을 초기화한 뒤 position까지의 출력을 한 번에 보내고 그 위치부터 이어서 재생
+    fn seek(&mut self, position: f64) {
+        let duration = self.frames.last().map(|f| f.time).unwrap_or(0.0);
+        self.position = position.clamp(0.0, duration);
+        self.index = 0;
+
+        let mut data = RESET_SEQUENCE.to_string();
+        data.push_str(&self.take_until(self.position));
+        self.emit_output(data);
+    }
+
+    /// time 이하 시각의 프레임을 출력 이벤트로 전송
+    fn emit_until(&mut self, time: f64) {
+        let data = self.take_until(time);
+        if !data.is_empty() {
+            self.emit_output(data);
+        }
+    }
+
+    /// 현재 인덱스부터 time 이하 시각의 프레임 데이터를 모아 반환
+    fn take_until(&mut self, time: f64) -> String {
+        let mut data = String::new();
+        while let Some(frame) = self.frames.get(self.index) {
+            if frame.time > time {
+                break;
+            }
+            data.push_str(&frame.data);
+            self.index += 1;
+        }
+        data
+    }
+
+    fn emit_output(&mut self, data: String) {
+        let seq = self.seq;
+        self.seq += 1;
+
+        if let Some(channel) = &self.output_channel {
+            if let Err(e) = channel.send(InvokeResponseBody::Raw(data.into_bytes())) {
+                eprintln!("Failed to send playback output to channel: {}", e);
+            }
+            return;
+        }
+
+        let _ = self.app_handle.emit(
+            &format!("pty-output-{}", self.playback_id),
+            PtyOutputEvent {
+                pty_id: self.playback_id.clone(),
+                data,
+                seq,
+            },
+        );
+    }
+
+    fn emit_exit(&self) {
+        let _ = self.app_handle.emit(
+            &format!("pty-exit-{}", self.playback_id),
+            PtyExitEvent {
+                pty_id: self.playback_id.clone(),
+                exit_code: Some(0),
+                signal: None,
+            },
+        );
+    }
+
+    /// 공유 상태 갱신 (위치와 완료 여부는 항상 반영)
+    fn update_status(&self, update: impl FnOnce(&mut PlaybackStatus)) {
+        let mut status = self.status.lock().unwrap();
+        update(&mut status);
+        status.position = self.position;
+        status.finished = self.finished();
+    }
+}
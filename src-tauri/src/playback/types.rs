@@ -0,0 +1,53 @@
+use serde::Serialize;
+use thiserror::Error;
+
+/// 재생 세션 생성 응답
+///
+/// playback_id는 PTY 세션 ID처럼 사용되며, 출력은 `pty-output-{playback_id}` 이벤트로 전달됩니다.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatePlaybackResponse {
+    pub playback_id: String,
+    pub width: u16,
+    pub height: u16,
+    /// 전체 재생 시간 (초, idle 시간 제한 적용 후)
+    pub duration: f64,
+    pub title: Option<String>,
+}
+
+/// 재생 상태
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaybackStatus {
+    pub playback_id: String,
+    /// 현재 재생 위치 (초)
+    pub position: f64,
+    pub duration: f64,
+    pub playing: bool,
+    pub speed: f64,
+    /// 마지막 프레임까지 재생 완료
+    pub finished: bool,
+}
+
+/// 재생 에러 타입
+#[derive(Debug, Error)]
+pub enum PlaybackError {
+    #[error("Playback not found: {0}")]
+    SessionNotFound(String),
+
+    #[error("Failed to load recording: {0}")]
+    LoadFailed(String),
+
+    #[error("Invalid asciicast file: {0}")]
+    InvalidFormat(String),
+
+    #[error("Invalid playback option: {0}")]
+    InvalidOption(String),
+
+    #[error("Failed to control playback: {0}")]
+    ControlFailed(String),
+}
+
+impl From<PlaybackError> for String {
+    fn from(err: PlaybackError) -> Self {
+        err.to_string()
+    }
+}
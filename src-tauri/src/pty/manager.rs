@@ -1,25 +1,41 @@
-use super::session::PtySession;
-use super::types::{CreatePtyResponse, PtyError};
+use super::process::ProcessTable;
+use super::session::{PtySession, UsageSampler, WriteDone};
+use super::types::{CreatePtyResponse, PtyError, PtyOutputMode, PtyProcessInfo, PtyResourceUsage};
+use crate::terminal::{
+    CommandHistoryEntry, MonitorConfig, RecordingInfo, ScreenSnapshot, ScrollbackLimit,
+    ScrollbackReplay, SearchOptions, SearchResult, SessionLogInfo, SessionLogOptions, ShellKind,
+    ShellState,
+};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tauri::AppHandle;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::ipc::Channel;
+use tauri::{AppHandle, Emitter};
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
 /// PTY 세션 관리자
 pub struct PtyManager {
     sessions: Arc<Mutex<HashMap<String, PtySession>>>,
+    /// 주기적 자원 사용량 이벤트 태스크
+    resource_task: Mutex<Option<JoinHandle<()>>>,
 }
 
+/// 자원 사용량 이벤트 최소 주기
+const MIN_RESOURCE_INTERVAL: Duration = Duration::from_millis(250);
+
 impl PtyManager {
     /// 새 PTY Manager 생성
     pub fn new() -> Self {
         PtyManager {
             sessions: Arc::new(Mutex::new(HashMap::new())),
+            resource_task: Mutex::new(None),
         }
     }
 
     /// PTY 세션 생성
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_session(
         &self,
         shell: Option<String>,
@@ -28,6 +44,11 @@ impl PtyManager {
         env: Option<HashMap<String, String>>,
         cols: u16,
         rows: u16,
+        output_mode: PtyOutputMode,
+        output_channel: Option<Channel>,
+        scrollback_limit: ScrollbackLimit,
+        log_options: Option<SessionLogOptions>,
+        encoding: Option<String>,
         app_handle: AppHandle,
     ) -> Result<CreatePtyResponse, PtyError> {
         // 고유 PTY ID 생성
@@ -42,6 +63,11 @@ impl PtyManager {
             env,
             cols,
             rows,
+            output_mode,
+            output_channel,
+            scrollback_limit,
+            log_options,
+            encoding,
             app_handle,
         )?;
 
@@ -60,12 +86,48 @@ impl PtyManager {
 
     /// PTY에 데이터 쓰기
     pub async fn write_to_session(&self, pty_id: &str, data: &str) -> Result<(), PtyError> {
+        let done = self.queue_write(pty_id, data).await?;
+
+        // 쓰기가 끝날 때까지 세션 맵 잠금 없이 대기 (시그널, 종료는 기다리지 않음)
+        wait_write(done).await
+    }
+
+    /// PTY 쓰기 요청만 넣고 완료 수신자 반환 (여러 세션에 한꺼번에 쓸 때 사용)
+    pub async fn queue_write(&self, pty_id: &str, data: &str) -> Result<WriteDone, PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+        session.write(data)
+    }
+
+    /// PTY에 바이트 쓰기 (큰 입력은 청크 단위로 쓰고, paste면 필요할 때 bracketed paste로 감쌈)
+    pub async fn write_bytes_to_session(
+        &self,
+        pty_id: &str,
+        data: Vec<u8>,
+        paste: bool,
+    ) -> Result<(), PtyError> {
+        let done = {
+            let sessions = self.sessions.lock().await;
+            let session = sessions
+                .get(pty_id)
+                .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+            session.write_bytes(data, paste)?
+        };
+
+        // 쓰기가 끝날 때까지 세션 맵 잠금 없이 대기
+        wait_write(done).await
+    }
+
+    /// PTY 문자 인코딩 변경
+    pub async fn set_encoding(&self, pty_id: &str, encoding: &str) -> Result<String, PtyError> {
         let sessions = self.sessions.lock().await;
         let session = sessions
             .get(pty_id)
             .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
 
-        session.write(data).await
+        session.set_encoding(encoding)
     }
 
     /// PTY 크기 조정
@@ -78,12 +140,266 @@ impl PtyManager {
         session.resize(cols, rows).await
     }
 
+    /// 출력 이벤트 처리 확인 (흐름 제어)
+    pub async fn ack_output(&self, pty_id: &str, seq: u64) -> Result<(), PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        session.ack_output(seq);
+        Ok(())
+    }
+
+    /// PTY 세션 스크롤백 검색
+    pub async fn search_session(
+        &self,
+        pty_id: &str,
+        pattern: &str,
+        options: &SearchOptions,
+    ) -> Result<SearchResult, PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        session.search(pattern, options)
+    }
+
+    /// PTY 세션 화면 텍스트 조회
+    pub async fn screen_text(&self, pty_id: &str) -> Result<String, PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        Ok(session.screen_text())
+    }
+
+    /// PTY 세션 명령 히스토리 조회
+    pub async fn command_history(
+        &self,
+        pty_id: &str,
+    ) -> Result<Vec<CommandHistoryEntry>, PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        Ok(session.command_history())
+    }
+
+    /// PTY 세션 활동/무출력 감시 설정
+    pub async fn set_monitor(&self, pty_id: &str, config: MonitorConfig) -> Result<(), PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        session.set_monitor(config);
+        Ok(())
+    }
+
+    /// PTY 세션 활동/무출력 감시 설정 조회
+    pub async fn monitor_config(&self, pty_id: &str) -> Result<MonitorConfig, PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        Ok(session.monitor_config())
+    }
+
+    /// PTY 세션 셸 통합 상태 조회
+    pub async fn shell_state(&self, pty_id: &str) -> Result<ShellState, PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        Ok(session.shell_state())
+    }
+
+    /// PTY 세션에 셸 통합 스크립트 주입 (shell이 None이면 세션의 셸로 판별)
+    pub async fn inject_shell_integration(
+        &self,
+        pty_id: &str,
+        shell: Option<ShellKind>,
+    ) -> Result<ShellKind, PtyError> {
+        let (shell, done) = {
+            let sessions = self.sessions.lock().await;
+            let session = sessions
+                .get(pty_id)
+                .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+            let shell = match shell {
+                Some(shell) => shell,
+                None => ShellKind::from_path(&session.shell)
+                    .ok_or_else(|| PtyError::UnsupportedShell(session.shell.clone()))?,
+            };
+            (shell, session.write(&shell.injection_command())?)
+        };
+
+        wait_write(done).await?;
+        Ok(shell)
+    }
+
+    /// PTY 세션 화면 셀 스냅샷 조회
+    pub async fn screen_snapshot(&self, pty_id: &str) -> Result<ScreenSnapshot, PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        Ok(session.screen_snapshot())
+    }
+
+    /// PTY 세션 스크롤백 재생
+    pub async fn replay_session(
+        &self,
+        pty_id: &str,
+        from_offset: u64,
+    ) -> Result<ScrollbackReplay, PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        Ok(session.replay(from_offset))
+    }
+
+    /// PTY 세션 녹화 시작 (asciicast v2)
+    pub async fn start_recording(
+        &self,
+        pty_id: &str,
+        path: Option<String>,
+        title: Option<String>,
+        record_input: bool,
+    ) -> Result<RecordingInfo, PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        session.start_recording(path, title, record_input).await
+    }
+
+    /// PTY 세션 녹화 종료
+    pub async fn stop_recording(&self, pty_id: &str) -> Result<RecordingInfo, PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        session.stop_recording()
+    }
+
+    /// PTY 세션 로그 시작
+    pub async fn start_logging(
+        &self,
+        pty_id: &str,
+        options: SessionLogOptions,
+    ) -> Result<SessionLogInfo, PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        session.start_logging(options)
+    }
+
+    /// PTY 세션 로그 종료
+    pub async fn stop_logging(&self, pty_id: &str) -> Result<SessionLogInfo, PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        session.stop_logging()
+    }
+
+    /// PTY 포그라운드 프로세스 정보 조회
+    pub async fn process_info(&self, pty_id: &str) -> Result<PtyProcessInfo, PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        Ok(session.process_info().await)
+    }
+
+    /// PTY 프로세스 트리 자원 사용량 조회
+    pub async fn resource_usage(&self, pty_id: &str) -> Result<PtyResourceUsage, PtyError> {
+        // /proc 전체를 읽는 블로킹 작업이므로 세션 맵을 잠그기 전에 별도 스레드에서 수행
+        let table = read_process_table().await?;
+
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        Ok(session.resource_usage(&table, UsageSampler::Poll))
+    }
+
+    /// 주기적 자원 사용량 이벤트 설정 (None이면 중지)
+    ///
+    /// 주기마다 모든 PTY 세션의 사용량을 `pty-resource-usage` 이벤트로 한 번에 보냅니다.
+    pub async fn set_resource_interval(&self, interval: Option<Duration>, app_handle: AppHandle) {
+        let mut task = self.resource_task.lock().await;
+        if let Some(previous) = task.take() {
+            previous.abort();
+        }
+
+        let Some(interval) = interval else {
+            return;
+        };
+        let interval = interval.max(MIN_RESOURCE_INTERVAL);
+        let sessions = Arc::clone(&self.sessions);
+
+        *task = Some(tauri::async_runtime::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+
+                let Ok(table) = read_process_table().await else {
+                    continue;
+                };
+                let usage: Vec<PtyResourceUsage> = sessions
+                    .lock()
+                    .await
+                    .values()
+                    .map(|session| session.resource_usage(&table, UsageSampler::Event))
+                    .collect();
+                let _ = app_handle.emit("pty-resource-usage", usage);
+            }
+        }));
+    }
+
+    /// PTY 세션에 시그널 전송
+    pub async fn signal_session(&self, pty_id: &str, signal: &str) -> Result<(), PtyError> {
+        let sessions = self.sessions.lock().await;
+        let session = sessions
+            .get(pty_id)
+            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+
+        session.send_signal(signal).await
+    }
+
     /// PTY 세션 종료
+    ///
+    /// 세션을 맵에서 제거한 뒤 프로세스 그룹을 단계적으로 종료합니다 (SIGHUP -> SIGTERM -> SIGKILL).
     pub async fn close_session(&self, pty_id: &str) -> Result<(), PtyError> {
-        let mut sessions = self.sessions.lock().await;
-        sessions
-            .remove(pty_id)
-            .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?;
+        let session = {
+            let mut sessions = self.sessions.lock().await;
+            sessions
+                .remove(pty_id)
+                .ok_or_else(|| PtyError::SessionNotFound(pty_id.to_string()))?
+        };
+
+        session.terminate().await;
+
+        // reader 스레드가 아직 마무리하지 않았다면 녹화/로그 종료
+        let _ = session.stop_recording();
+        let _ = session.stop_logging();
 
         Ok(())
     }
@@ -95,6 +411,19 @@ impl PtyManager {
     }
 }
 
+/// 프로세스 테이블 읽기 (블로킹 스레드에서)
+async fn read_process_table() -> Result<ProcessTable, PtyError> {
+    tauri::async_runtime::spawn_blocking(ProcessTable::read)
+        .await
+        .map_err(|e| PtyError::PtyError(e.to_string()))
+}
+
+/// writer 스레드의 쓰기 결과 대기
+pub async fn wait_write(done: WriteDone) -> Result<(), PtyError> {
+    done.await
+        .map_err(|_| PtyError::WriteFailed("PTY writer stopped".to_string()))?
+}
+
 impl Default for PtyManager {
     fn default() -> Self {
         Self::new()
@@ -1,6 +1,14 @@
 mod manager;
+mod process;
 mod session;
+mod shells;
+mod signal;
 mod types;
 
-pub use manager::PtyManager;
-pub use types::CreatePtyResponse;
+pub use manager::{wait_write, PtyManager};
+pub(crate) use session::{PtySession, WriteDone};
+pub use shells::{list_available_shells, LaunchProfile, LaunchSpec, ShellInfo};
+pub use types::{
+    CreatePtyResponse, PtyError, PtyExitEvent, PtyOutputEvent, PtyOutputMode, PtyProcessInfo,
+    PtyResourceUsage,
+};
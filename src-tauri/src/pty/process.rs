@@ -0,0 +1,234 @@
+//! 프로세스 정보 조회 (/proc 기반, Linux 전용)
+
+use std::collections::HashMap;
+
+/// /proc에서 읽은 프로세스 정보
+#[derive(Debug, Default)]
+pub struct ProcessDetails {
+    /// 프로세스 이름 (/proc/{pid}/comm)
+    pub name: Option<String>,
+    /// 명령줄 인자 (/proc/{pid}/cmdline)
+    pub cmdline: Vec<String>,
+    /// 작업 디렉토리 (/proc/{pid}/cwd)
+    pub cwd: Option<String>,
+}
+
+/// 프로세스 정보 읽기 (읽을 수 없는 항목은 비워 둠)
+#[cfg(target_os = "linux")]
+pub fn read_process(pid: u32) -> ProcessDetails {
+    let proc_dir = std::path::PathBuf::from(format!("/proc/{}", pid));
+
+    ProcessDetails {
+        name: std::fs::read_to_string(proc_dir.join("comm"))
+            .ok()
+            .map(|name| name.trim_end_matches('\n').to_string()),
+        cmdline: std::fs::read(proc_dir.join("cmdline"))
+            .map(|data| parse_cmdline(&data))
+            .unwrap_or_default(),
+        cwd: std::fs::read_link(proc_dir.join("cwd"))
+            .ok()
+            .map(|path| path.to_string_lossy().to_string()),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_process(_pid: u32) -> ProcessDetails {
+    ProcessDetails::default()
+}
+
+/// 프로세스 트리 자원 사용량 (한 시점의 누적값)
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TreeUsage {
+    /// 트리 전체의 누적 CPU 시간 (초)
+    pub cpu_seconds: f64,
+    /// 트리 전체의 상주 메모리 (바이트)
+    pub rss_bytes: u64,
+    /// 루트를 포함한 프로세스 수 (루트가 없으면 0)
+    pub processes: usize,
+}
+
+/// /proc/{pid}/stat에서 읽은 값
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ProcStat {
+    ppid: u32,
+    /// utime + stime (클럭 틱)
+    cpu_ticks: u64,
+    /// 상주 메모리 (페이지)
+    rss_pages: u64,
+}
+
+/// 모든 프로세스의 부모와 자원 사용량 스냅샷
+///
+/// 여러 세션의 프로세스 트리를 한 번의 /proc 스캔으로 계산하기 위해 사용합니다.
+#[derive(Debug, Default)]
+pub struct ProcessTable {
+    stats: HashMap<u32, ProcStat>,
+    ticks_per_second: u64,
+    page_size: u64,
+}
+
+impl ProcessTable {
+    /// /proc 스캔 (읽을 수 없는 프로세스는 건너뜀)
+    #[cfg(target_os = "linux")]
+    pub fn read() -> Self {
+        let mut stats = HashMap::new();
+        if let Ok(entries) = std::fs::read_dir("/proc") {
+            for entry in entries.flatten() {
+                let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse().ok()) else {
+                    continue;
+                };
+                let Ok(content) = std::fs::read_to_string(entry.path().join("stat")) else {
+                    continue;
+                };
+                if let Some(stat) = parse_stat(&content) {
+                    stats.insert(pid, stat);
+                }
+            }
+        }
+
+        Self {
+            stats,
+            ticks_per_second: unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as u64,
+            page_size: unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(1) as u64,
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn read() -> Self {
+        Self::default()
+    }
+
+    /// root와 모든 자손 프로세스의 사용량 합계
+    pub fn tree_usage(&self, root: u32) -> TreeUsage {
+        if !self.stats.contains_key(&root) {
+            return TreeUsage::default();
+        }
+
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+        for (pid, stat) in &self.stats {
+            children.entry(stat.ppid).or_default().push(*pid);
+        }
+
+        let mut cpu_ticks = 0;
+        let mut rss_pages = 0;
+        let mut processes = 0;
+        let mut pending = vec![root];
+        while let Some(pid) = pending.pop() {
+            let Some(stat) = self.stats.get(&pid) else {
+                continue;
+            };
+            cpu_ticks += stat.cpu_ticks;
+            rss_pages += stat.rss_pages;
+            processes += 1;
+            if let Some(list) = children.get(&pid) {
+                pending.extend(list);
+            }
+        }
+
+        TreeUsage {
+            cpu_seconds: cpu_ticks as f64 / self.ticks_per_second.max(1) as f64,
+            rss_bytes: rss_pages * self.page_size,
+            processes,
+        }
+    }
+}
+
+/// /proc/{pid}/stat 한 줄 파싱 (프로세스 이름에 공백이나 괄호가 있을 수 있어 마지막 ')' 뒤부터 읽음)
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_stat(content: &str) -> Option<ProcStat> {
+    let rest = &content[content.rfind(')')? + 1..];
+    // rest의 필드는 3번째(state)부터 시작
+    let fields: Vec<&str> = rest.split_whitespace().collect();
+    let field = |index: usize| -> Option<u64> { fields.get(index - 3)?.parse().ok() };
+
+    Some(ProcStat {
+        ppid: field(4)? as u32,
+        cpu_ticks: field(14)? + field(15)?,
+        rss_pages: field(24)?,
+    })
+}
+
+/// NUL로 끝나는 /proc/{pid}/cmdline 내용을 인자 목록으로 변환
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_cmdline(data: &[u8]) -> Vec<String> {
+    let data = data.strip_suffix(&[0]).unwrap_or(data);
+    if data.is_empty() {
+        return Vec::new();
+    }
+    data.split(|byte| *byte == 0)
+        .map(|arg| String::from_utf8_lossy(arg).to_string())
+        .collect()
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cmdline() {
+        assert_eq!(
+            parse_cmdline(b"grep\0\0main.rs\0"),
+            vec!["grep", "", "main.rs"]
+        );
+        assert!(parse_cmdline(b"").is_empty());
+    }
+
+    #[test]
+    fn test_tree_usage() {
+        let stat = |ppid: u32, cpu_ticks: u64, rss_pages: u64| ProcStat {
+            ppid,
+            cpu_ticks,
+            rss_pages,
+        };
+        let table = ProcessTable {
+            // 10(셸) -> 11(make) -> 12(cc), 20은 다른 트리
+            stats: HashMap::from([
+                (10, stat(1, 100, 10)),
+                (11, stat(10, 50, 20)),
+                (12, stat(11, 250, 300)),
+                (20, stat(1, 999, 999)),
+            ]),
+            ticks_per_second: 100,
+            page_size: 4096,
+        };
+
+        assert_eq!(
+            table.tree_usage(10),
+            TreeUsage {
+                cpu_seconds: 4.0,
+                rss_bytes: 330 * 4096,
+                processes: 3,
+            }
+        );
+        assert_eq!(table.tree_usage(11).processes, 2);
+        assert_eq!(table.tree_usage(99), TreeUsage::default());
+    }
+
+    #[test]
+    fn test_parse_stat() {
+        let line = "1234 (tmux: server) S 1 1234 1234 0 -1 4194560 1 0 0 0 7 3 0 0 20 0 1 0 100 1000 56 18446744073709551615 0 0 0 0 0 0 0 0 0 0 0 0 17 0 0 0 0 0 0\n";
+        assert_eq!(
+            parse_stat(line),
+            Some(ProcStat {
+                ppid: 1,
+                cpu_ticks: 10,
+                rss_pages: 56,
+            })
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_read_own_process() {
+        let details = read_process(std::process::id());
+        let cwd = std::env::current_dir().unwrap();
+
+        assert!(details.name.is_some());
+        assert!(!details.cmdline.is_empty());
+        assert_eq!(details.cwd.as_deref(), cwd.to_str());
+    }
+}
@@ -1,10 +1,36 @@
-use super::types::{PtyError, PtyExitEvent, PtyOutputEvent};
-use portable_pty::{Child, CommandBuilder, MasterPty, PtySize};
+use super::process;
+use super::signal;
+use super::types::{
+    PtyError, PtyExitEvent, PtyOutputEvent, PtyOutputMode, PtyProcessInfo, PtyRawOutputEvent,
+    PtyResourceUsage,
+};
+use crate::terminal::{
+    AsciicastRecorder, CommandHistoryEntry, MonitorConfig, RecordingInfo, ScreenSnapshot,
+    ScrollbackLimit, ScrollbackReplay, SearchOptions, SearchResult, SessionEncoding,
+    SessionLogContext, SessionLogInfo, SessionLogOptions, SessionLogger, SessionStream, ShellState,
+    INPUT_CHUNK_SIZE, LOCAL_HOST,
+};
+use portable_pty::{Child, ChildKiller, CommandBuilder, ExitStatus, MasterPty, PtySize};
+use std::collections::HashMap;
 use std::io::{Read, Write};
-use std::sync::Arc;
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc};
 use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::ipc::{Channel, InvokeResponseBody};
 use tauri::{AppHandle, Emitter};
-use tokio::sync::Mutex;
+use tokio::sync::{oneshot, Mutex};
+
+/// 입력 쓰기 요청 (writer 스레드가 받은 순서대로 씀)
+struct WriteJob {
+    data: Vec<u8>,
+    /// 녹화와 감사에 남길 입력
+    input: String,
+    done: oneshot::Sender<Result<(), PtyError>>,
+}
+
+/// 쓰기 완료 수신자 (세션 맵을 잠그지 않은 채 기다림)
+pub type WriteDone = oneshot::Receiver<Result<(), PtyError>>;
 
 /// 개별 PTY 세션
 pub struct PtySession {
@@ -13,23 +39,66 @@ pub struct PtySession {
     pub pid: u32,
     pub shell: String,
     master: Arc<Mutex<Box<dyn MasterPty + Send>>>,
-    writer: Arc<Mutex<Box<dyn Write + Send>>>,
-    #[allow(dead_code)]
+    /// writer 스레드로 보내는 입력 (PTY 입력 버퍼가 차도 세션 맵 잠금과 무관하게 대기)
+    input_tx: mpsc::Sender<WriteJob>,
     child: Arc<Mutex<Box<dyn Child + Send>>>,
+    /// wait() 중인 reader 스레드와 별개로 종료 신호를 보내기 위한 핸들
+    #[cfg_attr(unix, allow(dead_code))]
+    killer: Mutex<Box<dyn ChildKiller + Send + Sync>>,
+    /// 출력 스트림 (스크롤백, 녹화)
+    stream: Arc<SessionStream>,
+    /// 문자 인코딩 (reader 스레드와 공유, 실행 중 변경 가능)
+    encoding: Arc<SessionEncoding>,
+    /// 조회 커맨드의 직전 자원 사용량 샘플 (누적 CPU 시간, 샘플 시각)
+    poll_sample: std::sync::Mutex<Option<(f64, Instant)>>,
+    /// 주기적 이벤트의 직전 자원 사용량 샘플
+    event_sample: std::sync::Mutex<Option<(f64, Instant)>>,
+}
+
+/// 자원 사용량 샘플 주체 (CPU 사용률은 같은 주체의 직전 샘플과 비교)
+#[derive(Debug, Clone, Copy)]
+pub enum UsageSampler {
+    /// get_pty_resource_usage 조회
+    Poll,
+    /// 주기적 `pty-resource-usage` 이벤트
+    Event,
 }
 
+/// 종료 단계별 대기 시간 (SIGHUP -> SIGTERM -> SIGKILL)
+const TERMINATE_STEP_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// 종료 여부 확인 주기
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
 impl PtySession {
     /// 새 PTY 세션 생성
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         pty_id: String,
         shell: Option<String>,
         args: Option<Vec<String>>,
         cwd: Option<String>,
-        env: Option<std::collections::HashMap<String, String>>,
+        env: Option<HashMap<String, String>>,
         cols: u16,
         rows: u16,
+        output_mode: PtyOutputMode,
+        output_channel: Option<Channel>,
+        scrollback_limit: ScrollbackLimit,
+        log_options: Option<SessionLogOptions>,
+        encoding: Option<String>,
         app_handle: AppHandle,
     ) -> Result<Self, PtyError> {
+        let encoding = Arc::new(
+            SessionEncoding::new(encoding.as_deref())
+                .ok_or_else(|| PtyError::UnsupportedEncoding(encoding.unwrap_or_default()))?,
+        );
+
+        // 세션 로그 파일 (프로세스를 띄우기 전에 열어 첫 출력부터 기록)
+        let logger = log_options
+            .map(|options| SessionLogger::create(options, SessionLogContext::local(pty_id.clone())))
+            .transpose()
+            .map_err(|e| PtyError::LoggingFailed(e.to_string()))?;
+
         // PTY 시스템 생성
         let pty_system = portable_pty::native_pty_system();
 
@@ -80,6 +149,7 @@ impl PtySession {
         let pid = child
             .process_id()
             .ok_or_else(|| PtyError::CreationFailed("Failed to get PID".to_string()))?;
+        let killer = child.clone_killer();
 
         // Reader 생성 (출력 읽기용)
         let reader = pty_pair
@@ -93,13 +163,44 @@ impl PtySession {
             .take_writer()
             .map_err(|e| PtyError::CreationFailed(e.to_string()))?;
 
-        let writer = Arc::new(Mutex::new(writer));
+        let child: Arc<Mutex<Box<dyn Child + Send>>> = Arc::new(Mutex::new(child));
+        let stream = Arc::new(SessionStream::new(
+            pty_id.clone(),
+            LOCAL_HOST.to_string(),
+            scrollback_limit,
+            cols,
+            rows,
+            Arc::new(app_handle.clone()),
+        ));
+        if let Some(logger) = logger {
+            stream
+                .logging()
+                .start(logger)
+                .map_err(PtyError::LoggingFailed)?;
+        }
 
-        // Reader 스레드 시작 (출력을 읽어서 이벤트 발행)
+        // Writer 스레드 시작 (입력을 받은 순서대로 씀)
+        let (input_tx, input_rx) = mpsc::channel();
+        let stream_clone = stream.clone();
+        thread::spawn(move || Self::write_input(writer, input_rx, stream_clone));
+
+        // Reader 스레드 시작 (출력을 읽어서 이벤트 발행, 종료 시 자식 프로세스 회수)
         let pty_id_clone = pty_id.clone();
+        let child_clone = child.clone();
+        let stream_clone = stream.clone();
+        let encoding_clone = encoding.clone();
         let app_handle_clone = app_handle.clone();
         thread::spawn(move || {
-            Self::read_output(pty_id_clone, reader, app_handle_clone);
+            Self::read_output(
+                pty_id_clone,
+                reader,
+                child_clone,
+                stream_clone,
+                encoding_clone,
+                output_mode,
+                output_channel,
+                app_handle_clone,
+            );
         });
 
         Ok(PtySession {
@@ -107,21 +208,250 @@ impl PtySession {
             pid,
             shell: shell_path,
             master: Arc::new(Mutex::new(pty_pair.master)),
-            writer,
-            child: Arc::new(Mutex::new(child)),
+            input_tx,
+            child,
+            killer: Mutex::new(killer),
+            stream,
+            encoding,
+            poll_sample: std::sync::Mutex::new(None),
+            event_sample: std::sync::Mutex::new(None),
         })
     }
 
-    /// PTY에 데이터 쓰기
-    pub async fn write(&self, data: &str) -> Result<(), PtyError> {
-        let mut writer = self.writer.lock().await;
-        writer
-            .write_all(data.as_bytes())
-            .map_err(|e| PtyError::WriteFailed(e.to_string()))?;
-        writer
-            .flush()
-            .map_err(|e| PtyError::WriteFailed(e.to_string()))?;
-        Ok(())
+    /// 프론트엔드의 출력 처리 확인 (흐름 제어)
+    pub fn ack_output(&self, seq: u64) {
+        self.stream.ack_output(seq);
+    }
+
+    /// 스크롤백 검색
+    pub fn search(&self, pattern: &str, options: &SearchOptions) -> Result<SearchResult, PtyError> {
+        self.stream
+            .search(pattern, options)
+            .map_err(PtyError::InvalidPattern)
+    }
+
+    /// 현재 화면 텍스트
+    pub fn screen_text(&self) -> String {
+        self.stream.screen_text()
+    }
+
+    /// 현재 화면 셀 스냅샷
+    pub fn screen_snapshot(&self) -> ScreenSnapshot {
+        self.stream.screen_snapshot()
+    }
+
+    /// 셸 통합 상태
+    pub fn shell_state(&self) -> ShellState {
+        self.stream.shell_state()
+    }
+
+    /// 명령 히스토리
+    pub fn command_history(&self) -> Vec<CommandHistoryEntry> {
+        self.stream.command_history()
+    }
+
+    /// 포그라운드 프로세스 정보 (프로세스 그룹은 PTY master의 tcgetpgrp)
+    pub async fn process_info(&self) -> PtyProcessInfo {
+        #[cfg(unix)]
+        let foreground_pgid = self
+            .master
+            .lock()
+            .await
+            .process_group_leader()
+            .map(|pgid| pgid as u32);
+        #[cfg(not(unix))]
+        let foreground_pgid: Option<u32> = None;
+
+        // 프로세스 그룹 리더를 포그라운드 프로세스로 간주
+        let foreground_pid = foreground_pgid.unwrap_or(self.pid);
+        let mut details = process::read_process(foreground_pid);
+        if details.cwd.is_none() && foreground_pid != self.pid {
+            details.cwd = process::read_process(self.pid).cwd;
+        }
+
+        PtyProcessInfo {
+            pty_id: self.pty_id.clone(),
+            shell_pid: self.pid,
+            foreground_pgid,
+            name: details.name,
+            cmdline: details.cmdline,
+            cwd: details.cwd,
+            running_job: foreground_pgid.is_some_and(|pgid| pgid != self.pid),
+        }
+    }
+
+    /// 프로세스 트리 자원 사용량 (CPU 사용률은 같은 주체의 직전 샘플과의 차이로 계산)
+    pub fn resource_usage(
+        &self,
+        table: &process::ProcessTable,
+        sampler: UsageSampler,
+    ) -> PtyResourceUsage {
+        let usage = table.tree_usage(self.pid);
+        let now = Instant::now();
+
+        let sample = match sampler {
+            UsageSampler::Poll => &self.poll_sample,
+            UsageSampler::Event => &self.event_sample,
+        };
+        let previous = sample.lock().unwrap().replace((usage.cpu_seconds, now));
+        let cpu_percent = previous.and_then(|(cpu_seconds, at)| {
+            let elapsed = now.duration_since(at).as_secs_f64();
+            (elapsed > 0.0).then(|| (usage.cpu_seconds - cpu_seconds).max(0.0) / elapsed * 100.0)
+        });
+
+        PtyResourceUsage {
+            pty_id: self.pty_id.clone(),
+            cpu_percent,
+            rss_bytes: usage.rss_bytes,
+            child_processes: usage.processes.saturating_sub(1),
+            sampled_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+        }
+    }
+
+    /// 활동/무출력 감시 설정
+    pub fn set_monitor(&self, config: MonitorConfig) {
+        self.stream.set_monitor(config);
+    }
+
+    /// 활동/무출력 감시 설정 조회
+    pub fn monitor_config(&self) -> MonitorConfig {
+        self.stream.monitor_config()
+    }
+
+    /// 스크롤백 재생 (from_offset 이후의 출력)
+    pub fn replay(&self, from_offset: u64) -> ScrollbackReplay {
+        self.stream.replay(from_offset)
+    }
+
+    /// asciicast v2 녹화 시작
+    pub async fn start_recording(
+        &self,
+        path: Option<String>,
+        title: Option<String>,
+        record_input: bool,
+    ) -> Result<RecordingInfo, PtyError> {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => AsciicastRecorder::default_path(&self.pty_id).ok_or_else(|| {
+                PtyError::RecordingFailed("Config directory not found".to_string())
+            })?,
+        };
+
+        let size = self
+            .master
+            .lock()
+            .await
+            .get_size()
+            .map_err(|e| PtyError::RecordingFailed(e.to_string()))?;
+
+        let env = HashMap::from([
+            ("SHELL".to_string(), self.shell.clone()),
+            ("TERM".to_string(), "xterm-256color".to_string()),
+        ]);
+
+        let recorder =
+            AsciicastRecorder::create(&path, size.cols, size.rows, title, env, record_input)
+                .map_err(|e| PtyError::RecordingFailed(e.to_string()))?;
+        self.stream
+            .recording()
+            .start(recorder)
+            .map_err(PtyError::RecordingFailed)?;
+
+        Ok(RecordingInfo {
+            session_id: self.pty_id.clone(),
+            path: path.to_string_lossy().to_string(),
+        })
+    }
+
+    /// 녹화 종료
+    pub fn stop_recording(&self) -> Result<RecordingInfo, PtyError> {
+        let path = self
+            .stream
+            .recording()
+            .stop()
+            .ok_or_else(|| PtyError::RecordingFailed("Not recording".to_string()))?
+            .map_err(|e| PtyError::RecordingFailed(e.to_string()))?;
+
+        Ok(RecordingInfo {
+            session_id: self.pty_id.clone(),
+            path: path.to_string_lossy().to_string(),
+        })
+    }
+
+    /// 세션 로그 시작
+    pub fn start_logging(&self, options: SessionLogOptions) -> Result<SessionLogInfo, PtyError> {
+        let context = SessionLogContext::local(self.pty_id.clone());
+        let logger = SessionLogger::create(options, context)
+            .map_err(|e| PtyError::LoggingFailed(e.to_string()))?;
+        let path = logger.path().to_string_lossy().to_string();
+        self.stream
+            .logging()
+            .start(logger)
+            .map_err(PtyError::LoggingFailed)?;
+
+        Ok(SessionLogInfo {
+            session_id: self.pty_id.clone(),
+            path,
+        })
+    }
+
+    /// 세션 로그 종료
+    pub fn stop_logging(&self) -> Result<SessionLogInfo, PtyError> {
+        let path = self
+            .stream
+            .logging()
+            .stop()
+            .ok_or_else(|| PtyError::LoggingFailed("Not logging".to_string()))?
+            .map_err(|e| PtyError::LoggingFailed(e.to_string()))?;
+
+        Ok(SessionLogInfo {
+            session_id: self.pty_id.clone(),
+            path: path.to_string_lossy().to_string(),
+        })
+    }
+
+    /// PTY에 데이터 쓰기 (세션 인코딩으로 변환)
+    ///
+    /// writer 스레드가 받은 순서대로 쓰며, 쓰기가 끝나면 반환한 수신자로 결과를 받습니다.
+    pub fn write(&self, data: &str) -> Result<WriteDone, PtyError> {
+        self.queue_input(self.encoding.encode(data).into_owned(), data.to_string())
+    }
+
+    /// PTY에 바이트 쓰기 (인코딩 변환 없음, paste면 필요할 때 bracketed paste로 감쌈)
+    ///
+    /// 큰 입력은 청크 단위로 쓰며, PTY 입력 버퍼가 차면 프로세스가 읽을 때까지 기다립니다.
+    /// 붙여넣기 도중 다른 입력은 끼어들지 않고 뒤에서 기다립니다.
+    pub fn write_bytes(&self, data: Vec<u8>, paste: bool) -> Result<WriteDone, PtyError> {
+        // 녹화와 감사에는 bracketed paste 시퀀스를 빼고 입력한 내용만 남김
+        let input = String::from_utf8_lossy(&data).into_owned();
+        let data = if paste {
+            self.stream.prepare_paste(data)
+        } else {
+            data
+        };
+        self.queue_input(data, input)
+    }
+
+    fn queue_input(&self, data: Vec<u8>, input: String) -> Result<WriteDone, PtyError> {
+        let (done, done_rx) = oneshot::channel();
+        self.input_tx
+            .send(WriteJob { data, input, done })
+            .map_err(|_| PtyError::WriteFailed("PTY writer stopped".to_string()))?;
+        Ok(done_rx)
+    }
+
+    /// 문자 인코딩 변경 (변경된 인코딩 이름 반환)
+    pub fn set_encoding(&self, label: &str) -> Result<String, PtyError> {
+        let rest = self
+            .encoding
+            .set(label)
+            .ok_or_else(|| PtyError::UnsupportedEncoding(label.to_string()))?;
+        // 이전 인코딩에서 남은 바이트
+        self.stream.queue_output(rest.as_bytes());
+        Ok(self.encoding.name().to_string())
     }
 
     /// PTY 크기 조정
@@ -137,56 +467,286 @@ impl PtySession {
             .await
             .resize(pty_size)
             .map_err(|e| PtyError::ResizeFailed(e.to_string()))?;
+        self.stream.push_resize(cols, rows);
         Ok(())
     }
 
+    /// 포그라운드 프로세스 그룹에 시그널 전송
+    ///
+    /// 터미널에서 Ctrl+C를 누른 것과 같이 현재 실행 중인 작업(포그라운드 그룹)에 전달됩니다.
+    /// 포그라운드 그룹을 알 수 없으면 shell의 프로세스 그룹으로 보냅니다.
+    #[cfg(unix)]
+    pub async fn send_signal(&self, signal_name: &str) -> Result<(), PtyError> {
+        let signal = signal::number_from_name(signal_name)
+            .ok_or_else(|| PtyError::SignalFailed(format!("Unknown signal: {}", signal_name)))?;
+
+        let pgid = self
+            .master
+            .lock()
+            .await
+            .process_group_leader()
+            .unwrap_or(self.pid as libc::pid_t);
+
+        if unsafe { libc::killpg(pgid, signal) } == -1 {
+            return Err(PtyError::SignalFailed(
+                std::io::Error::last_os_error().to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 프로세스 종료 (Windows는 시그널이 없으므로 SIGKILL/SIGTERM만 지원)
+    #[cfg(windows)]
+    pub async fn send_signal(&self, signal_name: &str) -> Result<(), PtyError> {
+        match signal_name
+            .trim()
+            .to_ascii_uppercase()
+            .trim_start_matches("SIG")
+        {
+            "KILL" | "TERM" => self
+                .killer
+                .lock()
+                .await
+                .kill()
+                .map_err(|e| PtyError::SignalFailed(e.to_string())),
+            _ => Err(PtyError::SignalFailed(format!(
+                "Signal not supported on Windows: {}",
+                signal_name
+            ))),
+        }
+    }
+
+    /// 세션 프로세스 종료
+    ///
+    /// shell의 프로세스 그룹(및 포그라운드 작업 그룹)에 SIGHUP -> SIGTERM -> SIGKILL 순서로
+    /// 시그널을 보내고, 단계마다 TERMINATE_STEP_TIMEOUT 동안 종료를 기다립니다.
+    #[cfg(unix)]
+    pub async fn terminate(&self) {
+        // 흐름 제어로 멈춘 reader가 EOF까지 읽을 수 있도록 출력 대기열을 닫음
+        self.stream.close_output();
+
+        let shell_pgid = self.pid as libc::pid_t;
+        let foreground_pgid = self.master.lock().await.process_group_leader();
+
+        for signal in [libc::SIGHUP, libc::SIGTERM, libc::SIGKILL] {
+            if self.has_exited() {
+                return;
+            }
+
+            if let Some(pgid) = foreground_pgid.filter(|pgid| *pgid != shell_pgid) {
+                unsafe { libc::killpg(pgid, signal) };
+            }
+            if unsafe { libc::killpg(shell_pgid, signal) } == -1
+                && std::io::Error::last_os_error().raw_os_error() == Some(libc::ESRCH)
+            {
+                // 프로세스 그룹이 이미 없음
+                return;
+            }
+
+            if self.wait_for_exit(TERMINATE_STEP_TIMEOUT).await {
+                return;
+            }
+        }
+
+        eprintln!("PTY process {} did not exit after SIGKILL", self.pid);
+    }
+
+    /// 세션 프로세스 종료 (Windows)
+    #[cfg(windows)]
+    pub async fn terminate(&self) {
+        // 흐름 제어로 멈춘 reader가 EOF까지 읽을 수 있도록 출력 대기열을 닫음
+        self.stream.close_output();
+
+        if self.has_exited() {
+            return;
+        }
+        if let Err(e) = self.killer.lock().await.kill() {
+            eprintln!("Failed to kill PTY process {}: {}", self.pid, e);
+            return;
+        }
+        if !self.wait_for_exit(TERMINATE_STEP_TIMEOUT).await {
+            eprintln!("PTY process {} did not exit after kill", self.pid);
+        }
+    }
+
+    /// 자식 프로세스 종료 여부 확인
+    ///
+    /// reader 스레드가 wait() 중이면(잠금 실패) 아직 종료되지 않은 것으로 간주합니다.
+    fn has_exited(&self) -> bool {
+        match self.child.try_lock() {
+            Ok(mut child) => !matches!(child.try_wait(), Ok(None)),
+            Err(_) => false,
+        }
+    }
+
+    /// 지정 시간 동안 자식 프로세스 종료 대기
+    async fn wait_for_exit(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            if self.has_exited() {
+                return true;
+            }
+            tokio::time::sleep(EXIT_POLL_INTERVAL).await;
+        }
+        self.has_exited()
+    }
+
+    /// 입력 쓰기 스레드 함수 (세션이 닫혀 요청 채널이 끊기면 종료)
+    fn write_input(
+        mut writer: Box<dyn Write + Send>,
+        jobs: mpsc::Receiver<WriteJob>,
+        stream: Arc<SessionStream>,
+    ) {
+        for job in jobs {
+            let result = job
+                .data
+                .chunks(INPUT_CHUNK_SIZE)
+                .try_for_each(|chunk| writer.write_all(chunk).and_then(|_| writer.flush()))
+                .map_err(|e| PtyError::WriteFailed(e.to_string()));
+            if result.is_ok() {
+                stream.push_input(&job.input);
+            }
+            let _ = job.done.send(result);
+        }
+    }
+
     /// 출력 읽기 스레드 함수
-    fn read_output(pty_id: String, mut reader: Box<dyn Read + Send>, app_handle: AppHandle) {
+    #[allow(clippy::too_many_arguments)]
+    fn read_output(
+        pty_id: String,
+        mut reader: Box<dyn Read + Send>,
+        child: Arc<Mutex<Box<dyn Child + Send>>>,
+        stream: Arc<SessionStream>,
+        encoding: Arc<SessionEncoding>,
+        output_mode: PtyOutputMode,
+        output_channel: Option<Channel>,
+        app_handle: AppHandle,
+    ) {
         let mut buf = [0u8; 8192];
 
-        loop {
-            match reader.read(&mut buf) {
-                Ok(0) => {
-                    // EOF - 프로세스 종료
+        // 출력은 flusher 스레드에서 프레임 단위로 모아 채널 또는 이벤트로 발행
+        // (채널의 n번째 메시지가 seq n에 해당)
+        let flusher = {
+            let pty_id = pty_id.clone();
+            let app_handle = app_handle.clone();
+            let event_name = format!("pty-output-{}", pty_id);
+            stream.spawn_flusher(move |seq, frame| match (&output_channel, output_mode) {
+                (Some(channel), _) => {
+                    if let Err(e) = channel.send(InvokeResponseBody::Raw(frame)) {
+                        eprintln!("Failed to send PTY output to channel: {}", e);
+                    }
+                }
+                (None, PtyOutputMode::Text) => {
+                    // 디코더를 거친 출력만 모이므로 항상 유효한 UTF-8
+                    let data = String::from_utf8(frame)
+                        .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned());
+                    Self::emit_text(&app_handle, &event_name, &pty_id, seq, data);
+                }
+                (None, PtyOutputMode::Raw) => {
                     let _ = app_handle.emit(
-                        &format!("pty-exit-{}", pty_id),
-                        PtyExitEvent {
+                        &event_name,
+                        PtyRawOutputEvent {
                             pty_id: pty_id.clone(),
-                            exit_code: Some(0),
+                            data: frame,
+                            seq,
                         },
                     );
+                }
+            })
+        };
+
+        loop {
+            // 프론트엔드가 출력을 따라오지 못하면 읽기를 멈춤 (PTY 버퍼가 차면 프로세스도 멈춤)
+            stream.wait_ready(None);
+
+            match reader.read(&mut buf) {
+                Ok(0) => {
+                    // EOF - 프로세스 종료
                     break;
                 }
-                Ok(n) => {
-                    // 데이터 수신 - UTF-8로 변환하여 이벤트 발행
-                    if let Ok(data) = String::from_utf8(buf[..n].to_vec()) {
-                        let _ = app_handle.emit(
-                            &format!("pty-output-{}", pty_id),
-                            PtyOutputEvent {
-                                pty_id: pty_id.clone(),
-                                data,
-                            },
-                        );
+                Ok(n) => match output_mode {
+                    PtyOutputMode::Text => {
+                        // 데이터 수신 - 청크 경계에서 잘린 문자는 다음 read로 이월
+                        let data = encoding.decode(&buf[..n]);
+                        stream.queue_output(data.as_bytes());
                     }
-                }
+                    // UTF-8이 아닌 세션은 원시 모드에서도 UTF-8로 변환
+                    PtyOutputMode::Raw => stream.queue_output(&encoding.decode_raw(&buf[..n])),
+                },
                 Err(e) => {
                     // 에러 발생 - 종료
                     eprintln!("PTY read error: {}", e);
-                    let _ = app_handle.emit(
-                        &format!("pty-exit-{}", pty_id),
-                        PtyExitEvent {
-                            pty_id: pty_id.clone(),
-                            exit_code: None,
-                        },
-                    );
                     break;
                 }
             }
         }
+
+        // 종료 전 남아있는 미완성 바이트까지 모두 전송
+        stream.queue_output(encoding.finish().as_bytes());
+        stream.close_output();
+        let _ = flusher.join();
+
+        // 녹화 중이었다면 마무리
+        if let Some(Err(e)) = stream.recording().stop() {
+            eprintln!("Failed to finish recording for PTY {}: {}", pty_id, e);
+        }
+        if let Some(Err(e)) = stream.logging().stop() {
+            eprintln!("Failed to finish session log for PTY {}: {}", pty_id, e);
+        }
+
+        // 자식 프로세스 회수 후 실제 종료 상태 전달
+        let (exit_code, signal) = match child.blocking_lock().wait() {
+            Ok(status) => Self::exit_status_info(&status),
+            Err(e) => {
+                eprintln!("PTY wait error: {}", e);
+                (None, None)
+            }
+        };
+
+        let _ = app_handle.emit(
+            &format!("pty-exit-{}", pty_id),
+            PtyExitEvent {
+                pty_id: pty_id.clone(),
+                exit_code,
+                signal,
+            },
+        );
+    }
+
+    /// ExitStatus를 (종료 코드, 시그널 이름)으로 변환
+    ///
+    /// portable_pty는 시그널 정보를 Display("Terminated by <설명>")로만 노출합니다.
+    pub(crate) fn exit_status_info(status: &ExitStatus) -> (Option<i32>, Option<String>) {
+        if status.success() {
+            return (Some(0), None);
+        }
+
+        let text = status.to_string();
+        match text.strip_prefix("Terminated by ") {
+            Some(description) => {
+                let name = signal::name_from_description(description)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| description.to_string());
+                (None, Some(name))
+            }
+            None => (Some(status.exit_code() as i32), None),
+        }
+    }
+
+    /// 텍스트 출력 이벤트 발행
+    fn emit_text(app_handle: &AppHandle, event_name: &str, pty_id: &str, seq: u64, data: String) {
+        let _ = app_handle.emit(
+            event_name,
+            PtyOutputEvent {
+                pty_id: pty_id.to_string(),
+                data,
+                seq,
+            },
+        );
     }
 
     /// 기본 shell 경로 반환
-    fn default_shell() -> String {
+    pub(crate) fn default_shell() -> String {
         #[cfg(target_os = "windows")]
         {
             "powershell.exe".to_string()
@@ -0,0 +1,284 @@
+//! 설치된 셸 탐색과 실행 프로필
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// PATH에서 찾는 셸 이름
+const KNOWN_SHELLS: &[&str] = &["bash", "zsh", "fish", "nu", "pwsh"];
+
+/// 버전 확인 명령 대기 시간
+const VERSION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 설치된 셸 정보 (list_available_shells 커맨드)
+#[derive(Debug, Clone, Serialize)]
+pub struct ShellInfo {
+    /// 실행 파일 이름 (예: "zsh")
+    pub name: String,
+    pub path: String,
+    /// `--version` 출력에서 찾은 버전 (알려진 셸만 확인)
+    pub version: Option<String>,
+    /// 새 탭의 기본 셸인지
+    pub is_default: bool,
+}
+
+/// 로컬 탭 실행 프로필 (Settings.launch_profiles)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchProfile {
+    pub name: String,
+    /// None이면 기본 셸
+    #[serde(default)]
+    pub shell: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// 로그인 셸로 실행 (인자 앞에 "-l" 추가, Windows에서는 무시)
+    #[serde(default)]
+    pub login_shell: bool,
+    /// 문자 인코딩 (예: "euc-kr", 생략하면 UTF-8)
+    #[serde(default)]
+    pub encoding: Option<String>,
+}
+
+/// PTY 실행 파라미터 (create_pty / add_local_tab 인자)
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LaunchSpec {
+    pub shell: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub cwd: Option<String>,
+    pub env: Option<HashMap<String, String>>,
+    pub encoding: Option<String>,
+}
+
+impl LaunchSpec {
+    /// 실행 프로필 적용
+    ///
+    /// 직접 넘긴 shell, args, cwd, encoding이 프로필보다 우선하고, 환경 변수는 프로필 위에 덮어씁니다.
+    pub fn with_profile(self, profile: &LaunchProfile) -> Self {
+        let args = self.args.unwrap_or_else(|| {
+            let mut args = Vec::new();
+            if profile.login_shell && cfg!(not(target_os = "windows")) {
+                args.push("-l".to_string());
+            }
+            args.extend(profile.args.iter().cloned());
+            args
+        });
+
+        let mut env = profile.env.clone();
+        env.extend(self.env.unwrap_or_default());
+
+        Self {
+            shell: self.shell.or_else(|| profile.shell.clone()),
+            args: Some(args),
+            cwd: self.cwd.or_else(|| profile.cwd.clone()),
+            env: Some(env),
+            encoding: self.encoding.or_else(|| profile.encoding.clone()),
+        }
+    }
+}
+
+/// 설치된 셸 목록 (/etc/shells + PATH 탐색, 같은 실행 파일은 한 번만)
+///
+/// 알려진 셸은 `--version`을 실행하므로 블로킹 작업입니다.
+pub fn list_available_shells() -> Vec<ShellInfo> {
+    let mut candidates: Vec<PathBuf> = std::fs::read_to_string("/etc/shells")
+        .map(|content| parse_etc_shells(&content))
+        .unwrap_or_default();
+    candidates.extend(KNOWN_SHELLS.iter().filter_map(|name| find_in_path(name)));
+
+    let default_shell = canonical(Path::new(&super::PtySession::default_shell()));
+    let mut seen = Vec::new();
+    let mut shells = Vec::new();
+    for path in candidates {
+        if !path.is_file() {
+            continue;
+        }
+        let resolved = canonical(&path);
+        if seen.contains(&resolved) {
+            continue;
+        }
+
+        let name = shell_name(&path);
+        let version = KNOWN_SHELLS
+            .contains(&name.as_str())
+            .then(|| probe_version(&path))
+            .flatten();
+        shells.push(ShellInfo {
+            name,
+            path: path.to_string_lossy().to_string(),
+            version,
+            is_default: resolved == default_shell,
+        });
+        seen.push(resolved);
+    }
+
+    shells
+}
+
+/// /etc/shells 파싱 (주석과 빈 줄 제외)
+fn parse_etc_shells(content: &str) -> Vec<PathBuf> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| line.starts_with('/'))
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// PATH에서 실행 파일 찾기
+fn find_in_path(name: &str) -> Option<PathBuf> {
+    let file_name = if cfg!(target_os = "windows") {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    };
+
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(&file_name))
+        .find(|path| path.is_file())
+}
+
+/// 심볼릭 링크를 따라간 경로 (실패하면 그대로)
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// 실행 파일 이름 (확장자 제외)
+fn shell_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// `<shell> --version` 실행 후 버전 추출 (시간 초과 시 None)
+fn probe_version(path: &Path) -> Option<String> {
+    let mut child = Command::new(path)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    let deadline = Instant::now() + VERSION_TIMEOUT;
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(20)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+
+    let mut output = String::new();
+    child.stdout.take()?.read_to_string(&mut output).ok()?;
+    parse_version(&output)
+}
+
+/// 버전 출력에서 첫 번째 버전 번호 찾기
+///
+/// 예: "GNU bash, version 5.2.15(1)-release" -> "5.2.15", "PowerShell 7.4.0" -> "7.4.0"
+fn parse_version(output: &str) -> Option<String> {
+    output
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|word| {
+            let end = word
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(word.len());
+            word[..end].trim_end_matches('.')
+        })
+        .find(|version| version.contains('.'))
+        .map(str::to_string)
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_etc_shells() {
+        let content =
+            "# /etc/shells: valid login shells\n/bin/sh\n\n/usr/bin/zsh # zsh\n  /bin/bash\n";
+        assert_eq!(
+            parse_etc_shells(content),
+            vec![
+                PathBuf::from("/bin/sh"),
+                PathBuf::from("/usr/bin/zsh"),
+                PathBuf::from("/bin/bash"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_version() {
+        let cases = [
+            (
+                "GNU bash, version 5.2.15(1)-release (x86_64-pc-linux-gnu)\nCopyright",
+                Some("5.2.15"),
+            ),
+            ("zsh 5.9 (x86_64-debian-linux-gnu)", Some("5.9")),
+            ("fish, version 3.6.0", Some("3.6.0")),
+            ("0.90.1\n", Some("0.90.1")),
+            ("PowerShell 7.4.0", Some("7.4.0")),
+            ("unknown option", None),
+        ];
+        for (output, expected) in cases {
+            assert_eq!(parse_version(output).as_deref(), expected, "{}", output);
+        }
+    }
+
+    #[test]
+    fn test_launch_spec_with_profile() {
+        let profile = LaunchProfile {
+            name: "work".to_string(),
+            shell: Some("/usr/bin/zsh".to_string()),
+            args: vec!["-i".to_string()],
+            env: HashMap::from([
+                ("A".to_string(), "profile".to_string()),
+                ("B".to_string(), "profile".to_string()),
+            ]),
+            cwd: Some("/work".to_string()),
+            login_shell: true,
+            encoding: Some("euc-kr".to_string()),
+        };
+
+        let spec = LaunchSpec {
+            cwd: Some("/tmp".to_string()),
+            env: Some(HashMap::from([("B".to_string(), "explicit".to_string())])),
+            ..Default::default()
+        }
+        .with_profile(&profile);
+
+        assert_eq!(spec.shell.as_deref(), Some("/usr/bin/zsh"));
+        #[cfg(not(target_os = "windows"))]
+        assert_eq!(spec.args, Some(vec!["-l".to_string(), "-i".to_string()]));
+        assert_eq!(spec.cwd.as_deref(), Some("/tmp"));
+        assert_eq!(spec.encoding.as_deref(), Some("euc-kr"));
+        let env = spec.env.unwrap();
+        assert_eq!(env["A"], "profile");
+        assert_eq!(env["B"], "explicit");
+
+        // 직접 넘긴 인자는 프로필 인자와 로그인 플래그를 대체
+        let spec = LaunchSpec {
+            args: Some(vec!["-c".to_string(), "top".to_string()]),
+            ..Default::default()
+        }
+        .with_profile(&profile);
+        assert_eq!(spec.args, Some(vec!["-c".to_string(), "top".to_string()]));
+    }
+}
@@ -0,0 +1,78 @@
+//! 시그널 이름/번호 매핑 (Unix 전용)
+
+/// 지원하는 시그널 목록 (이름, 번호)
+#[cfg(unix)]
+const SIGNALS: &[(&str, i32)] = &[
+    ("SIGHUP", libc::SIGHUP),
+    ("SIGINT", libc::SIGINT),
+    ("SIGQUIT", libc::SIGQUIT),
+    ("SIGILL", libc::SIGILL),
+    ("SIGTRAP", libc::SIGTRAP),
+    ("SIGABRT", libc::SIGABRT),
+    ("SIGBUS", libc::SIGBUS),
+    ("SIGFPE", libc::SIGFPE),
+    ("SIGKILL", libc::SIGKILL),
+    ("SIGUSR1", libc::SIGUSR1),
+    ("SIGSEGV", libc::SIGSEGV),
+    ("SIGUSR2", libc::SIGUSR2),
+    ("SIGPIPE", libc::SIGPIPE),
+    ("SIGALRM", libc::SIGALRM),
+    ("SIGTERM", libc::SIGTERM),
+    ("SIGCHLD", libc::SIGCHLD),
+    ("SIGCONT", libc::SIGCONT),
+    ("SIGSTOP", libc::SIGSTOP),
+    ("SIGTSTP", libc::SIGTSTP),
+    ("SIGTTIN", libc::SIGTTIN),
+    ("SIGTTOU", libc::SIGTTOU),
+    ("SIGURG", libc::SIGURG),
+    ("SIGXCPU", libc::SIGXCPU),
+    ("SIGXFSZ", libc::SIGXFSZ),
+    ("SIGVTALRM", libc::SIGVTALRM),
+    ("SIGPROF", libc::SIGPROF),
+    ("SIGWINCH", libc::SIGWINCH),
+    ("SIGIO", libc::SIGIO),
+    ("SIGSYS", libc::SIGSYS),
+];
+
+/// 시그널 이름으로 번호 찾기
+///
+/// "SIGINT", "INT", "sigint" 형식과 숫자("2")를 모두 허용합니다.
+#[cfg(unix)]
+pub fn number_from_name(name: &str) -> Option<i32> {
+    let name = name.trim();
+    if let Ok(number) = name.parse::<i32>() {
+        return SIGNALS.iter().any(|(_, n)| *n == number).then_some(number);
+    }
+
+    let upper = name.to_ascii_uppercase();
+    let full = if upper.starts_with("SIG") {
+        upper
+    } else {
+        format!("SIG{}", upper)
+    };
+    SIGNALS
+        .iter()
+        .find(|(n, _)| *n == full)
+        .map(|(_, number)| *number)
+}
+
+/// strsignal() 설명 문자열로 시그널 이름 찾기
+///
+/// portable_pty::ExitStatus는 시그널을 strsignal() 설명("Killed", "Hangup" 등)으로만
+/// 보관하므로, 같은 함수로 만든 설명과 비교하여 "SIGKILL" 같은 이름으로 되돌립니다.
+#[cfg(unix)]
+pub fn name_from_description(description: &str) -> Option<&'static str> {
+    SIGNALS.iter().find_map(|(name, number)| {
+        let ptr = unsafe { libc::strsignal(*number) };
+        if ptr.is_null() {
+            return None;
+        }
+        let text = unsafe { std::ffi::CStr::from_ptr(ptr) };
+        (text.to_string_lossy() == description).then_some(*name)
+    })
+}
+
+#[cfg(not(unix))]
+pub fn name_from_description(_description: &str) -> Option<&'static str> {
+    None
+}
@@ -43,13 +43,76 @@ pub struct ResizePtyRequest {
 pub struct PtyOutputEvent {
     pub pty_id: String,
     pub data: String,
+    /// 출력 시퀀스 번호 (누락 감지용, 0부터 1씩 증가)
+    pub seq: u64,
+}
+
+/// PTY 출력 모드
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PtyOutputMode {
+    /// UTF-8 문자열로 디코딩하여 전송 (기본값)
+    #[default]
+    Text,
+    /// 원시 바이트 그대로 전송 (디코딩 없음)
+    Raw,
+}
+
+/// PTY 원시 출력 이벤트 페이로드 (Raw 모드)
+#[derive(Debug, Clone, Serialize)]
+pub struct PtyRawOutputEvent {
+    pub pty_id: String,
+    pub data: Vec<u8>,
+    /// 출력 시퀀스 번호 (누락 감지용, 0부터 1씩 증가)
+    pub seq: u64,
 }
 
 /// PTY 종료 이벤트 페이로드
+///
+/// - 정상 종료: `exit_code`에 실제 종료 코드
+/// - 시그널로 종료: `exit_code`는 None, `signal`에 시그널 이름 (예: "SIGKILL")
+/// - 상태를 알 수 없는 경우: 둘 다 None
 #[derive(Debug, Clone, Serialize)]
 pub struct PtyExitEvent {
     pub pty_id: String,
     pub exit_code: Option<i32>,
+    pub signal: Option<String>,
+}
+
+/// PTY 포그라운드 프로세스 정보 (get_pty_process_info 커맨드)
+///
+/// 프로세스 이름, 명령줄, 작업 디렉토리는 /proc에서 읽으므로 Linux에서만 채워집니다.
+#[derive(Debug, Clone, Serialize)]
+pub struct PtyProcessInfo {
+    pub pty_id: String,
+    /// 셸 PID
+    pub shell_pid: u32,
+    /// 포그라운드 프로세스 그룹 ID (tcgetpgrp, 알 수 없으면 None)
+    pub foreground_pgid: Option<u32>,
+    /// 포그라운드 프로세스 이름 (예: "vim")
+    pub name: Option<String>,
+    /// 포그라운드 프로세스 명령줄 (예: ["vim", "main.rs"])
+    pub cmdline: Vec<String>,
+    /// 포그라운드 프로세스 작업 디렉토리 (읽을 수 없으면 셸의 작업 디렉토리)
+    pub cwd: Option<String>,
+    /// 셸이 아닌 작업이 포그라운드에서 실행 중인지
+    pub running_job: bool,
+}
+
+/// PTY 프로세스 트리 자원 사용량 (get_pty_resource_usage 커맨드, pty-resource-usage 이벤트)
+///
+/// 셸과 모든 자손 프로세스의 합계입니다. /proc에서 읽으므로 Linux에서만 채워집니다.
+#[derive(Debug, Clone, Serialize)]
+pub struct PtyResourceUsage {
+    pub pty_id: String,
+    /// 이전 샘플 이후 CPU 사용률 (코어 1개 = 100, 첫 샘플은 None)
+    pub cpu_percent: Option<f64>,
+    /// 상주 메모리 합계 (바이트)
+    pub rss_bytes: u64,
+    /// 셸을 제외한 자손 프로세스 수
+    pub child_processes: usize,
+    /// 샘플 시각 (Unix epoch 밀리초)
+    pub sampled_at: u64,
 }
 
 /// PTY 에러 타입
@@ -67,6 +130,24 @@ pub enum PtyError {
     #[error("Failed to resize PTY: {0}")]
     ResizeFailed(String),
 
+    #[error("Failed to send signal: {0}")]
+    SignalFailed(String),
+
+    #[error("Recording error: {0}")]
+    RecordingFailed(String),
+
+    #[error("Session log error: {0}")]
+    LoggingFailed(String),
+
+    #[error("Unsupported encoding: {0}")]
+    UnsupportedEncoding(String),
+
+    #[error("Shell integration not supported for shell: {0}")]
+    UnsupportedShell(String),
+
+    #[error("Invalid search pattern: {0}")]
+    InvalidPattern(String),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -4,6 +4,7 @@ use std::sync::{Arc, RwLock};
 use thiserror::Error;
 
 use super::types::Settings;
+use crate::terminal::{HighlightSet, TriggerSet};
 
 /// Settings manager errors
 #[derive(Debug, Error)]
@@ -16,11 +17,21 @@ pub enum SettingsError {
 
     #[error("Settings directory not found")]
     DirectoryNotFound,
+
+    #[error("{0}")]
+    InvalidTrigger(String),
+
+    #[error("{0}")]
+    InvalidHighlight(String),
 }
 
 /// Manages application settings with file persistence
 pub struct SettingsManager {
     settings: Arc<RwLock<Settings>>,
+    /// Compiled trigger rules shared with session output streams
+    triggers: Arc<RwLock<Arc<TriggerSet>>>,
+    /// Compiled highlight rules shared with session output streams
+    highlights: Arc<RwLock<Arc<HighlightSet>>>,
     settings_path: PathBuf,
 }
 
@@ -29,9 +40,20 @@ impl SettingsManager {
     pub fn new() -> Result<Self, SettingsError> {
         let settings_path = Self::get_settings_path()?;
         let settings = Self::load_or_create(&settings_path)?;
+        let triggers = TriggerSet::compile(&settings.triggers).unwrap_or_else(|e| {
+            // Keep the app usable with a hand-edited settings file; fixed on next save
+            eprintln!("Failed to compile triggers ({}), ignoring them", e);
+            TriggerSet::default()
+        });
+        let highlights = HighlightSet::compile(&settings.highlights).unwrap_or_else(|e| {
+            eprintln!("Failed to compile highlights ({}), ignoring them", e);
+            HighlightSet::default()
+        });
 
         Ok(Self {
             settings: Arc::new(RwLock::new(settings)),
+            triggers: Arc::new(RwLock::new(Arc::new(triggers))),
+            highlights: Arc::new(RwLock::new(Arc::new(highlights))),
             settings_path,
         })
     }
@@ -80,16 +102,41 @@ impl SettingsManager {
         self.settings.read().unwrap().clone()
     }
 
+    /// Get the compiled trigger rules
+    pub fn triggers(&self) -> Arc<TriggerSet> {
+        self.triggers.read().unwrap().clone()
+    }
+
+    /// Get the compiled highlight rules
+    pub fn highlights(&self) -> Arc<HighlightSet> {
+        self.highlights.read().unwrap().clone()
+    }
+
     /// Update settings and save to file
     pub fn update_settings(&self, new_settings: Settings) -> Result<(), SettingsError> {
-        {
-            let mut settings = self.settings.write().unwrap();
-            *settings = new_settings.clone();
-        }
+        self.apply(new_settings.clone())?;
         Self::save_to_file(&self.settings_path, &new_settings)?;
         Ok(())
     }
 
+    /// Re-read the settings file saved by another process (the session host follows the GUI)
+    pub fn reload(&self) -> Result<(), SettingsError> {
+        let content = fs::read_to_string(&self.settings_path)?;
+        self.apply(serde_json::from_str(&content)?)
+    }
+
+    /// Compile rules and replace the in-memory settings
+    fn apply(&self, new_settings: Settings) -> Result<(), SettingsError> {
+        let triggers =
+            TriggerSet::compile(&new_settings.triggers).map_err(SettingsError::InvalidTrigger)?;
+        let highlights = HighlightSet::compile(&new_settings.highlights)
+            .map_err(SettingsError::InvalidHighlight)?;
+        *self.settings.write().unwrap() = new_settings;
+        *self.triggers.write().unwrap() = Arc::new(triggers);
+        *self.highlights.write().unwrap() = Arc::new(highlights);
+        Ok(())
+    }
+
     /// Reset settings to default values
     pub fn reset_to_default(&self) -> Result<(), SettingsError> {
         let default_settings = Settings::default();
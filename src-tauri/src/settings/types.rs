@@ -1,3 +1,5 @@
+use crate::pty::LaunchProfile;
+use crate::terminal::{HighlightRule, ScrollbackLimit, SessionLogSettings, TriggerRule};
 use serde::{Deserialize, Serialize};
 
 /// Main settings structure
@@ -11,6 +13,24 @@ pub struct Settings {
     pub font_family: String,
     #[serde(default = "default_terminal_theme_id")]
     pub terminal_theme_id: String,
+    /// Backend scrollback limit per session
+    #[serde(default)]
+    pub scrollback_limit: ScrollbackLimit,
+    /// Trigger rules applied to every session output
+    #[serde(default)]
+    pub triggers: Vec<TriggerRule>,
+    /// Highlight rules applied to every session output
+    #[serde(default)]
+    pub highlights: Vec<HighlightRule>,
+    /// Session logging applied automatically to new sessions
+    #[serde(default)]
+    pub session_logging: SessionLogSettings,
+    /// SSH input audit log (JSON Lines per host, lines that are not echoed back are redacted)
+    #[serde(default)]
+    pub audit_ssh_input: bool,
+    /// Local tab launch profiles (referenced by name from create_pty / add_local_tab)
+    #[serde(default)]
+    pub launch_profiles: Vec<LaunchProfile>,
 }
 
 fn default_app_theme() -> String {
@@ -30,6 +50,21 @@ impl Default for Settings {
             font_size: 14,
             font_family: "Cascadia Code, Consolas, Monaco, monospace".to_string(),
             terminal_theme_id: "retro".to_string(),
+            scrollback_limit: ScrollbackLimit::default(),
+            triggers: Vec::new(),
+            highlights: Vec::new(),
+            session_logging: SessionLogSettings::default(),
+            audit_ssh_input: false,
+            launch_profiles: Vec::new(),
         }
     }
 }
+
+impl Settings {
+    /// Find a launch profile by name
+    pub fn launch_profile(&self, name: &str) -> Option<&LaunchProfile> {
+        self.launch_profiles
+            .iter()
+            .find(|profile| profile.name == name)
+    }
+}
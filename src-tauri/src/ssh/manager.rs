@@ -1,15 +1,28 @@
-use super::session::SshSession;
+use super::session::{FrontendOutput, SshOutput, SshSession, WriteDone};
 use super::types::{CreateSshResponse, SshConfig, SshError};
+use crate::host::{HostClient, SshCall};
+use crate::terminal::{
+    CommandHistoryEntry, MonitorConfig, RecordingInfo, ScreenSnapshot, ScrollbackLimit,
+    ScrollbackReplay, SearchOptions, SearchResult, SessionLogInfo, SessionLogOptions, SessionSink,
+    ShellKind, ShellState,
+};
+use base64::Engine;
+use serde::de::DeserializeOwned;
 use std::collections::HashMap;
 use std::sync::Arc;
+use tauri::ipc::Channel;
 use tauri::AppHandle;
-use tokio::sync::Mutex;
+use tokio::sync::{oneshot, Mutex};
 use uuid::Uuid;
 
 /// SSH 세션 관리자
+///
+/// 세션 호스트 클라이언트가 있으면 이 프로세스에 없는 세션 요청을
+/// 세션 호스트가 소유한 SSH 세션으로 넘깁니다.
 #[derive(Clone)]
 pub struct SshManager {
     sessions: Arc<Mutex<HashMap<String, SshSession>>>,
+    host: Option<HostClient>,
 }
 
 impl SshManager {
@@ -17,40 +30,101 @@ impl SshManager {
     pub fn new() -> Self {
         SshManager {
             sessions: Arc::new(Mutex::new(HashMap::new())),
+            host: None,
+        }
+    }
+
+    /// 세션 호스트의 SSH 세션도 다루는 SSH Manager 생성 (GUI 프로세스용)
+    pub fn with_host(host: HostClient) -> Self {
+        SshManager {
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            host: Some(host),
         }
     }
 
     /// SSH 세션 생성
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_session(
         &self,
         config: SshConfig,
         cols: u16,
         rows: u16,
+        output_channel: Option<Channel>,
+        scrollback_limit: ScrollbackLimit,
+        log_options: Option<SessionLogOptions>,
         app_handle: AppHandle,
     ) -> Result<CreateSshResponse, SshError> {
-        self.create_session_with_id(None, config, cols, rows, app_handle).await
+        self.create_session_with_id(
+            None,
+            config,
+            cols,
+            rows,
+            output_channel,
+            scrollback_limit,
+            log_options,
+            app_handle,
+        )
+        .await
     }
 
     /// SSH 세션 생성 (세션 ID 지정 가능)
     /// IPC에서 탭을 먼저 생성하고 나중에 연결할 때 사용
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_session_with_id(
         &self,
         session_id: Option<String>,
         config: SshConfig,
         cols: u16,
         rows: u16,
+        output_channel: Option<Channel>,
+        scrollback_limit: ScrollbackLimit,
+        log_options: Option<SessionLogOptions>,
         app_handle: AppHandle,
     ) -> Result<CreateSshResponse, SshError> {
         // 세션 ID 생성 또는 사용
         let session_id = session_id.unwrap_or_else(|| Uuid::new_v4().to_string());
 
+        let output = Arc::new(FrontendOutput {
+            session_id: session_id.clone(),
+            channel: output_channel,
+            app_handle: app_handle.clone(),
+        });
+        self.open_session(
+            session_id,
+            config,
+            cols,
+            rows,
+            scrollback_limit,
+            log_options,
+            Arc::new(app_handle),
+            output,
+        )
+        .await
+    }
+
+    /// 출력과 이벤트를 보낼 곳을 지정해 SSH 세션 생성 (세션 호스트에서 사용)
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn open_session(
+        &self,
+        session_id: String,
+        config: SshConfig,
+        cols: u16,
+        rows: u16,
+        scrollback_limit: ScrollbackLimit,
+        log_options: Option<SessionLogOptions>,
+        sink: Arc<dyn SessionSink>,
+        output: Arc<dyn SshOutput>,
+    ) -> Result<CreateSshResponse, SshError> {
         // SSH 세션 생성
         let session = SshSession::new(
             session_id.clone(),
             config.clone(),
             cols,
             rows,
-            app_handle,
+            scrollback_limit,
+            log_options,
+            sink,
+            output,
         )?;
 
         let response = CreateSshResponse {
@@ -68,12 +142,75 @@ impl SshManager {
 
     /// SSH 세션에 데이터 쓰기
     pub async fn write_to_session(&self, session_id: &str, data: &str) -> Result<(), SshError> {
-        let sessions = self.sessions.lock().await;
-        let session = sessions
-            .get(session_id)
-            .ok_or_else(|| SshError::SessionNotFound(session_id.to_string()))?;
+        let done = self.queue_write(session_id, data).await?;
+
+        // 원격이 입력을 읽을 때까지 세션 맵 잠금 없이 대기
+        wait_write(done).await
+    }
+
+    /// SSH 쓰기 요청만 넣고 완료 수신자 반환 (여러 세션에 한꺼번에 쓸 때 사용)
+    pub async fn queue_write(&self, session_id: &str, data: &str) -> Result<WriteDone, SshError> {
+        match self.with_local(session_id, |s| s.write(data)).await {
+            Some(result) => result,
+            None => self.queue_remote(
+                session_id,
+                SshCall::Write {
+                    data: data.to_string(),
+                },
+            ),
+        }
+    }
+
+    /// SSH 세션에 바이트 쓰기 (보내지 못한 입력이 쌓이면 대기, paste면 필요할 때 bracketed paste로 감쌈)
+    pub async fn write_bytes_to_session(
+        &self,
+        session_id: &str,
+        data: Vec<u8>,
+        paste: bool,
+    ) -> Result<(), SshError> {
+        let done = self.queue_write_bytes(session_id, data, paste).await?;
+
+        // 원격이 입력을 읽을 때까지 세션 맵 잠금 없이 대기
+        wait_write(done).await
+    }
 
-        session.write(data).await
+    /// SSH 바이트 쓰기 요청만 넣고 완료 수신자 반환
+    pub async fn queue_write_bytes(
+        &self,
+        session_id: &str,
+        data: Vec<u8>,
+        paste: bool,
+    ) -> Result<WriteDone, SshError> {
+        if let Some(session) = self.sessions.lock().await.get(session_id) {
+            return session.write_bytes(data, paste);
+        }
+
+        self.queue_remote(
+            session_id,
+            SshCall::WriteBytes {
+                data: base64::engine::general_purpose::STANDARD.encode(data),
+                paste,
+            },
+        )
+    }
+
+    /// SSH 세션 문자 인코딩 변경
+    pub async fn set_encoding(&self, session_id: &str, encoding: &str) -> Result<String, SshError> {
+        match self
+            .with_local(session_id, |s| s.set_encoding(encoding))
+            .await
+        {
+            Some(result) => result,
+            None => {
+                self.call_remote(
+                    session_id,
+                    SshCall::SetEncoding {
+                        encoding: encoding.to_string(),
+                    },
+                )
+                .await
+            }
+        }
     }
 
     /// SSH 세션 크기 조정
@@ -83,20 +220,233 @@ impl SshManager {
         cols: u16,
         rows: u16,
     ) -> Result<(), SshError> {
-        let sessions = self.sessions.lock().await;
-        let session = sessions
-            .get(session_id)
-            .ok_or_else(|| SshError::SessionNotFound(session_id.to_string()))?;
+        {
+            let sessions = self.sessions.lock().await;
+            if let Some(session) = sessions.get(session_id) {
+                return session.resize(cols, rows).await;
+            }
+        }
+
+        self.call_remote(session_id, SshCall::Resize { cols, rows })
+            .await
+    }
+
+    /// 출력 이벤트 처리 확인 (흐름 제어)
+    pub async fn ack_output(&self, session_id: &str, seq: u64) -> Result<(), SshError> {
+        match self.with_local(session_id, |s| s.ack_output(seq)).await {
+            Some(()) => Ok(()),
+            None => self.call_remote(session_id, SshCall::Ack { seq }).await,
+        }
+    }
 
-        session.resize(cols, rows).await
+    /// 흐름 제어 해제 (출력을 확인하던 프론트엔드가 detach한 경우)
+    pub async fn release_output(&self, session_id: &str) -> Result<(), SshError> {
+        self.with_local(session_id, |s| s.release_output())
+            .await
+            .ok_or_else(|| SshError::SessionNotFound(session_id.to_string()))
+    }
+
+    /// SSH 세션 스크롤백 검색
+    pub async fn search_session(
+        &self,
+        session_id: &str,
+        pattern: &str,
+        options: &SearchOptions,
+    ) -> Result<SearchResult, SshError> {
+        match self
+            .with_local(session_id, |s| s.search(pattern, options))
+            .await
+        {
+            Some(result) => result,
+            None => {
+                self.call_remote(
+                    session_id,
+                    SshCall::Search {
+                        pattern: pattern.to_string(),
+                        options: options.clone(),
+                    },
+                )
+                .await
+            }
+        }
+    }
+
+    /// SSH 세션 화면 텍스트 조회
+    pub async fn screen_text(&self, session_id: &str) -> Result<String, SshError> {
+        match self.with_local(session_id, |s| s.screen_text()).await {
+            Some(text) => Ok(text),
+            None => self.call_remote(session_id, SshCall::ScreenText).await,
+        }
+    }
+
+    /// SSH 세션 명령 히스토리 조회
+    pub async fn command_history(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<CommandHistoryEntry>, SshError> {
+        match self.with_local(session_id, |s| s.command_history()).await {
+            Some(history) => Ok(history),
+            None => self.call_remote(session_id, SshCall::CommandHistory).await,
+        }
+    }
+
+    /// SSH 세션 활동/무출력 감시 설정
+    pub async fn set_monitor(
+        &self,
+        session_id: &str,
+        config: MonitorConfig,
+    ) -> Result<(), SshError> {
+        {
+            let sessions = self.sessions.lock().await;
+            if let Some(session) = sessions.get(session_id) {
+                session.set_monitor(config);
+                return Ok(());
+            }
+        }
+
+        self.call_remote(session_id, SshCall::SetMonitor { config })
+            .await
+    }
+
+    /// SSH 세션 활동/무출력 감시 설정 조회
+    pub async fn monitor_config(&self, session_id: &str) -> Result<MonitorConfig, SshError> {
+        match self.with_local(session_id, |s| s.monitor_config()).await {
+            Some(config) => Ok(config),
+            None => self.call_remote(session_id, SshCall::MonitorConfig).await,
+        }
+    }
+
+    /// SSH 세션 셸 통합 상태 조회
+    pub async fn shell_state(&self, session_id: &str) -> Result<ShellState, SshError> {
+        match self.with_local(session_id, |s| s.shell_state()).await {
+            Some(state) => Ok(state),
+            None => self.call_remote(session_id, SshCall::ShellState).await,
+        }
+    }
+
+    /// SSH 세션에 셸 통합 스크립트 주입 (원격 셸을 알 수 없으므로 기본값은 bash)
+    pub async fn inject_shell_integration(
+        &self,
+        session_id: &str,
+        shell: Option<ShellKind>,
+    ) -> Result<ShellKind, SshError> {
+        let (shell, done) = self.queue_shell_integration(session_id, shell).await?;
+        wait_write(done).await?;
+        Ok(shell)
+    }
+
+    /// 셸 통합 스크립트 쓰기 요청만 넣고 셸 종류와 완료 수신자 반환
+    pub async fn queue_shell_integration(
+        &self,
+        session_id: &str,
+        shell: Option<ShellKind>,
+    ) -> Result<(ShellKind, WriteDone), SshError> {
+        let shell = shell.unwrap_or(ShellKind::Bash);
+        let done = match self
+            .with_local(session_id, |s| s.write(&shell.injection_command()))
+            .await
+        {
+            Some(result) => result?,
+            None => self.queue_remote(
+                session_id,
+                SshCall::InjectShellIntegration { shell: Some(shell) },
+            )?,
+        };
+        Ok((shell, done))
+    }
+
+    /// SSH 세션 화면 셀 스냅샷 조회
+    pub async fn screen_snapshot(&self, session_id: &str) -> Result<ScreenSnapshot, SshError> {
+        match self.with_local(session_id, |s| s.screen_snapshot()).await {
+            Some(snapshot) => Ok(snapshot),
+            None => self.call_remote(session_id, SshCall::ScreenSnapshot).await,
+        }
+    }
+
+    /// SSH 세션 스크롤백 재생
+    pub async fn replay_session(
+        &self,
+        session_id: &str,
+        from_offset: u64,
+    ) -> Result<ScrollbackReplay, SshError> {
+        match self.with_local(session_id, |s| s.replay(from_offset)).await {
+            Some(replay) => Ok(replay),
+            None => {
+                self.call_remote(session_id, SshCall::Replay { from_offset })
+                    .await
+            }
+        }
+    }
+
+    /// SSH 세션 녹화 시작 (asciicast v2)
+    pub async fn start_recording(
+        &self,
+        session_id: &str,
+        path: Option<String>,
+        title: Option<String>,
+        record_input: bool,
+    ) -> Result<RecordingInfo, SshError> {
+        {
+            let sessions = self.sessions.lock().await;
+            if let Some(session) = sessions.get(session_id) {
+                return session.start_recording(path, title, record_input);
+            }
+        }
+
+        self.call_remote(
+            session_id,
+            SshCall::StartRecording {
+                path,
+                title,
+                record_input,
+            },
+        )
+        .await
+    }
+
+    /// SSH 세션 녹화 종료
+    pub async fn stop_recording(&self, session_id: &str) -> Result<RecordingInfo, SshError> {
+        match self.with_local(session_id, |s| s.stop_recording()).await {
+            Some(result) => result,
+            None => self.call_remote(session_id, SshCall::StopRecording).await,
+        }
+    }
+
+    /// SSH 세션 로그 시작
+    pub async fn start_logging(
+        &self,
+        session_id: &str,
+        options: SessionLogOptions,
+    ) -> Result<SessionLogInfo, SshError> {
+        {
+            let sessions = self.sessions.lock().await;
+            if let Some(session) = sessions.get(session_id) {
+                return session.start_logging(options);
+            }
+        }
+
+        self.call_remote(session_id, SshCall::StartLogging { options })
+            .await
+    }
+
+    /// SSH 세션 로그 종료
+    pub async fn stop_logging(&self, session_id: &str) -> Result<SessionLogInfo, SshError> {
+        match self.with_local(session_id, |s| s.stop_logging()).await {
+            Some(result) => result,
+            None => self.call_remote(session_id, SshCall::StopLogging).await,
+        }
     }
 
     /// SSH 세션 종료
     pub async fn close_session(&self, session_id: &str) -> Result<(), SshError> {
-        let mut sessions = self.sessions.lock().await;
-        sessions
-            .remove(session_id)
-            .ok_or_else(|| SshError::SessionNotFound(session_id.to_string()))?;
+        let Some(session) = self.sessions.lock().await.remove(session_id) else {
+            // 세션 호스트의 세션이면 호스트에서 종료
+            return self.call_remote(session_id, SshCall::Close).await;
+        };
+
+        // 녹화/로그 중이었다면 마무리 (I/O 스레드는 세션 drop 후 종료됨)
+        let _ = session.stop_recording();
+        let _ = session.stop_logging();
 
         Ok(())
     }
@@ -106,6 +456,52 @@ impl SshManager {
         let sessions = self.sessions.lock().await;
         sessions.keys().cloned().collect()
     }
+
+    /// 이 프로세스의 세션에 대해 f 실행 (세션이 없으면 None)
+    async fn with_local<R>(&self, session_id: &str, f: impl FnOnce(&SshSession) -> R) -> Option<R> {
+        let sessions = self.sessions.lock().await;
+        sessions.get(session_id).map(f)
+    }
+
+    /// 세션 호스트의 SSH 세션 호출 (호스트에도 없으면 SessionNotFound)
+    async fn call_remote<T: DeserializeOwned>(
+        &self,
+        session_id: &str,
+        call: SshCall,
+    ) -> Result<T, SshError> {
+        let host = self.remote_host(session_id)?;
+        Ok(host.ssh_call(session_id, call).await?)
+    }
+
+    /// 세션 호스트에 쓰기 호출을 순서대로 넣고 완료 수신자 반환
+    fn queue_remote(&self, session_id: &str, call: SshCall) -> Result<WriteDone, SshError> {
+        let reply = self
+            .remote_host(session_id)?
+            .queue_ssh_call(session_id, call)?;
+
+        let (done_tx, done_rx) = oneshot::channel();
+        tauri::async_runtime::spawn(async move {
+            let result = HostClient::wait::<serde_json::Value>(reply)
+                .await
+                .map(|_| ())
+                .map_err(SshError::from);
+            let _ = done_tx.send(result);
+        });
+        Ok(done_rx)
+    }
+
+    fn remote_host(&self, session_id: &str) -> Result<&HostClient, SshError> {
+        self.host
+            .as_ref()
+            .filter(|host| host.has_ssh_session(session_id))
+            .ok_or_else(|| SshError::SessionNotFound(session_id.to_string()))
+    }
+}
+
+/// I/O 스레드로 넘긴 쓰기 결과 대기
+pub async fn wait_write(done: WriteDone) -> Result<(), SshError> {
+    done.await
+        .map_err(|_| SshError::WriteFailed("Session closed".to_string()))?
 }
 
 impl Default for SshManager {
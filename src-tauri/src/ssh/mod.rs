@@ -2,7 +2,8 @@ mod manager;
 mod session;
 mod types;
 
-pub use manager::SshManager;
+pub use manager::{wait_write, SshManager};
+pub(crate) use session::{SshOutput, WriteDone};
 // AuthMethod와 SshError는 public API의 일부로 export (프론트엔드에서 사용 가능)
 #[allow(unused_imports)]
-pub use types::{AuthMethod, CreateSshResponse, SshConfig, SshError, SshOutputEvent};
+pub use types::{AuthMethod, CreateSshResponse, SshConfig, SshError, SshExitEvent, SshOutputEvent};
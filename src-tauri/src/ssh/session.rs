@@ -1,35 +1,197 @@
 use super::types::{AuthMethod, SshConfig, SshError, SshExitEvent, SshOutputEvent};
+use crate::terminal::{
+    AsciicastRecorder, RecordingInfo, ScreenSnapshot, ScrollbackLimit, ScrollbackReplay,
+    SearchOptions, SearchResult, SessionSink, SessionStream, ShellState,
+    CommandHistoryEntry, MonitorConfig, SessionLogContext, SessionLogInfo, SessionLogOptions,
+    InputAuditor, SessionEncoding, SessionLogger, INPUT_CHUNK_SIZE,
+};
 use ssh2::Session;
+use std::collections::{HashMap, VecDeque};
 use std::io::{Read, Write};
 use std::net::TcpStream;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use std::thread;
+use tauri::ipc::{Channel, InvokeResponseBody};
 use tauri::{AppHandle, Emitter};
 use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TryRecvError;
+use tokio::sync::{oneshot, Semaphore};
+
+/// I/O 스레드가 아직 보내지 못한 입력의 최대 크기 (바이트, 넘으면 쓰기가 대기)
+const MAX_PENDING_INPUT: usize = 256 * 1024;
 
 /// SSH 채널 작업 명령
 enum SshCommand {
-    Write(String),
+    /// 세션 인코딩으로 변환된 입력
+    Write(Vec<u8>),
     Resize(u16, u16),
 }
 
+/// 입력 쓰기 요청 (받은 순서대로 I/O 스레드에 넘김)
+struct WriteJob {
+    data: Vec<u8>,
+    /// 녹화와 감사에 남길 입력
+    input: String,
+    done: oneshot::Sender<Result<(), SshError>>,
+}
+
+/// 쓰기 완료 수신자 (세션 맵을 잠그지 않은 채 기다림)
+pub type WriteDone = oneshot::Receiver<Result<(), SshError>>;
+
+/// I/O 스레드로 입력 보내기
+///
+/// 원격 윈도우가 가득 차 보내지 못한 입력이 MAX_PENDING_INPUT을 넘으면
+/// I/O 스레드가 보낼 때까지 기다립니다 (PTY 입력 버퍼가 찼을 때와 같은 흐름 제어).
+/// 요청은 받은 순서대로 처리하므로 붙여넣기 도중 다른 입력이 끼어들지 않습니다.
+struct InputSender {
+    jobs: mpsc::UnboundedSender<WriteJob>,
+    /// 대기열에 더 넣을 수 있는 바이트 수 (I/O 스레드가 보낸 만큼 돌려줌)
+    budget: Arc<Semaphore>,
+}
+
+impl InputSender {
+    /// on_sent는 I/O 스레드에 넘긴 입력마다 호출 (녹화, 입력 감사)
+    fn new(
+        command_tx: mpsc::UnboundedSender<SshCommand>,
+        on_sent: impl Fn(&str) + Send + 'static,
+    ) -> Self {
+        let budget = Arc::new(Semaphore::new(MAX_PENDING_INPUT));
+        let (jobs, mut job_rx) = mpsc::unbounded_channel::<WriteJob>();
+
+        // 세션이 닫혀 요청 채널이 끊기면 종료
+        let task_budget = budget.clone();
+        tauri::async_runtime::spawn(async move {
+            while let Some(job) = job_rx.recv().await {
+                let result = Self::send_chunks(&command_tx, &task_budget, &job.data).await;
+                if result.is_ok() {
+                    on_sent(&job.input);
+                }
+                let _ = job.done.send(result);
+            }
+        });
+
+        Self { jobs, budget }
+    }
+
+    /// 쓰기 요청 추가
+    fn send(&self, data: Vec<u8>, input: String) -> Result<WriteDone, SshError> {
+        let (done, done_rx) = oneshot::channel();
+        self.jobs
+            .send(WriteJob { data, input, done })
+            .map_err(|_| SshError::WriteFailed("Failed to send write command".to_string()))?;
+        Ok(done_rx)
+    }
+
+    /// 청크 단위로 대기열에 넣기 (대기열에 자리가 날 때까지 대기)
+    async fn send_chunks(
+        command_tx: &mpsc::UnboundedSender<SshCommand>,
+        budget: &Semaphore,
+        data: &[u8],
+    ) -> Result<(), SshError> {
+        for chunk in data.chunks(INPUT_CHUNK_SIZE) {
+            budget
+                .acquire_many(chunk.len() as u32)
+                .await
+                .map_err(|_| SshError::WriteFailed("Session closed".to_string()))?
+                .forget();
+            command_tx
+                .send(SshCommand::Write(chunk.to_vec()))
+                .map_err(|_| SshError::WriteFailed("Failed to send write command".to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+/// SSH 출력을 받는 곳 (GUI 프로세스의 프론트엔드 또는 세션 호스트)
+pub trait SshOutput: Send + Sync {
+    /// 출력 프레임 전달 (seq는 스크롤백 청크 시퀀스 번호)
+    fn frame(&self, seq: u64, frame: Vec<u8>);
+
+    /// 연결 종료 알림 (세션을 닫아 끝난 경우 reason은 None)
+    fn exit(&self, reason: Option<String>);
+}
+
+/// 프론트엔드로 출력 전달
+///
+/// 채널이 있으면 원시 바이트를 보내고 (채널의 n번째 메시지가 seq n에 해당),
+/// 없으면 `ssh://output/{session_id}` 이벤트로 보냅니다.
+pub struct FrontendOutput {
+    pub session_id: String,
+    pub channel: Option<Channel>,
+    pub app_handle: AppHandle,
+}
+
+impl SshOutput for FrontendOutput {
+    fn frame(&self, seq: u64, frame: Vec<u8>) {
+        match &self.channel {
+            Some(channel) => {
+                if let Err(e) = channel.send(InvokeResponseBody::Raw(frame)) {
+                    eprintln!("Failed to send SSH output to channel: {}", e);
+                }
+            }
+            None => {
+                let _ = self.app_handle.emit(
+                    &format!("ssh://output/{}", self.session_id),
+                    SshOutputEvent {
+                        session_id: self.session_id.clone(),
+                        data: String::from_utf8_lossy(&frame).into_owned(),
+                        seq,
+                    },
+                );
+            }
+        }
+    }
+
+    fn exit(&self, reason: Option<String>) {
+        if let Some(reason) = reason {
+            let _ = self.app_handle.emit(
+                &format!("ssh://exit/{}", self.session_id),
+                SshExitEvent {
+                    session_id: self.session_id.clone(),
+                    reason,
+                },
+            );
+        }
+    }
+}
+
 /// SSH 세션
 pub struct SshSession {
-    #[allow(dead_code)]
     session_id: String,
-    #[allow(dead_code)]
     config: SshConfig,
     command_tx: mpsc::UnboundedSender<SshCommand>,
+    input: InputSender,
+    /// 현재 터미널 크기 (cols, rows)
+    size: Mutex<(u16, u16)>,
+    /// 출력 스트림 (스크롤백, 녹화)
+    stream: Arc<SessionStream>,
+    /// 문자 인코딩 (I/O 스레드와 공유, 실행 중 변경 가능)
+    encoding: Arc<SessionEncoding>,
 }
 
 impl SshSession {
     /// 새 SSH 세션 생성 및 연결
+    ///
+    /// 출력 프레임과 종료는 output으로, 트리거/셸 통합 이벤트는 sink로 보냅니다.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         session_id: String,
         config: SshConfig,
         cols: u16,
         rows: u16,
-        app_handle: AppHandle,
+        scrollback_limit: ScrollbackLimit,
+        log_options: Option<SessionLogOptions>,
+        sink: Arc<dyn SessionSink>,
+        output: Arc<dyn SshOutput>,
     ) -> Result<Self, SshError> {
+        // 문자 인코딩 (연결 전에 확인)
+        let encoding = Arc::new(
+            SessionEncoding::new(config.encoding.as_deref()).ok_or_else(|| {
+                SshError::UnsupportedEncoding(config.encoding.clone().unwrap_or_default())
+            })?,
+        );
+
         // TCP 연결 생성
         let tcp = TcpStream::connect(format!("{}:{}", config.host, config.port))
             .map_err(|e| SshError::ConnectionFailed(format!("TCP connection failed: {}", e)))?;
@@ -61,16 +223,209 @@ impl SshSession {
         // mpsc 채널 생성 (쓰기 및 리사이즈 명령 전송용)
         let (command_tx, command_rx) = mpsc::unbounded_channel();
 
+        let stream = Arc::new(SessionStream::new(
+            session_id.clone(),
+            config.host.clone(),
+            scrollback_limit,
+            cols,
+            rows,
+            sink.clone(),
+        ));
+
+        // 세션 로그 (I/O 스레드 시작 전에 열어 첫 출력부터 기록)
+        if let Some(options) = log_options {
+            let logger = SessionLogger::create(options, Self::log_context(&session_id, &config))
+                .map_err(|e| SshError::LoggingFailed(e.to_string()))?;
+            stream
+                .logging()
+                .start(logger)
+                .map_err(SshError::LoggingFailed)?;
+        }
+
+        // 입력 감사 (설정에서 켠 경우, 호스트별 파일에 기록)
+        if sink
+            .settings()
+            .is_some_and(|settings| settings.get_settings().audit_ssh_input)
+        {
+            stream.start_input_audit(InputAuditor::new(
+                session_id.clone(),
+                config.host.clone(),
+                config.username.clone(),
+                InputAuditor::default_path(&config.host),
+            ));
+        }
+
+        let input = InputSender::new(command_tx.clone(), {
+            let stream = stream.clone();
+            move |input| stream.push_input(input)
+        });
+
         // 백그라운드 I/O 스레드 시작 (읽기/쓰기 모두 처리)
-        Self::start_io_thread(session_id.clone(), session, channel, command_rx, app_handle);
+        Self::start_io_thread(
+            session,
+            channel,
+            command_rx,
+            input.budget.clone(),
+            stream.clone(),
+            encoding.clone(),
+            output,
+        );
 
         Ok(Self {
             session_id,
             config,
             command_tx,
+            input,
+            size: Mutex::new((cols, rows)),
+            stream,
+            encoding,
         })
     }
 
+    /// 프론트엔드의 출력 처리 확인 (흐름 제어)
+    pub fn ack_output(&self, seq: u64) {
+        self.stream.ack_output(seq);
+    }
+
+    /// 흐름 제어 해제 (출력을 확인하던 프론트엔드가 detach한 경우)
+    pub fn release_output(&self) {
+        self.stream.release_output();
+    }
+
+    /// 스크롤백 검색
+    pub fn search(
+        &self,
+        pattern: &str,
+        options: &SearchOptions,
+    ) -> Result<SearchResult, SshError> {
+        self.stream
+            .search(pattern, options)
+            .map_err(SshError::InvalidPattern)
+    }
+
+    /// 현재 화면 텍스트
+    pub fn screen_text(&self) -> String {
+        self.stream.screen_text()
+    }
+
+    /// 현재 화면 셀 스냅샷
+    pub fn screen_snapshot(&self) -> ScreenSnapshot {
+        self.stream.screen_snapshot()
+    }
+
+    /// 셸 통합 상태
+    pub fn shell_state(&self) -> ShellState {
+        self.stream.shell_state()
+    }
+
+    /// 명령 히스토리
+    pub fn command_history(&self) -> Vec<CommandHistoryEntry> {
+        self.stream.command_history()
+    }
+
+    /// 활동/무출력 감시 설정
+    pub fn set_monitor(&self, config: MonitorConfig) {
+        self.stream.set_monitor(config);
+    }
+
+    /// 활동/무출력 감시 설정 조회
+    pub fn monitor_config(&self) -> MonitorConfig {
+        self.stream.monitor_config()
+    }
+
+    /// 스크롤백 재생 (from_offset 이후의 출력)
+    pub fn replay(&self, from_offset: u64) -> ScrollbackReplay {
+        self.stream.replay(from_offset)
+    }
+
+    /// asciicast v2 녹화 시작
+    pub fn start_recording(
+        &self,
+        path: Option<String>,
+        title: Option<String>,
+        record_input: bool,
+    ) -> Result<RecordingInfo, SshError> {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => AsciicastRecorder::default_path(&self.session_id).ok_or_else(|| {
+                SshError::RecordingFailed("Config directory not found".to_string())
+            })?,
+        };
+
+        let (cols, rows) = *self.size.lock().unwrap();
+        let title =
+            title.or_else(|| Some(format!("{}@{}", self.config.username, self.config.host)));
+        let env = HashMap::from([("TERM".to_string(), "xterm-256color".to_string())]);
+
+        let recorder = AsciicastRecorder::create(&path, cols, rows, title, env, record_input)
+            .map_err(|e| SshError::RecordingFailed(e.to_string()))?;
+        self.stream
+            .recording()
+            .start(recorder)
+            .map_err(SshError::RecordingFailed)?;
+
+        Ok(RecordingInfo {
+            session_id: self.session_id.clone(),
+            path: path.to_string_lossy().to_string(),
+        })
+    }
+
+    /// 녹화 종료
+    pub fn stop_recording(&self) -> Result<RecordingInfo, SshError> {
+        let path = self
+            .stream
+            .recording()
+            .stop()
+            .ok_or_else(|| SshError::RecordingFailed("Not recording".to_string()))?
+            .map_err(|e| SshError::RecordingFailed(e.to_string()))?;
+
+        Ok(RecordingInfo {
+            session_id: self.session_id.clone(),
+            path: path.to_string_lossy().to_string(),
+        })
+    }
+
+    /// 세션 로그 시작
+    pub fn start_logging(&self, options: SessionLogOptions) -> Result<SessionLogInfo, SshError> {
+        let context = Self::log_context(&self.session_id, &self.config);
+        let logger = SessionLogger::create(options, context)
+            .map_err(|e| SshError::LoggingFailed(e.to_string()))?;
+        let path = logger.path().to_string_lossy().to_string();
+        self.stream
+            .logging()
+            .start(logger)
+            .map_err(SshError::LoggingFailed)?;
+
+        Ok(SessionLogInfo {
+            session_id: self.session_id.clone(),
+            path,
+        })
+    }
+
+    /// 세션 로그 종료
+    pub fn stop_logging(&self) -> Result<SessionLogInfo, SshError> {
+        let path = self
+            .stream
+            .logging()
+            .stop()
+            .ok_or_else(|| SshError::LoggingFailed("Not logging".to_string()))?
+            .map_err(|e| SshError::LoggingFailed(e.to_string()))?;
+
+        Ok(SessionLogInfo {
+            session_id: self.session_id.clone(),
+            path: path.to_string_lossy().to_string(),
+        })
+    }
+
+    /// 로그 파일 이름에 들어갈 세션 정보
+    fn log_context(session_id: &str, config: &SshConfig) -> SessionLogContext {
+        SessionLogContext {
+            session_id: session_id.to_string(),
+            host: config.host.clone(),
+            user: config.username.clone(),
+        }
+    }
+
     /// SSH 인증 수행
     fn authenticate(session: &mut Session, config: &SshConfig) -> Result<(), SshError> {
         match &config.auth_method {
@@ -116,14 +471,17 @@ impl SshSession {
     /// 백그라운드 스레드에서 SSH I/O 처리 (읽기/쓰기 통합)
     ///
     /// 동일한 SSH 채널에서 읽기와 쓰기를 모두 처리합니다.
-    /// - 읽기: 지속적으로 SSH 출력을 읽어 Tauri 이벤트로 전송
+    /// - 읽기: 지속적으로 SSH 출력을 읽어 output으로 전송
     /// - 쓰기: command_rx를 통해 받은 명령(Write, Resize) 처리
+    #[allow(clippy::too_many_arguments)]
     fn start_io_thread(
-        session_id: String,
         session: Session,
         mut channel: ssh2::Channel,
         mut command_rx: mpsc::UnboundedReceiver<SshCommand>,
-        app_handle: AppHandle,
+        input_budget: Arc<Semaphore>,
+        stream: Arc<SessionStream>,
+        encoding: Arc<SessionEncoding>,
+        output: Arc<dyn SshOutput>,
     ) {
         thread::spawn(move || {
             // 세션을 논블로킹 모드로 설정 (채널도 자동으로 논블로킹이 됨)
@@ -133,19 +491,30 @@ impl SshSession {
             let _session = session;
 
             let mut buffer = [0u8; 4096];
+            // 아직 보내지 못한 입력 (원격 윈도우가 가득 차면 다음 반복에서 이어서 보냄)
+            let mut pending_input: VecDeque<u8> = VecDeque::new();
 
-            loop {
+            // 출력은 flusher 스레드에서 프레임 단위로 모아 output으로 전달
+            let flusher = {
+                let output = output.clone();
+                stream.spawn_flusher(move |seq, frame| output.frame(seq, frame))
+            };
+
+            // 연결 종료 사유 (세션이 닫혀 스레드가 끝나는 경우 None)
+            let exit_reason = 'io: loop {
                 // 1. 쓰기/리사이즈 명령 처리 (non-blocking)
-                while let Ok(cmd) = command_rx.try_recv() {
-                    match cmd {
-                        SshCommand::Write(data) => {
-                            if let Err(e) = channel.write_all(data.as_bytes()) {
-                                eprintln!("SSH write error: {}", e);
-                            }
-                            if let Err(e) = channel.flush() {
-                                eprintln!("SSH flush error: {}", e);
-                            }
+                loop {
+                    let cmd = match command_rx.try_recv() {
+                        Ok(cmd) => cmd,
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => {
+                            // 세션이 닫힘 (SshSession drop) - 스레드 종료
+                            break 'io None;
                         }
+                    };
+
+                    match cmd {
+                        SshCommand::Write(data) => pending_input.extend(data),
                         SshCommand::Resize(cols, rows) => {
                             if let Err(e) =
                                 channel.request_pty_size(cols as u32, rows as u32, None, None)
@@ -156,28 +525,41 @@ impl SshSession {
                     }
                 }
 
-                // 2. 읽기 처리
+                // 2. 입력 전송 (논블로킹 채널이므로 보낼 수 있는 만큼만 청크 단위로 씀)
+                // flush()는 libssh2에서 수신 버퍼를 비우는 동작이라 호출하지 않음
+                while !pending_input.is_empty() {
+                    let (chunk, _) = pending_input.as_slices();
+                    let chunk = &chunk[..chunk.len().min(INPUT_CHUNK_SIZE)];
+                    match channel.write(chunk) {
+                        Ok(0) => break,
+                        Ok(n) => {
+                            pending_input.drain(..n);
+                            input_budget.add_permits(n);
+                        }
+                        Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => break,
+                        Err(e) => {
+                            eprintln!("SSH write error: {}", e);
+                            input_budget.add_permits(pending_input.len());
+                            pending_input.clear();
+                        }
+                    }
+                }
+
+                // 3. 프론트엔드가 출력을 따라오지 못하면 읽기를 건너뜀 (명령 처리는 계속)
+                if !stream.wait_ready(Some(std::time::Duration::from_millis(10))) {
+                    continue;
+                }
+
+                // 4. 읽기 처리
                 match channel.read(&mut buffer) {
                     Ok(0) => {
                         // EOF - 연결 종료
-                        let _ = app_handle.emit(
-                            &format!("ssh://exit/{}", session_id),
-                            SshExitEvent {
-                                session_id: session_id.clone(),
-                                reason: "Connection closed".to_string(),
-                            },
-                        );
-                        break;
+                        break Some("Connection closed".to_string());
                     }
                     Ok(n) => {
-                        let data = String::from_utf8_lossy(&buffer[..n]).to_string();
-                        let _ = app_handle.emit(
-                            &format!("ssh://output/{}", session_id),
-                            SshOutputEvent {
-                                session_id: session_id.clone(),
-                                data,
-                            },
-                        );
+                        // 청크 경계에서 잘린 문자는 다음 read로 이월
+                        let data = encoding.decode(&buffer[..n]);
+                        stream.queue_output(data.as_bytes());
                     }
                     Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
                         // 데이터 없음, 잠시 대기
@@ -185,26 +567,63 @@ impl SshSession {
                     }
                     Err(e) => {
                         eprintln!("SSH read error: {}", e);
-                        let _ = app_handle.emit(
-                            &format!("ssh://exit/{}", session_id),
-                            SshExitEvent {
-                                session_id: session_id.clone(),
-                                reason: format!("Read error: {}", e),
-                            },
-                        );
-                        break;
+                        break Some(format!("Read error: {}", e));
                     }
                 }
+            };
+
+            // 대기열에 자리가 나기를 기다리는 쓰기 종료
+            input_budget.close();
+
+            // 남은 출력을 모두 보낸 뒤 종료 이벤트 발행
+            stream.queue_output(encoding.finish().as_bytes());
+            stream.close_output();
+            let _ = flusher.join();
+
+            // 녹화 중이었다면 마무리
+            if let Some(Err(e)) = stream.recording().stop() {
+                eprintln!("Failed to finish SSH recording: {}", e);
             }
+            if let Some(Err(e)) = stream.logging().stop() {
+                eprintln!("Failed to finish SSH session log: {}", e);
+            }
+
+            output.exit(exit_reason);
         });
     }
 
-    /// SSH 세션에 데이터 쓰기
-    pub async fn write(&self, data: &str) -> Result<(), SshError> {
-        self.command_tx
-            .send(SshCommand::Write(data.to_string()))
-            .map_err(|_| SshError::WriteFailed("Failed to send write command".to_string()))?;
-        Ok(())
+    /// SSH 세션에 데이터 쓰기 (녹화, 입력 감사 포함)
+    ///
+    /// 세션 맵을 잠근 채 기다리지 않도록 I/O 스레드에 넘긴 뒤 완료 수신자를 반환합니다.
+    pub fn write(&self, data: &str) -> Result<WriteDone, SshError> {
+        self.input
+            .send(self.encoding.encode(data).into_owned(), data.to_string())
+    }
+
+    /// SSH 세션에 바이트 쓰기 (인코딩 변환 없음, paste면 필요할 때 bracketed paste로 감쌈)
+    ///
+    /// 큰 입력은 I/O 스레드가 원격 윈도우가 허용하는 만큼씩 나눠 보내며, 보내지 못한 입력이 쌓이면
+    /// 원격이 읽을 때까지 기다립니다.
+    pub fn write_bytes(&self, data: Vec<u8>, paste: bool) -> Result<WriteDone, SshError> {
+        // 녹화와 감사에는 bracketed paste 시퀀스를 빼고 입력한 내용만 남김
+        let input = String::from_utf8_lossy(&data).into_owned();
+        let data = if paste {
+            self.stream.prepare_paste(data)
+        } else {
+            data
+        };
+        self.input.send(data, input)
+    }
+
+    /// 문자 인코딩 변경 (변경된 인코딩 이름 반환)
+    pub fn set_encoding(&self, label: &str) -> Result<String, SshError> {
+        let rest = self
+            .encoding
+            .set(label)
+            .ok_or_else(|| SshError::UnsupportedEncoding(label.to_string()))?;
+        // 이전 인코딩에서 남은 바이트
+        self.stream.queue_output(rest.as_bytes());
+        Ok(self.encoding.name().to_string())
     }
 
     /// SSH PTY 크기 조정
@@ -212,6 +631,66 @@ impl SshSession {
         self.command_tx
             .send(SshCommand::Resize(cols, rows))
             .map_err(|_| SshError::ResizeFailed("Failed to send resize command".to_string()))?;
+        *self.size.lock().unwrap() = (cols, rows);
+        self.stream.push_resize(cols, rows);
         Ok(())
     }
 }
+
+impl Drop for SshSession {
+    /// 대기열에 자리가 나기를 기다리는 쓰기 종료 (I/O 스레드는 명령 채널이 닫히면 종료)
+    fn drop(&mut self) {
+        self.input.budget.close();
+    }
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queued(command_rx: &mut mpsc::UnboundedReceiver<SshCommand>) -> Vec<Vec<u8>> {
+        let mut chunks = Vec::new();
+        while let Ok(SshCommand::Write(chunk)) = command_rx.try_recv() {
+            chunks.push(chunk);
+        }
+        chunks
+    }
+
+    #[tokio::test]
+    async fn test_input_waits_for_queue_space() {
+        let (command_tx, mut command_rx) = mpsc::unbounded_channel();
+        let sender = InputSender::new(command_tx, |_| {});
+
+        let paste = sender
+            .send(
+                vec![b'x'; MAX_PENDING_INPUT + INPUT_CHUNK_SIZE],
+                String::new(),
+            )
+            .unwrap();
+        let typed = sender.send(b"ls".to_vec(), "ls".to_string()).unwrap();
+
+        // 대기열이 가득 차면 I/O 스레드가 보낼 때까지 대기 (뒤의 입력은 끼어들지 않음)
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        let chunks = queued(&mut command_rx);
+        assert_eq!(
+            chunks.iter().map(Vec::len).sum::<usize>(),
+            MAX_PENDING_INPUT
+        );
+
+        sender.budget.add_permits(INPUT_CHUNK_SIZE + 2);
+        paste.await.unwrap().unwrap();
+        typed.await.unwrap().unwrap();
+        let chunks = queued(&mut command_rx);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1], b"ls");
+
+        // 세션이 닫히면 대기 중인 쓰기는 실패
+        sender.budget.close();
+        let closed = sender.send(b"x".to_vec(), "x".to_string()).unwrap();
+        assert!(closed.await.unwrap().is_err());
+    }
+}
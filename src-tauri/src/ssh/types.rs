@@ -1,3 +1,4 @@
+use crate::host::HostError;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -10,6 +11,9 @@ pub struct SshConfig {
     pub username: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub auth_method: Option<AuthMethod>,
+    /// 서버 문자 인코딩 (예: "euc-kr", "shift_jis", 생략하면 UTF-8)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
 }
 
 /// SSH 인증 방법
@@ -61,6 +65,8 @@ pub struct ResizeSshRequest {
 pub struct SshOutputEvent {
     pub session_id: String,
     pub data: String,
+    /// 출력 시퀀스 번호 (누락 감지용, 0부터 1씩 증가)
+    pub seq: u64,
 }
 
 /// SSH 연결 종료 이벤트 페이로드
@@ -88,11 +94,37 @@ pub enum SshError {
     #[error("Failed to resize SSH session: {0}")]
     ResizeFailed(String),
 
+    #[error("Recording error: {0}")]
+    RecordingFailed(String),
+
+    #[error("Session log error: {0}")]
+    LoggingFailed(String),
+
+    #[error("Unsupported encoding: {0}")]
+    UnsupportedEncoding(String),
+
+    #[error("Invalid search pattern: {0}")]
+    InvalidPattern(String),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
     #[error("SSH error: {0}")]
     SshError(String),
+
+    /// 세션 호스트가 소유한 세션의 요청 실패
+    #[error("{0}")]
+    Remote(String),
+}
+
+impl From<HostError> for SshError {
+    fn from(err: HostError) -> Self {
+        match err {
+            // 호스트 쪽 SshError 메시지를 그대로 전달
+            HostError::RequestFailed(message) => SshError::Remote(message),
+            err => SshError::Remote(err.to_string()),
+        }
+    }
 }
 
 impl From<SshError> for String {
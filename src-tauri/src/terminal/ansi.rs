@@ -0,0 +1,75 @@
+use vte::{Parser, Perform};
+
+/// 출력에서 ANSI 이스케이프 시퀀스와 제어 문자를 제거한 텍스트 반환
+///
+/// 출력 문자와 개행(\n), 탭(\t)만 남깁니다.
+pub fn strip_ansi(data: &[u8]) -> String {
+    AnsiStripper::new().push(data)
+}
+
+/// 청크 단위로 들어오는 출력의 ANSI 제거기
+///
+/// 파서 상태를 유지하므로 이스케이프 시퀀스나 UTF-8 문자가 청크 경계에서 잘려도 올바르게 처리합니다.
+pub struct AnsiStripper {
+    parser: Parser,
+}
+
+impl AnsiStripper {
+    pub fn new() -> Self {
+        Self {
+            parser: Parser::new(),
+        }
+    }
+
+    /// 청크를 처리하고 새로 확정된 텍스트 반환
+    pub fn push(&mut self, data: &[u8]) -> String {
+        let mut text = PlainText(String::with_capacity(data.len()));
+        self.parser.advance(&mut text, data);
+        text.0
+    }
+}
+
+impl Default for AnsiStripper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct PlainText(String);
+
+impl Perform for PlainText {
+    fn print(&mut self, c: char) {
+        self.0.push(c);
+    }
+
+    fn execute(&mut self, byte: u8) {
+        if matches!(byte, b'\n' | b'\t') {
+            self.0.push(byte as char);
+        }
+    }
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_ansi() {
+        let data = "\x1b[1;31merror\x1b[0m: 실패\r\n\x1b]0;title\x07done\t!";
+        assert_eq!(strip_ansi(data.as_bytes()), "error: 실패\ndone\t!");
+    }
+
+    #[test]
+    fn test_stripper_split_chunks() {
+        let data = "\x1b[1;31merror\x1b[0m: 실패".as_bytes();
+        let mut stripper = AnsiStripper::new();
+        let mut text = stripper.push(&data[..3]);
+        text.push_str(&stripper.push(&data[3..data.len() - 2]));
+        text.push_str(&stripper.push(&data[data.len() - 2..]));
+        assert_eq!(text, "error: 실패");
+    }
+}
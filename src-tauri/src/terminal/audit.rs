@@ -0,0 +1,547 @@
+use super::ansi::AnsiStripper;
+use super::history::sanitize_host;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 프롬프트로 기억할 출력 줄의 최대 길이
+const MAX_PROMPT_LEN: usize = 4096;
+
+/// 에코를 기다리는 최대 시간 (밀리초, 지나면 가린 채 기록)
+const ECHO_TIMEOUT_MS: u64 = 5_000;
+
+/// 에코를 기다리는 줄의 최대 수 (넘으면 오래된 줄부터 가린 채 기록)
+const MAX_PENDING_LINES: usize = 64;
+
+/// 입력을 가려야 하는 프롬프트 (출력의 마지막 미완성 줄 기준)
+const SECRET_PROMPT_PATTERN: &str = r"(?i)(password|passphrase|passcode|\bpin\b|otp|one-time|verification code|token|secret|비밀번호|암호)[^:]*:\s*$";
+
+/// 입력 감사 기록 (호스트별 JSON Lines 파일의 한 줄)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputAuditRecord {
+    /// 줄 입력을 마친 시각 (Unix epoch 밀리초)
+    pub time: u64,
+    pub session_id: String,
+    pub host: String,
+    pub user: String,
+    /// 입력한 줄 (가려진 경우 None)
+    pub line: Option<String>,
+    /// 에코되지 않았거나(비밀번호 입력 등) 비밀번호 프롬프트 뒤라 내용을 가렸는지
+    pub redacted: bool,
+    /// Enter 대신 Ctrl+C로 취소된 줄인지
+    pub cancelled: bool,
+}
+
+/// 입력 이스케이프 시퀀스 건너뛰기 상태 (방향키, 붙여넣기 마커 등)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum InputEscape {
+    None,
+    Escape,
+    Csi,
+    Ss3,
+}
+
+/// 세션 입력 감사기
+///
+/// 키 입력을 줄 단위로 재구성해 Enter나 Ctrl+C마다 한 건씩 기록합니다.
+/// 입력한 글자가 출력에 그대로 에코된 줄만 내용을 남기고, 에코를 확인하지 못한 줄(원격 에코가 꺼진
+/// 비밀번호 입력 등)과 비밀번호 프롬프트 뒤의 줄은 가립니다. 붙여넣기처럼 Enter까지 한 번에 쓴 줄은
+/// 에코를 최대 ECHO_TIMEOUT_MS 동안 기다렸다가 기록합니다.
+/// 방향키나 탭 완성처럼 원격 셸이 편집한 내용은 반영하지 않습니다.
+pub struct InputAuditor {
+    session_id: String,
+    host: String,
+    user: String,
+    /// 감사 파일 경로 (None이면 디스크에 저장하지 않음)
+    path: Option<PathBuf>,
+    secret_prompt: Regex,
+    /// 입력 중인 줄
+    line: String,
+    /// 입력 중인 줄의 에코 확인 상태
+    echo: EchoTracker,
+    /// 현재 줄 입력을 시작할 때의 프롬프트 (입력 전이면 None)
+    prompt: Option<String>,
+    /// 에코 확인을 기다리는 완성된 줄 (입력 순서)
+    pending: VecDeque<PendingLine>,
+    /// 출력의 마지막 미완성 줄
+    output_line: String,
+    stripper: AnsiStripper,
+    escape: InputEscape,
+}
+
+/// 입력 글자의 에코 확인 상태
+#[derive(Debug, Default)]
+struct EchoTracker {
+    /// 아직 에코를 확인하지 못한 입력 글자
+    expected: VecDeque<char>,
+    /// expected 앞에서부터 출력에 연달아 나온 글자 수
+    matched: usize,
+    /// 입력한 글자가 에코된 적이 있는지
+    echoed: bool,
+}
+
+impl EchoTracker {
+    fn push(&mut self, c: char) {
+        self.expected.push_back(c);
+    }
+
+    /// 마지막 입력 글자 지우기 (이미 에코된 글자면 무시)
+    fn pop(&mut self) {
+        self.expected.pop_back();
+        if self.matched > 0 && self.matched >= self.expected.len() {
+            self.complete_run();
+        }
+    }
+
+    fn waiting(&self) -> bool {
+        !self.expected.is_empty()
+    }
+
+    /// 입력한 글자가 모두 에코되었는지
+    fn complete(&self) -> bool {
+        self.echoed && self.expected.is_empty()
+    }
+
+    /// 출력 글자 확인 (입력이 끊기지 않고 그대로 출력되어야 에코로 인정, 제어 문자는 무시)
+    fn observe(&mut self, c: char) {
+        if c.is_control() || self.expected.is_empty() {
+            return;
+        }
+        if self.expected.get(self.matched) != Some(&c) {
+            self.matched = 0;
+            if self.expected.front() != Some(&c) {
+                return;
+            }
+        }
+
+        self.matched += 1;
+        if self.matched == self.expected.len() {
+            self.complete_run();
+        }
+    }
+
+    fn complete_run(&mut self) {
+        self.expected.clear();
+        self.matched = 0;
+        self.echoed = true;
+    }
+}
+
+/// 에코 확인을 기다리는 줄
+struct PendingLine {
+    time: u64,
+    line: String,
+    cancelled: bool,
+    /// 비밀번호 프롬프트 뒤에 입력한 줄
+    secret: bool,
+    echo: EchoTracker,
+}
+
+impl PendingLine {
+    /// 더 기다리지 않아도 기록 내용이 정해졌는지
+    fn decided(&self) -> bool {
+        self.secret || self.echo.complete()
+    }
+}
+
+impl InputAuditor {
+    pub fn new(session_id: String, host: String, user: String, path: Option<PathBuf>) -> Self {
+        Self {
+            session_id,
+            host,
+            user,
+            path,
+            secret_prompt: Regex::new(SECRET_PROMPT_PATTERN).unwrap(),
+            line: String::new(),
+            echo: EchoTracker::default(),
+            prompt: None,
+            pending: VecDeque::new(),
+            output_line: String::new(),
+            stripper: AnsiStripper::new(),
+            escape: InputEscape::None,
+        }
+    }
+
+    /// 기본 감사 파일 경로 (설정 디렉토리/rusterm/audit/{host}.jsonl)
+    pub fn default_path(host: &str) -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| {
+            dir.join("rusterm")
+                .join("audit")
+                .join(format!("{}.jsonl", sanitize_host(host)))
+        })
+    }
+
+    /// 입력 기록 (기록 내용이 정해진 줄은 감사 파일에 추가)
+    pub fn input(&mut self, data: &str) {
+        let records = self.feed_input(data, now_millis());
+        self.append(&records);
+    }
+
+    /// 출력 관찰 (에코 확인, 프롬프트 추적)
+    pub fn output(&mut self, data: &[u8]) {
+        let records = self.feed_output(data, now_millis());
+        self.append(&records);
+    }
+
+    fn append(&self, records: &[InputAuditRecord]) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        for record in records {
+            if let Err(e) = append_record(path, record) {
+                eprintln!("Failed to write input audit log: {}", e);
+            }
+        }
+    }
+
+    /// 입력을 줄 단위로 재구성해 기록 내용이 정해진 줄 반환
+    fn feed_input(&mut self, data: &str, now: u64) -> Vec<InputAuditRecord> {
+        for c in data.chars() {
+            match self.escape {
+                InputEscape::Escape => {
+                    self.escape = match c {
+                        '[' => InputEscape::Csi,
+                        'O' => InputEscape::Ss3,
+                        _ => InputEscape::None,
+                    };
+                    continue;
+                }
+                InputEscape::Csi => {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        self.escape = InputEscape::None;
+                    }
+                    continue;
+                }
+                InputEscape::Ss3 => {
+                    self.escape = InputEscape::None;
+                    continue;
+                }
+                InputEscape::None => {}
+            }
+
+            match c {
+                '\x1b' => self.escape = InputEscape::Escape,
+                '\r' | '\n' => self.finish_line(now, false),
+                '\x03' => self.finish_line(now, true),
+                '\x7f' | '\x08' => {
+                    let len = self.line.chars().count();
+                    self.erase_from(len.saturating_sub(1));
+                }
+                // Ctrl+U: 줄 지우기
+                '\x15' => self.erase_from(0),
+                // Ctrl+W: 단어 지우기
+                '\x17' => {
+                    let kept = self.line.trim_end().rfind(' ').map_or(0, |i| i + 1);
+                    self.erase_from(self.line[..kept].chars().count());
+                }
+                c if c == '\t' || !c.is_control() => {
+                    if self.prompt.is_none() {
+                        self.prompt = Some(self.output_line.trim_end().to_string());
+                    }
+                    self.line.push(c);
+                    if c != '\t' {
+                        self.echo.push(c);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.take_ready(now)
+    }
+
+    /// 출력에서 에코 확인 후 기록 내용이 정해진 줄 반환
+    fn feed_output(&mut self, data: &[u8], now: u64) -> Vec<InputAuditRecord> {
+        let text = self.stripper.push(data);
+        for c in text.chars() {
+            // 에코는 입력 순서대로 돌아오므로 에코를 기다리는 가장 오래된 줄과 비교
+            match self.pending.iter_mut().find(|p| p.echo.waiting()) {
+                Some(pending) => pending.echo.observe(c),
+                None => self.echo.observe(c),
+            }
+
+            if c == '\n' {
+                self.output_line.clear();
+            } else if self.output_line.len() < MAX_PROMPT_LEN {
+                self.output_line.push(c);
+            }
+        }
+
+        self.take_ready(now)
+    }
+
+    /// 입력 중인 줄에서 start번째 글자부터 지우기
+    fn erase_from(&mut self, start: usize) {
+        let byte_start = self
+            .line
+            .char_indices()
+            .nth(start)
+            .map_or(self.line.len(), |(i, _)| i);
+        for c in self.line.split_off(byte_start).chars().rev() {
+            if c != '\t' {
+                self.echo.pop();
+            }
+        }
+    }
+
+    /// 현재 줄을 에코 확인 대기열에 추가 (빈 줄은 기록하지 않음)
+    fn finish_line(&mut self, now: u64, cancelled: bool) {
+        let line = std::mem::take(&mut self.line);
+        let echo = std::mem::take(&mut self.echo);
+        let secret = self
+            .prompt
+            .take()
+            .is_some_and(|prompt| self.secret_prompt.is_match(&prompt));
+
+        if !line.is_empty() {
+            self.pending.push_back(PendingLine {
+                time: now,
+                line,
+                cancelled,
+                secret,
+                echo,
+            });
+        }
+    }
+
+    /// 앞에서부터 기록 내용이 정해졌거나 에코 대기 시간이 지난 줄을 기록으로 변환
+    ///
+    /// 에코를 끝내 확인하지 못한 줄은 가립니다.
+    fn take_ready(&mut self, now: u64) -> Vec<InputAuditRecord> {
+        let mut records = Vec::new();
+        while let Some(pending) = self.pending.front() {
+            let expired = now.saturating_sub(pending.time) >= ECHO_TIMEOUT_MS;
+            if !pending.decided() && !expired && self.pending.len() <= MAX_PENDING_LINES {
+                break;
+            }
+            if let Some(pending) = self.pending.pop_front() {
+                records.push(self.resolve(pending));
+            }
+        }
+        records
+    }
+
+    fn resolve(&self, pending: PendingLine) -> InputAuditRecord {
+        let redacted = pending.secret || !pending.echo.complete();
+        InputAuditRecord {
+            time: pending.time,
+            session_id: self.session_id.clone(),
+            host: self.host.clone(),
+            user: self.user.clone(),
+            line: (!redacted).then_some(pending.line),
+            redacted,
+            cancelled: pending.cancelled,
+        }
+    }
+}
+
+impl Drop for InputAuditor {
+    /// 세션이 끝날 때까지 에코를 확인하지 못한 줄은 가린 채 기록
+    fn drop(&mut self) {
+        let records: Vec<InputAuditRecord> = std::mem::take(&mut self.pending)
+            .into_iter()
+            .map(|pending| self.resolve(pending))
+            .collect();
+        self.append(&records);
+    }
+}
+
+/// 현재 시각 (Unix epoch 밀리초)
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// 호스트 감사 파일을 destination으로 내보내기 (since 이후 기록만, 내보낸 기록 수 반환)
+///
+/// 감사 파일이 없으면 빈 파일을 만들고, 손상된 줄은 건너뜁니다.
+pub fn export_host_audit(
+    host: &str,
+    destination: &Path,
+    since: Option<u64>,
+) -> std::io::Result<usize> {
+    let source = InputAuditor::default_path(host).ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "Config directory not found")
+    })?;
+    export_audit_file(&source, destination, since)
+}
+
+fn export_audit_file(
+    source: &Path,
+    destination: &Path,
+    since: Option<u64>,
+) -> std::io::Result<usize> {
+    let records = match File::open(source) {
+        Ok(file) => load_records(file, since)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e),
+    };
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut out = Vec::new();
+    for record in &records {
+        serde_json::to_writer(&mut out, record)?;
+        out.push(b'\n');
+    }
+    fs::write(destination, out)?;
+    Ok(records.len())
+}
+
+fn load_records(file: File, since: Option<u64>) -> std::io::Result<Vec<InputAuditRecord>> {
+    let mut records = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let Ok(record) = serde_json::from_str::<InputAuditRecord>(&line?) else {
+            continue;
+        };
+        if since.is_some_and(|since| record.time < since) {
+            continue;
+        }
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn append_record(path: &Path, record: &InputAuditRecord) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // 한 번의 append 쓰기로 한 줄을 기록 (같은 호스트의 여러 세션이 섞이지 않도록)
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut line = serde_json::to_vec(record)?;
+    line.push(b'\n');
+    file.write_all(&line)
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auditor() -> InputAuditor {
+        InputAuditor::new(
+            "s1".to_string(),
+            "example.com".to_string(),
+            "admin".to_string(),
+            None,
+        )
+    }
+
+    fn lines(records: &[InputAuditRecord]) -> Vec<Option<&str>> {
+        records.iter().map(|r| r.line.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_line_editing_and_echo() {
+        let mut auditor = auditor();
+        auditor.output(b"$ ");
+
+        let mut records = Vec::new();
+        for key in ["l", "x", "\x7f", "s", " ", "-", "l"] {
+            records.extend(auditor.feed_input(key, 1));
+            records.extend(auditor.feed_output(key.replace('\x7f', "\x08 \x08").as_bytes(), 1));
+        }
+        records.extend(auditor.feed_input("\x1b[D\r", 2));
+        assert_eq!(lines(&records), vec![Some("ls -l")]);
+
+        // 붙여넣은 줄은 에코가 돌아온 뒤 기록 (두 번째 줄은 첫 명령의 출력 뒤에 에코됨)
+        records.extend(auditor.feed_input("echo hi\rrm -rf /tmp/x\x17\x03", 3));
+        assert_eq!(records.len(), 1);
+        records.extend(auditor.feed_output(b"echo hi\r\nhi\r\n$ ", 4));
+        records.extend(auditor.feed_output(b"rm -rf /tmp/x^C\r\n$ ", 4));
+
+        assert_eq!(
+            lines(&records),
+            vec![Some("ls -l"), Some("echo hi"), Some("rm -rf ")]
+        );
+        assert!(records.iter().all(|r| !r.redacted));
+        assert!(records[2].cancelled);
+    }
+
+    #[test]
+    fn test_redacts_when_echo_off() {
+        let mut auditor = auditor();
+
+        // 비밀번호 프롬프트 뒤의 붙여넣기
+        auditor.output(b"[sudo] password for admin: ");
+        let pasted = auditor.feed_input("hunter2\r", 1);
+        assert_eq!(lines(&pasted), vec![None]);
+        assert!(pasted[0].redacted);
+
+        // 프롬프트를 알 수 없어도 에코되지 않은 줄은 가림 (한 번에 붙여넣은 줄 포함)
+        let mut records = auditor.feed_output(b"\r\nEnter code > ", 2);
+        for key in ["1", "2", "3", "\r"] {
+            records.extend(auditor.feed_input(key, 2));
+        }
+        records.extend(auditor.feed_output(b"\r\nKey> ", 3));
+        records.extend(auditor.feed_input("s3cret\r", 3));
+        records.extend(auditor.feed_output(b"\r\nInvalid key s3\r\n$ ", 4));
+        assert!(records.is_empty());
+
+        records.extend(auditor.feed_input("", 2 + ECHO_TIMEOUT_MS));
+        assert_eq!(lines(&records), vec![None]);
+        records.extend(auditor.feed_input("", 3 + ECHO_TIMEOUT_MS));
+        assert_eq!(lines(&records), vec![None, None]);
+        assert!(records.iter().all(|r| r.redacted));
+
+        // 에코가 돌아오면 다시 기록
+        auditor.feed_input("p", 10_000);
+        auditor.feed_output(b"p", 10_000);
+        auditor.feed_input("wd", 10_000);
+        assert!(auditor.feed_input("\r", 10_000).is_empty());
+        assert_eq!(
+            lines(&auditor.feed_output(b"wd\r\n", 10_001)),
+            vec![Some("pwd")]
+        );
+    }
+
+    #[test]
+    fn test_pending_lines_limit() {
+        let mut auditor = auditor();
+        let input = "x\r".repeat(MAX_PENDING_LINES + 1);
+        let records = auditor.feed_input(&input, 1);
+        assert_eq!(lines(&records), vec![None]);
+        assert_eq!(auditor.pending.len(), MAX_PENDING_LINES);
+    }
+
+    #[test]
+    fn test_export_since() {
+        let dir = std::env::temp_dir().join(format!("rusterm-audit-test-{}", std::process::id()));
+        let source = dir.join("example.com.jsonl");
+        let destination = dir.join("export").join("out.jsonl");
+
+        let mut auditor = auditor();
+        for (time, input) in [(1, "first"), (5, "second")] {
+            auditor.feed_input(input, time);
+            auditor.feed_output(input.as_bytes(), time);
+            for record in auditor.feed_input("\r", time) {
+                append_record(&source, &record).unwrap();
+            }
+        }
+        fs::write(
+            &source,
+            [fs::read(&source).unwrap(), b"broken\n".to_vec()].concat(),
+        )
+        .unwrap();
+
+        let exported = export_audit_file(&source, &destination, Some(2)).unwrap();
+        let content = fs::read_to_string(&destination).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(exported, 1);
+        let record: InputAuditRecord = serde_json::from_str(content.trim_end()).unwrap();
+        assert_eq!(record.line.as_deref(), Some("second"));
+        assert_eq!(record.user, "admin");
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// 출력을 모아 한 번에 보내는 프레임 간격
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(12);
+
+/// 프레임 간격을 기다리지 않고 바로 보내는 프레임 크기
+const MAX_FRAME_BYTES: usize = 256 * 1024;
+
+/// 프론트엔드가 확인(ack)하지 않은 채 보낼 수 있는 최대 바이트 수
+const MAX_UNACKED_BYTES: usize = 1024 * 1024;
+
+/// 전송 대기 버퍼가 이 크기를 넘으면 reader를 멈춤
+const MAX_PENDING_BYTES: usize = 1024 * 1024;
+
+/// 출력 배칭 및 흐름 제어
+///
+/// reader 스레드가 `push`로 넣은 출력을 flusher 스레드가 `next_frame`으로 프레임 단위로 꺼내 보냅니다.
+/// 프론트엔드가 한 번이라도 `ack`를 보내면 흐름 제어가 켜지고,
+/// 확인되지 않은 출력이 한도를 넘으면 전송을 멈춰 대기 버퍼가 차고 reader도 `wait_ready`에서 멈춥니다.
+pub struct OutputBatcher {
+    state: Mutex<BatchState>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct BatchState {
+    /// 전송 대기 중인 출력
+    pending: Vec<u8>,
+    /// pending의 첫 바이트가 들어온 시각
+    pending_since: Option<Instant>,
+    /// 전송 후 확인되지 않은 프레임 (seq, 바이트 수)
+    inflight: VecDeque<(u64, usize)>,
+    unacked_bytes: usize,
+    /// 프론트엔드가 ack를 보내기 시작했는지 여부
+    flow_control: bool,
+    /// reader 종료 또는 세션 종료 (남은 출력은 흐름 제어 없이 모두 전송)
+    closed: bool,
+}
+
+impl BatchState {
+    fn throttled(&self) -> bool {
+        self.flow_control && !self.closed && self.unacked_bytes >= MAX_UNACKED_BYTES
+    }
+
+    fn full(&self) -> bool {
+        self.throttled() && self.pending.len() >= MAX_PENDING_BYTES
+    }
+}
+
+impl OutputBatcher {
+    /// 새 배처 생성
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(BatchState::default()),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// 출력 추가 (블로킹하지 않음)
+    pub fn push(&self, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if state.pending.is_empty() {
+            state.pending_since = Some(Instant::now());
+        }
+        state.pending.extend_from_slice(data);
+        self.changed.notify_all();
+    }
+
+    /// 대기 버퍼에 여유가 생길 때까지 대기
+    ///
+    /// timeout이 None이면 여유가 생기거나 닫힐 때까지 기다리고,
+    /// 시간 안에 여유가 생기지 않으면 false를 반환합니다.
+    pub fn wait_ready(&self, timeout: Option<Duration>) -> bool {
+        let state = self.state.lock().unwrap();
+        match timeout {
+            Some(timeout) => {
+                let (state, _) = self
+                    .changed
+                    .wait_timeout_while(state, timeout, |s| s.full())
+                    .unwrap();
+                !state.full()
+            }
+            None => {
+                let _state = self.changed.wait_while(state, |s| s.full()).unwrap();
+                true
+            }
+        }
+    }
+
+    /// 다음 프레임 대기 후 반환 (닫혔고 남은 출력이 없으면 None)
+    pub fn next_frame(&self) -> Option<Vec<u8>> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if state.pending.is_empty() {
+                if state.closed {
+                    return None;
+                }
+                state = self.changed.wait(state).unwrap();
+                continue;
+            }
+
+            if state.throttled() {
+                state = self.changed.wait(state).unwrap();
+                continue;
+            }
+
+            let deadline = state.pending_since.unwrap_or_else(Instant::now) + FRAME_INTERVAL;
+            let now = Instant::now();
+            if now < deadline && state.pending.len() < MAX_FRAME_BYTES && !state.closed {
+                state = self.changed.wait_timeout(state, deadline - now).unwrap().0;
+                continue;
+            }
+
+            state.pending_since = None;
+            let frame = std::mem::take(&mut state.pending);
+            self.changed.notify_all();
+            return Some(frame);
+        }
+    }
+
+    /// 프레임 전송 기록 (이벤트 발행 전에 호출)
+    pub fn sent(&self, seq: u64, len: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.inflight.push_back((seq, len));
+        state.unacked_bytes += len;
+    }
+
+    /// 프론트엔드가 seq까지의 출력을 처리했음을 확인
+    pub fn ack(&self, seq: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.flow_control = true;
+        while let Some(&(front_seq, len)) = state.inflight.front() {
+            if front_seq > seq {
+                break;
+            }
+            state.inflight.pop_front();
+            state.unacked_bytes -= len;
+        }
+        self.changed.notify_all();
+    }
+
+    /// 흐름 제어 해제 (확인을 보내던 프론트엔드가 사라진 경우, 다시 ack를 받으면 켜짐)
+    pub fn release(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.flow_control = false;
+        state.inflight.clear();
+        state.unacked_bytes = 0;
+        self.changed.notify_all();
+    }
+
+    /// 배처 닫기 (대기 중인 reader/flusher를 깨우고 남은 출력을 내보냄)
+    pub fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.changed.notify_all();
+    }
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coalesces_pushes_into_one_frame() {
+        let batcher = OutputBatcher::new();
+        batcher.push(b"hello ");
+        batcher.push(b"world");
+
+        let started_at = Instant::now();
+        assert_eq!(batcher.next_frame().unwrap(), b"hello world");
+        assert!(started_at.elapsed() <= FRAME_INTERVAL * 10);
+
+        batcher.close();
+        assert_eq!(batcher.next_frame(), None);
+    }
+
+    #[test]
+    fn test_backpressure_until_ack() {
+        let batcher = OutputBatcher::new();
+        batcher.ack(0); // 흐름 제어 활성화
+        batcher.sent(1, MAX_UNACKED_BYTES);
+        batcher.push(&vec![b'x'; MAX_PENDING_BYTES]);
+
+        assert!(!batcher.wait_ready(Some(Duration::from_millis(10))));
+
+        batcher.ack(1);
+        assert!(batcher.wait_ready(Some(Duration::from_millis(10))));
+    }
+
+    #[test]
+    fn test_release_resumes_reader() {
+        let batcher = OutputBatcher::new();
+        batcher.ack(0);
+        batcher.sent(1, MAX_UNACKED_BYTES);
+        batcher.push(&vec![b'x'; MAX_PENDING_BYTES]);
+        assert!(!batcher.wait_ready(Some(Duration::from_millis(10))));
+
+        // 확인을 보낼 프론트엔드가 사라지면 흐름 제어를 풀어 읽기를 계속함
+        batcher.release();
+        assert!(batcher.wait_ready(Some(Duration::from_millis(10))));
+        assert!(batcher.next_frame().is_some());
+    }
+
+    #[test]
+    fn test_no_backpressure_without_ack() {
+        let batcher = OutputBatcher::new();
+        batcher.sent(0, MAX_UNACKED_BYTES);
+        batcher.push(&vec![b'x'; MAX_PENDING_BYTES]);
+
+        assert!(batcher.wait_ready(Some(Duration::from_millis(10))));
+    }
+}
@@ -0,0 +1,97 @@
+/// 스트리밍 UTF-8 디코더
+///
+/// PTY/SSH에서 읽은 바이트 청크를 문자열로 변환합니다.
+/// 멀티바이트 문자가 두 번의 read에 걸쳐 잘린 경우 미완성 바이트를
+/// 다음 청크까지 보관하고, 잘못된 바이트는 U+FFFD로 치환하여 출력이 유실되지 않도록 합니다.
+#[derive(Debug, Default)]
+pub struct Utf8Decoder {
+    pending: Vec<u8>,
+}
+
+impl Utf8Decoder {
+    /// 새 디코더 생성
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 바이트 청크 디코딩 (끝부분의 미완성 시퀀스는 다음 호출로 이월)
+    pub fn decode(&mut self, input: &[u8]) -> String {
+        let mut bytes = std::mem::take(&mut self.pending);
+        bytes.extend_from_slice(input);
+
+        let mut output = String::with_capacity(bytes.len());
+        let mut rest = bytes.as_slice();
+
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(valid) => {
+                    output.push_str(valid);
+                    break;
+                }
+                Err(e) => {
+                    let (valid, after) = rest.split_at(e.valid_up_to());
+                    // SAFETY: valid_up_to까지는 유효한 UTF-8임이 보장됨
+                    output.push_str(unsafe { std::str::from_utf8_unchecked(valid) });
+
+                    match e.error_len() {
+                        Some(len) => {
+                            // 잘못된 시퀀스 - 치환 문자로 대체 후 계속
+                            output.push(char::REPLACEMENT_CHARACTER);
+                            rest = &after[len..];
+                        }
+                        None => {
+                            // 입력 끝에서 잘린 시퀀스 - 다음 청크까지 보관
+                            self.pending.extend_from_slice(after);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        output
+    }
+
+    /// 남은 미완성 바이트 처리 (스트림 종료 시 호출)
+    pub fn finish(&mut self) -> String {
+        if self.pending.is_empty() {
+            return String::new();
+        }
+        let pending = std::mem::take(&mut self.pending);
+        String::from_utf8_lossy(&pending).into_owned()
+    }
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_multibyte_sequence() {
+        let text = "한글 ✓ 😀";
+        let bytes = text.as_bytes();
+        let mut decoder = Utf8Decoder::new();
+
+        // 한 바이트씩 나눠서 넣어도 원문이 복원되어야 함
+        let decoded: String = bytes.iter().map(|b| decoder.decode(&[*b])).collect();
+        assert_eq!(decoded, text);
+        assert!(decoder.finish().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_bytes_are_replaced() {
+        let mut decoder = Utf8Decoder::new();
+        assert_eq!(decoder.decode(b"a\xffb"), "a\u{FFFD}b");
+    }
+
+    #[test]
+    fn test_finish_flushes_incomplete_sequence() {
+        let mut decoder = Utf8Decoder::new();
+        assert_eq!(decoder.decode(&[b'x', 0xED, 0x95]), "x");
+        assert_eq!(decoder.finish(), "\u{FFFD}");
+    }
+}
@@ -0,0 +1,219 @@
+//! 세션 문자 인코딩 (EUC-KR, Shift_JIS, GB18030 등 레거시 서버 지원)
+
+use super::decoder::Utf8Decoder;
+use encoding_rs::{CoderResult, Decoder, Encoder, EncoderResult, Encoding, UTF_8};
+use std::borrow::Cow;
+use std::sync::Mutex;
+
+/// 세션 인코딩 변환기
+///
+/// 출력은 세션 인코딩에서 UTF-8로 변환해 스크롤백, 화면 모델, 로그가 항상 UTF-8을 받도록 하고,
+/// 입력은 UTF-8에서 세션 인코딩으로 변환합니다.
+/// 실행 중에 인코딩을 바꿀 수 있도록 세션과 읽기 스레드가 함께 사용합니다.
+pub struct SessionEncoding {
+    state: Mutex<CodecState>,
+}
+
+/// 현재 인코딩과 스트리밍 디코더
+struct CodecState {
+    encoding: &'static Encoding,
+    /// UTF-8 세션용 디코더
+    utf8: Utf8Decoder,
+    /// 그 외 인코딩용 디코더 (청크 경계에서 잘린 문자는 다음 청크로 이월)
+    decoder: Option<Decoder>,
+}
+
+impl CodecState {
+    fn new(encoding: &'static Encoding) -> Self {
+        Self {
+            encoding,
+            utf8: Utf8Decoder::new(),
+            decoder: (encoding != UTF_8).then(|| encoding.new_decoder_without_bom_handling()),
+        }
+    }
+
+    fn decode(&mut self, input: &[u8]) -> String {
+        match &mut self.decoder {
+            Some(decoder) => decode_chunk(decoder, input, false),
+            None => self.utf8.decode(input),
+        }
+    }
+
+    /// 남은 미완성 바이트 처리 후 디코더 초기화
+    fn finish(&mut self) -> String {
+        match &mut self.decoder {
+            Some(decoder) => {
+                let rest = decode_chunk(decoder, &[], true);
+                *decoder = self.encoding.new_decoder_without_bom_handling();
+                rest
+            }
+            None => self.utf8.finish(),
+        }
+    }
+}
+
+impl SessionEncoding {
+    /// 인코딩 레이블로 생성 (None이면 UTF-8)
+    ///
+    /// WHATWG Encoding 표준 레이블("euc-kr", "shift_jis", "gb18030", "latin1" 등)을 받습니다.
+    /// 입력을 인코딩할 수 없는 UTF-16 계열은 지원하지 않습니다.
+    pub fn new(label: Option<&str>) -> Option<Self> {
+        let encoding = match label {
+            Some(label) => lookup(label)?,
+            None => UTF_8,
+        };
+        Some(Self {
+            state: Mutex::new(CodecState::new(encoding)),
+        })
+    }
+
+    /// 현재 인코딩 이름 (예: "EUC-KR")
+    pub fn name(&self) -> &'static str {
+        self.state.lock().unwrap().encoding.name()
+    }
+
+    /// 인코딩 변경 (이전 인코딩에서 남은 미완성 바이트를 변환해 반환, 지원하지 않는 레이블이면 None)
+    pub fn set(&self, label: &str) -> Option<String> {
+        let encoding = lookup(label)?;
+        let mut state = self.state.lock().unwrap();
+        let rest = state.finish();
+        *state = CodecState::new(encoding);
+        Some(rest)
+    }
+
+    /// 출력 청크를 UTF-8 문자열로 변환
+    pub fn decode(&self, input: &[u8]) -> String {
+        self.state.lock().unwrap().decode(input)
+    }
+
+    /// 원시 출력 모드용 변환 (UTF-8 세션은 받은 바이트 그대로)
+    pub fn decode_raw<'a>(&self, input: &'a [u8]) -> Cow<'a, [u8]> {
+        let mut state = self.state.lock().unwrap();
+        if state.decoder.is_none() {
+            return Cow::Borrowed(input);
+        }
+        Cow::Owned(state.decode(input).into_bytes())
+    }
+
+    /// 남은 미완성 바이트 처리 (스트림 종료 시 호출)
+    pub fn finish(&self) -> String {
+        self.state.lock().unwrap().finish()
+    }
+
+    /// 입력을 세션 인코딩으로 변환 (표현할 수 없는 문자는 '?')
+    pub fn encode<'a>(&self, text: &'a str) -> Cow<'a, [u8]> {
+        let encoding = self.state.lock().unwrap().encoding;
+        if encoding == UTF_8 {
+            return Cow::Borrowed(text.as_bytes());
+        }
+        Cow::Owned(encode_text(&mut encoding.new_encoder(), text))
+    }
+}
+
+/// 레이블로 인코딩 찾기 (출력 인코딩이 자신과 다른 UTF-16, replacement는 제외)
+fn lookup(label: &str) -> Option<&'static Encoding> {
+    Encoding::for_label(label.trim().as_bytes())
+        .filter(|encoding| encoding.output_encoding() == *encoding)
+}
+
+/// 디코더로 청크 변환 (잘못된 바이트는 U+FFFD)
+fn decode_chunk(decoder: &mut Decoder, input: &[u8], last: bool) -> String {
+    let mut output = String::new();
+    let mut rest = input;
+    loop {
+        output.reserve(
+            decoder
+                .max_utf8_buffer_length(rest.len())
+                .unwrap_or(rest.len() * 3 + 4),
+        );
+        let (result, read, _) = decoder.decode_to_string(rest, &mut output, last);
+        rest = &rest[read..];
+        if let CoderResult::InputEmpty = result {
+            return output;
+        }
+    }
+}
+
+/// 인코더로 문자열 변환 (표현할 수 없는 문자는 '?'로 대체)
+fn encode_text(encoder: &mut Encoder, text: &str) -> Vec<u8> {
+    let mut output = Vec::new();
+    let mut rest = text;
+    loop {
+        output.reserve(
+            encoder
+                .max_buffer_length_from_utf8_without_replacement(rest.len())
+                .unwrap_or(rest.len() * 4 + 8),
+        );
+        let (result, read) =
+            encoder.encode_from_utf8_to_vec_without_replacement(rest, &mut output, true);
+        rest = &rest[read..];
+        match result {
+            EncoderResult::InputEmpty => return output,
+            // ISO-2022-JP처럼 상태가 있는 인코딩도 처리되도록 인코더로 '?'를 씀
+            EncoderResult::Unmappable(_) => {
+                output.reserve(8);
+                let _ =
+                    encoder.encode_from_utf8_to_vec_without_replacement("?", &mut output, false);
+            }
+            EncoderResult::OutputFull => {}
+        }
+    }
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_split_euc_kr() {
+        // "한글" = C7 D1 B1 DB
+        let encoding = SessionEncoding::new(Some("EUC-KR")).unwrap();
+        assert_eq!(encoding.name(), "EUC-KR");
+
+        let decoded: String = [0xC7, 0xD1, 0xB1, 0xDB, b'\n']
+            .iter()
+            .map(|byte| encoding.decode(&[*byte]))
+            .collect();
+        assert_eq!(decoded, "한글\n");
+    }
+
+    #[test]
+    fn test_encode_shift_jis() {
+        let encoding = SessionEncoding::new(Some("shift_jis")).unwrap();
+        assert_eq!(
+            encoding.encode("ls 日本").as_ref(),
+            &[b'l', b's', b' ', 0x93, 0xFA, 0x96, 0x7B]
+        );
+        // 표현할 수 없는 문자는 '?'
+        assert_eq!(encoding.encode("a한b").as_ref(), b"a?b");
+    }
+
+    #[test]
+    fn test_utf8_passthrough() {
+        let encoding = SessionEncoding::new(None).unwrap();
+        assert_eq!(encoding.name(), "UTF-8");
+        assert!(matches!(encoding.encode("한글"), Cow::Borrowed(_)));
+        assert!(matches!(encoding.decode_raw(&[0xFF]), Cow::Borrowed(_)));
+        assert_eq!(encoding.decode("한".as_bytes()), "한");
+    }
+
+    #[test]
+    fn test_set_encoding() {
+        assert!(SessionEncoding::new(Some("utf-16le")).is_none());
+        assert!(SessionEncoding::new(Some("unknown")).is_none());
+
+        let encoding = SessionEncoding::new(None).unwrap();
+        // 잘린 UTF-8 문자는 인코딩을 바꿀 때 치환 문자로 나옴
+        assert_eq!(encoding.decode(&[0xED, 0x95]), "");
+        assert_eq!(encoding.set("latin1").as_deref(), Some("\u{FFFD}"));
+        assert_eq!(encoding.name(), "windows-1252");
+        assert_eq!(encoding.decode(&[0xE9]), "é");
+        assert_eq!(encoding.decode_raw(&[0xE9]).as_ref(), "é".as_bytes());
+        assert!(encoding.set("nope").is_none());
+        assert_eq!(encoding.name(), "windows-1252");
+    }
+}
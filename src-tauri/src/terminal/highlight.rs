@@ -0,0 +1,388 @@
+use regex::bytes::Regex;
+use serde::{Deserialize, Serialize};
+
+/// 버퍼에 모아둘 CSI 파라미터 최대 길이 (넘는 부분은 버림)
+const MAX_CSI_LEN: usize = 64;
+
+/// 출력 하이라이트 규칙 (설정에 저장)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HighlightRule {
+    /// 정규식 패턴
+    pub pattern: String,
+    /// 매칭된 텍스트에 적용할 SGR 파라미터 (예: "1;31", "38;5;208")
+    pub sgr: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 검증된 하이라이트 규칙 목록 (설정이 바뀔 때마다 새로 만듦)
+#[derive(Debug, Default)]
+pub struct HighlightSet {
+    /// (패턴, `ESC [ {sgr} m`)
+    rules: Vec<(Regex, Vec<u8>)>,
+}
+
+impl HighlightSet {
+    /// 활성화된 규칙 검증 및 컴파일 (잘못된 규칙이 있으면 에러 메시지 반환)
+    pub fn compile(rules: &[HighlightRule]) -> Result<Self, String> {
+        let rules = rules
+            .iter()
+            .filter(|rule| rule.enabled)
+            .map(|rule| {
+                let regex = Regex::new(&rule.pattern)
+                    .map_err(|e| format!("Invalid highlight pattern '{}': {}", rule.pattern, e))?;
+                // 빈 매칭은 아무것도 칠하지 못하고 무한 반복만 일으킴
+                if regex.is_match(b"") {
+                    return Err(format!(
+                        "Highlight pattern '{}' matches empty text",
+                        rule.pattern
+                    ));
+                }
+                if rule.sgr.is_empty()
+                    || !rule
+                        .sgr
+                        .bytes()
+                        .all(|b| b.is_ascii_digit() || b == b';' || b == b':')
+                {
+                    return Err(format!("Invalid SGR parameters '{}'", rule.sgr));
+                }
+                Ok((regex, format!("\x1b[{}m", rule.sgr).into_bytes()))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// 텍스트 구간에 규칙 적용 (겹치면 먼저 시작하는 매칭, 같은 위치면 먼저 나열된 규칙)
+    fn apply(&self, text: &[u8], out: &mut Vec<u8>) -> bool {
+        let mut pos = 0;
+        let mut changed = false;
+
+        while pos < text.len() {
+            let found = self
+                .rules
+                .iter()
+                .filter_map(|(regex, sgr)| regex.find_at(text, pos).map(|m| (m.range(), sgr)))
+                .min_by_key(|(range, _)| range.start);
+            let Some((range, sgr)) = found else {
+                break;
+            };
+
+            out.extend_from_slice(&text[pos..range.start]);
+            out.extend_from_slice(sgr);
+            out.extend_from_slice(&text[range.clone()]);
+            out.extend_from_slice(b"\x1b[0m");
+            pos = range.end;
+            changed = true;
+        }
+
+        out.extend_from_slice(&text[pos..]);
+        changed
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LexState {
+    Ground,
+    Escape,
+    /// ESC 뒤 중간 바이트 (문자셋 지정 등)
+    EscapeIntermediate,
+    Csi,
+    /// OSC/DCS/SOS/PM/APC 문자열 (BEL 또는 ST로 끝남)
+    String,
+    /// 문자열 안에서 ESC를 만난 상태 (ST 확인용)
+    StringEscape,
+}
+
+/// 세션별 출력 하이라이터
+///
+/// 출력 바이트를 텍스트와 이스케이프 시퀀스로 나눠 텍스트 구간에만 SGR을 삽입합니다.
+/// 프로그램이 지정한 속성을 덮어쓰지 않도록 SGR이 기본 상태인 텍스트만 칠하고
+/// (그래서 `ESC [ 0 m`으로 정확히 복원됨), 대체 화면(전체 화면 앱)에서는 동작하지 않습니다.
+/// 청크 경계에서 잘린 시퀀스를 이어서 해석하도록 파서 상태를 유지합니다.
+pub struct Highlighter {
+    state: LexState,
+    /// 진행 중인 CSI의 파라미터/중간 바이트
+    csi: Vec<u8>,
+    /// 현재 SGR이 기본 상태인지
+    sgr_default: bool,
+    alternate_screen: bool,
+}
+
+impl Highlighter {
+    pub fn new() -> Self {
+        Self {
+            state: LexState::Ground,
+            csi: Vec::new(),
+            sgr_default: true,
+            alternate_screen: false,
+        }
+    }
+
+    /// 출력 프레임 처리 후 내보낼 프레임 반환 (규칙이 없거나 바뀐 것이 없으면 원본 그대로)
+    ///
+    /// 규칙이 없어도 이후 프레임을 올바르게 해석하도록 상태는 항상 갱신합니다.
+    pub fn process(&mut self, rules: &HighlightSet, frame: Vec<u8>) -> Vec<u8> {
+        let mut out = Vec::with_capacity(if rules.is_empty() {
+            0
+        } else {
+            frame.len() + 32
+        });
+        let mut changed = false;
+        let mut text_start: Option<usize> = None;
+
+        for (i, &byte) in frame.iter().enumerate() {
+            let highlightable = !rules.is_empty() && self.sgr_default && !self.alternate_screen;
+            let is_text = self.state == LexState::Ground && byte >= 0x20 && byte != 0x7f;
+
+            if is_text && highlightable {
+                text_start.get_or_insert(i);
+                continue;
+            }
+
+            if let Some(start) = text_start.take() {
+                changed |= rules.apply(&frame[start..i], &mut out);
+            }
+            if !rules.is_empty() {
+                out.push(byte);
+            }
+            if !is_text {
+                self.advance(byte);
+            }
+        }
+
+        if let Some(start) = text_start {
+            changed |= rules.apply(&frame[start..], &mut out);
+        }
+
+        if changed {
+            out
+        } else {
+            frame
+        }
+    }
+
+    /// 텍스트가 아닌 바이트로 파서 상태 갱신
+    fn advance(&mut self, byte: u8) {
+        // CAN/SUB는 진행 중인 시퀀스를 취소
+        if matches!(byte, 0x18 | 0x1a) {
+            self.state = LexState::Ground;
+            return;
+        }
+
+        match self.state {
+            LexState::Ground => {
+                if byte == 0x1b {
+                    self.state = LexState::Escape;
+                }
+            }
+            LexState::Escape => self.escape(byte),
+            LexState::EscapeIntermediate => match byte {
+                0x1b => self.state = LexState::Escape,
+                0x30..=0x7e => self.state = LexState::Ground,
+                _ => {}
+            },
+            LexState::Csi => match byte {
+                0x1b => self.state = LexState::Escape,
+                0x20..=0x3f if self.csi.len() < MAX_CSI_LEN => self.csi.push(byte),
+                0x40..=0x7e => {
+                    self.csi_dispatch(byte);
+                    self.state = LexState::Ground;
+                }
+                // CSI 도중의 C0 제어 문자는 실행만 되고 시퀀스는 계속됨
+                _ => {}
+            },
+            LexState::String => match byte {
+                0x07 => self.state = LexState::Ground,
+                0x1b => self.state = LexState::StringEscape,
+                _ => {}
+            },
+            LexState::StringEscape => {
+                if byte == b'\\' {
+                    self.state = LexState::Ground;
+                } else {
+                    // ST가 아닌 ESC는 문자열을 끝내고 새 이스케이프 시퀀스를 시작
+                    self.escape(byte);
+                }
+            }
+        }
+    }
+
+    fn escape(&mut self, byte: u8) {
+        self.state = match byte {
+            b'[' => {
+                self.csi.clear();
+                LexState::Csi
+            }
+            b']' | b'P' | b'X' | b'^' | b'_' => LexState::String,
+            0x1b => LexState::Escape,
+            0x20..=0x2f => LexState::EscapeIntermediate,
+            b'c' => {
+                // RIS: 속성과 화면 초기화
+                self.sgr_default = true;
+                self.alternate_screen = false;
+                LexState::Ground
+            }
+            _ => LexState::Ground,
+        };
+    }
+
+    fn csi_dispatch(&mut self, action: u8) {
+        match (self.csi.first(), action) {
+            (Some(b'?'), b'h' | b'l') => {
+                let alternate = self.csi[1..]
+                    .split(|&b| b == b';')
+                    .any(|param| matches!(param, b"47" | b"1047" | b"1049"));
+                if alternate {
+                    self.alternate_screen = action == b'h';
+                }
+            }
+            (first, b'm') if first.is_none_or(|b| b.is_ascii_digit() || *b == b';') => {
+                self.sgr_default = sgr_resets_to_default(&self.csi, self.sgr_default);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for Highlighter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// SGR 적용 후 속성이 기본 상태인지 판단
+///
+/// 0(또는 빈 파라미터) 뒤에 다른 속성이 없으면 기본 상태로 봅니다.
+/// 39/49처럼 일부만 되돌리는 경우는 보수적으로 기본 상태가 아닌 것으로 취급합니다.
+fn sgr_resets_to_default(params: &[u8], current: bool) -> bool {
+    if params.is_empty() {
+        return true;
+    }
+
+    let params: Vec<&[u8]> = params.split(|&b| b == b';').collect();
+    let mut default = current;
+    let mut i = 0;
+    while i < params.len() {
+        let value = std::str::from_utf8(params[i]).ok().and_then(|p| {
+            if p.is_empty() {
+                Some(0)
+            } else {
+                p.parse::<u16>().ok()
+            }
+        });
+        match value {
+            Some(0) => default = true,
+            // 확장 색상: 색상 인덱스/RGB 값은 속성이 아니므로 건너뜀
+            Some(38 | 48 | 58) => {
+                default = false;
+                i += match params.get(i + 1).copied() {
+                    Some(b"5") => 2,
+                    Some(b"2") => 4,
+                    _ => 0,
+                };
+            }
+            _ => default = false,
+        }
+        i += 1;
+    }
+    default
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> HighlightSet {
+        HighlightSet::compile(&[
+            HighlightRule {
+                pattern: "ERROR".to_string(),
+                sgr: "1;31".to_string(),
+                enabled: true,
+            },
+            HighlightRule {
+                pattern: r"\d+\.\d+\.\d+\.\d+".to_string(),
+                sgr: "36".to_string(),
+                enabled: true,
+            },
+        ])
+        .unwrap()
+    }
+
+    fn process(highlighter: &mut Highlighter, rules: &HighlightSet, data: &[u8]) -> Vec<u8> {
+        highlighter.process(rules, data.to_vec())
+    }
+
+    #[test]
+    fn test_highlights_plain_text() {
+        let rules = rules();
+        let mut highlighter = Highlighter::new();
+        let out = process(&mut highlighter, &rules, b"ERROR from 10.0.0.1\r\n");
+        assert_eq!(
+            out,
+            b"\x1b[1;31mERROR\x1b[0m from \x1b[36m10.0.0.1\x1b[0m\r\n".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_preserves_sequences_and_styled_text() {
+        let rules = rules();
+        let mut highlighter = Highlighter::new();
+
+        // OSC 제목과 CSI 파라미터 안의 텍스트는 건드리지 않음
+        let data = b"\x1b]0;ERROR 1.2.3.4\x07\x1b[10;20H";
+        assert_eq!(process(&mut highlighter, &rules, data), data.to_vec());
+
+        // 프로그램이 칠한 텍스트는 그대로, 리셋 이후만 하이라이트
+        let data = b"\x1b[32mERROR\x1b[m ERROR";
+        assert_eq!(
+            process(&mut highlighter, &rules, data),
+            b"\x1b[32mERROR\x1b[m \x1b[1;31mERROR\x1b[0m".to_vec()
+        );
+
+        // 청크 경계에서 잘린 CSI
+        assert_eq!(
+            process(&mut highlighter, &rules, b"\x1b[38;5"),
+            b"\x1b[38;5".to_vec()
+        );
+        assert_eq!(
+            process(&mut highlighter, &rules, b";0mERROR"),
+            b";0mERROR".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_disabled_on_alternate_screen() {
+        let rules = rules();
+        let mut highlighter = Highlighter::new();
+
+        let data = b"\x1b[?1049hERROR";
+        assert_eq!(process(&mut highlighter, &rules, data), data.to_vec());
+        let out = process(&mut highlighter, &rules, b"\x1b[?1049lERROR");
+        assert_eq!(out, b"\x1b[?1049l\x1b[1;31mERROR\x1b[0m".to_vec());
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let rule = |pattern: &str, sgr: &str| HighlightRule {
+            pattern: pattern.to_string(),
+            sgr: sgr.to_string(),
+            enabled: true,
+        };
+        assert!(HighlightSet::compile(&[rule("(", "31")]).is_err());
+        assert!(HighlightSet::compile(&[rule("a*", "31")]).is_err());
+        assert!(HighlightSet::compile(&[rule("a", "31m\x1b")]).is_err());
+    }
+}
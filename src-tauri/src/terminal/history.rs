@@ -0,0 +1,220 @@
+use super::shell::FinishedCommand;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// 세션별로 메모리에 보관하는 최대 명령 수
+const MAX_SESSION_ENTRIES: usize = 1000;
+
+/// 로컬 PTY 세션의 히스토리 호스트 이름
+pub const LOCAL_HOST: &str = "localhost";
+
+/// 명령 히스토리 항목
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandHistoryEntry {
+    /// 세션 내 순번 (0부터 1씩 증가)
+    pub index: u64,
+    pub session_id: String,
+    /// 명령이 실행된 호스트 (로컬 PTY는 "localhost")
+    pub host: String,
+    pub command: Option<String>,
+    pub cwd: Option<String>,
+    /// 시작 시각 (Unix epoch 밀리초)
+    pub started_at: u64,
+    pub duration_ms: u64,
+    pub exit_code: Option<i32>,
+    /// 명령 출력의 스트림 오프셋 범위 [output_start, output_end)
+    pub output_start: u64,
+    pub output_end: u64,
+}
+
+/// 세션 명령 히스토리
+///
+/// 셸 통합 마커로 감지한 명령을 메모리에 보관하고,
+/// 호스트별 히스토리 파일(JSON Lines)에 한 줄씩 추가합니다.
+#[derive(Debug)]
+pub struct CommandHistory {
+    session_id: String,
+    host: String,
+    entries: VecDeque<CommandHistoryEntry>,
+    next_index: u64,
+    /// 히스토리 파일 경로 (None이면 디스크에 저장하지 않음)
+    path: Option<PathBuf>,
+}
+
+impl CommandHistory {
+    /// 새 히스토리 생성
+    pub fn new(session_id: String, host: String, path: Option<PathBuf>) -> Self {
+        Self {
+            session_id,
+            host,
+            entries: VecDeque::new(),
+            next_index: 0,
+            path,
+        }
+    }
+
+    /// 기본 히스토리 파일 경로 (설정 디렉토리/rusterm/history/{host}.jsonl)
+    pub fn default_path(host: &str) -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| {
+            dir.join("rusterm")
+                .join("history")
+                .join(format!("{}.jsonl", sanitize_host(host)))
+        })
+    }
+
+    /// 종료된 명령 기록 후 추가된 항목 반환
+    pub fn record(&mut self, command: FinishedCommand) -> CommandHistoryEntry {
+        let entry = CommandHistoryEntry {
+            index: self.next_index,
+            session_id: self.session_id.clone(),
+            host: self.host.clone(),
+            command: command.command,
+            cwd: command.cwd,
+            started_at: command.started_at,
+            duration_ms: command.duration_ms,
+            exit_code: command.exit_code,
+            output_start: command.output_start,
+            output_end: command.output_end,
+        };
+        self.next_index += 1;
+
+        if let Some(path) = &self.path {
+            if let Err(e) = append_entry(path, &entry) {
+                eprintln!("Failed to write command history: {}", e);
+            }
+        }
+
+        if self.entries.len() >= MAX_SESSION_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry.clone());
+        entry
+    }
+
+    /// 보관 중인 히스토리 (오래된 순)
+    pub fn entries(&self) -> Vec<CommandHistoryEntry> {
+        self.entries.iter().cloned().collect()
+    }
+}
+
+/// 호스트 히스토리 파일 읽기 (최근 limit개, 오래된 순)
+///
+/// 파일이 없으면 빈 목록을 반환하고, 손상된 줄은 건너뜁니다.
+pub fn load_host_history(
+    host: &str,
+    limit: Option<usize>,
+) -> std::io::Result<Vec<CommandHistoryEntry>> {
+    match CommandHistory::default_path(host) {
+        Some(path) => load_history_file(&path, limit),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn load_history_file(
+    path: &Path,
+    limit: Option<usize>,
+) -> std::io::Result<Vec<CommandHistoryEntry>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    if limit == Some(0) {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = VecDeque::new();
+    for line in BufReader::new(file).lines() {
+        let Ok(entry) = serde_json::from_str::<CommandHistoryEntry>(&line?) else {
+            continue;
+        };
+        if limit.is_some_and(|limit| entries.len() >= limit) {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    Ok(entries.into())
+}
+
+fn append_entry(path: &Path, entry: &CommandHistoryEntry) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut line = serde_json::to_vec(entry)?;
+    line.push(b'\n');
+    file.write_all(&line)
+}
+
+/// 파일 이름에 쓸 수 없는 문자를 '_'로 치환
+pub(super) fn sanitize_host(host: &str) -> String {
+    host.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '@') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finished(command: &str, exit_code: i32) -> FinishedCommand {
+        FinishedCommand {
+            command: Some(command.to_string()),
+            cwd: Some("/tmp".to_string()),
+            started_at: 1_000,
+            duration_ms: 250,
+            exit_code: Some(exit_code),
+            output_start: 10,
+            output_end: 20,
+        }
+    }
+
+    #[test]
+    fn test_history_persists_per_host() {
+        let path =
+            std::env::temp_dir().join(format!("rusterm-history-test-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut history = CommandHistory::new(
+            "s1".to_string(),
+            "example.com".to_string(),
+            Some(path.clone()),
+        );
+        history.record(finished("ls", 0));
+        let entry = history.record(finished("false", 1));
+        assert_eq!(entry.index, 1);
+        assert_eq!(history.entries().len(), 2);
+
+        let loaded = load_history_file(&path, None).unwrap();
+        let last = load_history_file(&path, Some(1)).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].command.as_deref(), Some("ls"));
+        assert_eq!(loaded[1].host, "example.com");
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].exit_code, Some(1));
+    }
+
+    #[test]
+    fn test_sanitize_host() {
+        assert_eq!(sanitize_host("user@10.0.0.1"), "user@10.0.0.1");
+        assert_eq!(sanitize_host("fe80::1/x"), "fe80__1_x");
+    }
+}
@@ -0,0 +1,479 @@
+use super::ansi::AnsiStripper;
+use super::history::{sanitize_host, LOCAL_HOST};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// 세션 로그 파일 형식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// ANSI 이스케이프 시퀀스와 제어 문자를 제거한 텍스트
+    #[default]
+    Plain,
+    /// 받은 출력 그대로
+    Raw,
+}
+
+/// 세션 로그 옵션
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionLogOptions {
+    #[serde(default)]
+    pub format: LogFormat,
+    /// 줄마다 UTC 시각 붙이기
+    #[serde(default)]
+    pub timestamps: bool,
+    /// 파일 이름 템플릿 ({host}, {user}, {date}, {time}, {session})
+    ///
+    /// {date}가 들어 있으면 날짜가 바뀔 때 새 파일로 넘어갑니다.
+    #[serde(default = "default_file_name")]
+    pub file_name: String,
+    /// 로그 디렉토리 (None이면 설정 디렉토리/rusterm/logs)
+    #[serde(default)]
+    pub directory: Option<String>,
+    /// 파일 하나의 최대 크기 (넘으면 회전, 0이면 회전 안 함)
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
+    /// 현재 파일을 포함해 보관할 파일 수 (`name.1`, `name.2`, ...)
+    #[serde(default = "default_max_files")]
+    pub max_files: u32,
+}
+
+fn default_file_name() -> String {
+    "{user}@{host}_{date}_{time}.log".to_string()
+}
+
+fn default_max_file_bytes() -> u64 {
+    10 * 1024 * 1024
+}
+
+fn default_max_files() -> u32 {
+    5
+}
+
+impl Default for SessionLogOptions {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::default(),
+            timestamps: false,
+            file_name: default_file_name(),
+            directory: None,
+            max_file_bytes: default_max_file_bytes(),
+            max_files: default_max_files(),
+        }
+    }
+}
+
+/// 전역 세션 로그 설정 (설정에 저장, 새 세션에 자동 적용)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionLogSettings {
+    /// 모든 SSH 세션 자동 로그
+    #[serde(default)]
+    pub ssh: bool,
+    /// 모든 로컬 PTY 세션 자동 로그
+    #[serde(default)]
+    pub local: bool,
+    #[serde(default)]
+    pub options: SessionLogOptions,
+}
+
+impl SessionLogSettings {
+    /// 새 세션에 자동으로 적용할 로그 옵션 (꺼져 있으면 None)
+    pub fn auto_options(&self, ssh: bool) -> Option<SessionLogOptions> {
+        let enabled = if ssh { self.ssh } else { self.local };
+        enabled.then(|| self.options.clone())
+    }
+}
+
+/// 로그 파일 이름에 들어갈 세션 정보
+#[derive(Debug, Clone)]
+pub struct SessionLogContext {
+    pub session_id: String,
+    pub host: String,
+    pub user: String,
+}
+
+impl SessionLogContext {
+    /// 로컬 PTY 세션 정보 (사용자는 환경 변수에서 읽음)
+    pub fn local(session_id: String) -> Self {
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_default();
+        Self {
+            session_id,
+            host: LOCAL_HOST.to_string(),
+            user,
+        }
+    }
+}
+
+/// 로그 시작/종료 응답
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionLogInfo {
+    pub session_id: String,
+    /// 현재 로그 파일 경로
+    pub path: String,
+}
+
+/// 세션 출력 로그 파일 기록기
+///
+/// 파일이 최대 크기를 넘으면 `name.1`, `name.2`, ... 로 밀어내고 새 파일에 이어 씁니다.
+pub struct SessionLogger {
+    options: SessionLogOptions,
+    context: SessionLogContext,
+    directory: PathBuf,
+    /// 회전 중에는 잠시 비어 있음 (Windows는 열린 파일의 이름을 바꿀 수 없음)
+    writer: Option<BufWriter<File>>,
+    path: PathBuf,
+    written: u64,
+    /// 현재 파일을 연 날 (UTC 기준 1970-01-01부터 지난 일수)
+    day: u64,
+    stripper: AnsiStripper,
+    /// 다음 바이트가 줄의 시작인지 (타임스탬프용)
+    at_line_start: bool,
+}
+
+impl SessionLogger {
+    /// 템플릿으로 정한 로그 파일을 열고 기록 시작 (같은 파일이 있으면 이어 씀)
+    pub fn create(options: SessionLogOptions, context: SessionLogContext) -> std::io::Result<Self> {
+        let directory = match &options.directory {
+            Some(directory) => PathBuf::from(directory),
+            None => Self::default_directory().ok_or_else(|| {
+                std::io::Error::new(std::io::ErrorKind::NotFound, "Config directory not found")
+            })?,
+        };
+
+        let now = unix_secs();
+        let path = directory.join(render_file_name(&options.file_name, &context, now));
+        let (writer, written) = Self::open(&path, false)?;
+
+        Ok(Self {
+            options,
+            context,
+            directory,
+            writer: Some(writer),
+            path,
+            written,
+            day: now / SECS_PER_DAY,
+            stripper: AnsiStripper::new(),
+            at_line_start: true,
+        })
+    }
+
+    /// 기본 로그 디렉토리 (설정 디렉토리/rusterm/logs)
+    pub fn default_directory() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("rusterm").join("logs"))
+    }
+
+    /// 현재 로그 파일 경로
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 출력 기록
+    pub fn write_output(&mut self, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let mut bytes = match self.options.format {
+            LogFormat::Plain => self.stripper.push(data).into_bytes(),
+            LogFormat::Raw => data.to_vec(),
+        };
+        if self.options.timestamps {
+            let stamp = format!("[{}] ", format_timestamp(now));
+            bytes = stamp_lines(&bytes, &stamp, &mut self.at_line_start);
+        }
+        if bytes.is_empty() {
+            return;
+        }
+
+        if let Err(e) = self.write_bytes(&bytes, now / 1000) {
+            eprintln!("Failed to write session log {}: {}", self.path.display(), e);
+        }
+    }
+
+    /// 로그 종료 (버퍼를 디스크에 기록)
+    pub fn finish(mut self) -> std::io::Result<PathBuf> {
+        if let Some(writer) = self.writer.as_mut() {
+            writer.flush()?;
+        }
+        Ok(self.path)
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8], now_secs: u64) -> std::io::Result<()> {
+        let day = now_secs / SECS_PER_DAY;
+        if day != self.day && self.options.file_name.contains("{date}") {
+            self.reopen(now_secs)?;
+        } else if self.options.max_file_bytes > 0
+            && self.written > 0
+            && self.written + bytes.len() as u64 > self.options.max_file_bytes
+        {
+            self.rotate()?;
+        }
+
+        let writer = match self.writer.as_mut() {
+            Some(writer) => writer,
+            None => {
+                // 이전 회전이 실패해 파일이 닫혀 있으면 다시 열기
+                let (writer, written) = Self::open(&self.path, false)?;
+                self.written = written;
+                self.writer.insert(writer)
+            }
+        };
+        writer.write_all(bytes)?;
+        self.written += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// 날짜가 바뀌면 새 이름의 파일로 전환
+    fn reopen(&mut self, now_secs: u64) -> std::io::Result<()> {
+        if let Some(mut writer) = self.writer.take() {
+            writer.flush()?;
+        }
+        self.day = now_secs / SECS_PER_DAY;
+        self.path = self.directory.join(render_file_name(
+            &self.options.file_name,
+            &self.context,
+            now_secs,
+        ));
+        let (writer, written) = Self::open(&self.path, false)?;
+        self.writer = Some(writer);
+        self.written = written;
+        Ok(())
+    }
+
+    /// 현재 파일을 `name.1`로 밀어내고 (오래된 파일은 삭제) 빈 파일로 다시 시작
+    fn rotate(&mut self) -> std::io::Result<()> {
+        if let Some(mut writer) = self.writer.take() {
+            writer.flush()?;
+        }
+
+        if self.options.max_files > 1 {
+            let oldest = rotated_path(&self.path, self.options.max_files - 1);
+            if oldest.exists() {
+                fs::remove_file(&oldest)?;
+            }
+            for index in (1..self.options.max_files - 1).rev() {
+                let from = rotated_path(&self.path, index);
+                if from.exists() {
+                    fs::rename(&from, rotated_path(&self.path, index + 1))?;
+                }
+            }
+            fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        }
+
+        let (writer, _) = Self::open(&self.path, true)?;
+        self.writer = Some(writer);
+        self.written = 0;
+        Ok(())
+    }
+
+    /// 로그 파일 열기 (기존 크기 반환)
+    fn open(path: &Path, truncate: bool) -> std::io::Result<(BufWriter<File>, u64)> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut options = OpenOptions::new();
+        if truncate {
+            options.write(true).create(true).truncate(true);
+        } else {
+            options.append(true).create(true);
+        }
+        let file = options.open(path)?;
+        let written = file.metadata()?.len();
+        Ok((BufWriter::new(file), written))
+    }
+}
+
+/// 세션의 로그 상태 (flusher 스레드와 세션이 공유)
+#[derive(Default)]
+pub struct LogSlot {
+    logger: Mutex<Option<SessionLogger>>,
+}
+
+impl LogSlot {
+    /// 로그 기록기 등록 (이미 기록 중이면 실패)
+    pub fn start(&self, logger: SessionLogger) -> Result<(), String> {
+        let mut slot = self.logger.lock().unwrap();
+        if let Some(current) = slot.as_ref() {
+            return Err(format!("Already logging to {}", current.path().display()));
+        }
+        *slot = Some(logger);
+        Ok(())
+    }
+
+    /// 로그 종료 (기록 중이 아니면 None)
+    pub fn stop(&self) -> Option<std::io::Result<PathBuf>> {
+        let logger = self.logger.lock().unwrap().take();
+        logger.map(SessionLogger::finish)
+    }
+
+    /// 출력 기록
+    pub fn output(&self, data: &[u8]) {
+        if let Some(logger) = self.logger.lock().unwrap().as_mut() {
+            logger.write_output(data);
+        }
+    }
+}
+
+fn unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// 파일 이름 템플릿 치환 (값에 들어 있는 경로 구분자 등 파일 이름에 쓸 수 없는 문자는 `_`로 바꿈)
+fn render_file_name(template: &str, context: &SessionLogContext, now_secs: u64) -> String {
+    let (year, month, day, hour, minute, second) = utc_datetime(now_secs);
+    template
+        .replace("{host}", &sanitize_host(&context.host))
+        .replace("{user}", &sanitize_host(&context.user))
+        .replace("{session}", &sanitize_host(&context.session_id))
+        .replace("{date}", &format!("{:04}-{:02}-{:02}", year, month, day))
+        .replace("{time}", &format!("{:02}{:02}{:02}", hour, minute, second))
+}
+
+/// 회전된 파일 경로 (`name.log` -> `name.log.1`)
+fn rotated_path(path: &Path, index: u32) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// 줄의 시작마다 타임스탬프 삽입 (청크 경계를 넘는 줄은 at_line_start로 이어감)
+fn stamp_lines(data: &[u8], stamp: &str, at_line_start: &mut bool) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() + stamp.len());
+    for &byte in data {
+        if *at_line_start {
+            out.extend_from_slice(stamp.as_bytes());
+            *at_line_start = false;
+        }
+        out.push(byte);
+        if byte == b'\n' {
+            *at_line_start = true;
+        }
+    }
+    out
+}
+
+/// 밀리초 단위 UTC 시각 (예: 2024-03-01T09:30:00.250Z)
+fn format_timestamp(unix_millis: u64) -> String {
+    let (year, month, day, hour, minute, second) = utc_datetime(unix_millis / 1000);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        unix_millis % 1000
+    )
+}
+
+/// Unix 시각(초)을 UTC 날짜/시각으로 변환 (연, 월, 일, 시, 분, 초)
+fn utc_datetime(unix_secs: u64) -> (u64, u64, u64, u64, u64, u64) {
+    let days = unix_secs / SECS_PER_DAY;
+    let secs = unix_secs % SECS_PER_DAY;
+
+    // 0000-03-01 기준 400년 주기로 날짜 계산
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    (year, month, day, secs / 3600, secs % 3600 / 60, secs % 60)
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> SessionLogContext {
+        SessionLogContext {
+            session_id: "abc".to_string(),
+            host: "10.0.0.1".to_string(),
+            user: "DOMAIN\\admin".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_file_name_template() {
+        // 2024-02-29 13:05:09 UTC
+        let name = render_file_name("{user}@{host}_{date}_{time}.log", &context(), 1_709_211_909);
+        assert_eq!(name, "DOMAIN_admin@10.0.0.1_2024-02-29_130509.log");
+        assert_eq!(
+            format_timestamp(1_709_211_909_042),
+            "2024-02-29T13:05:09.042Z"
+        );
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00.000Z");
+    }
+
+    #[test]
+    fn test_timestamps_across_chunks() {
+        let mut at_line_start = true;
+        let first = stamp_lines(b"one\ntw", "[t] ", &mut at_line_start);
+        let second = stamp_lines(b"o\nthree", "[t] ", &mut at_line_start);
+        assert_eq!(first, b"[t] one\n[t] tw");
+        assert_eq!(second, b"o\n[t] three");
+    }
+
+    #[test]
+    fn test_plain_log_rotation() {
+        let directory =
+            std::env::temp_dir().join(format!("rusterm-log-test-{}", std::process::id()));
+        let options = SessionLogOptions {
+            file_name: "{session}.log".to_string(),
+            directory: Some(directory.to_string_lossy().to_string()),
+            max_file_bytes: 8,
+            max_files: 3,
+            ..SessionLogOptions::default()
+        };
+        let mut logger = SessionLogger::create(options, context()).unwrap();
+        for line in [
+            "\x1b[31mone\x1b[0m\r\n",
+            "two\r\n",
+            "three\r\n",
+            "four\r\n",
+            "five\r\n",
+        ] {
+            logger.write_output(line.as_bytes());
+        }
+        let path = logger.finish().unwrap();
+
+        let read = |path: PathBuf| fs::read_to_string(path).unwrap_or_default();
+        let current = read(path.clone());
+        let previous = read(rotated_path(&path, 1));
+        let oldest = read(rotated_path(&path, 2));
+        let dropped = rotated_path(&path, 3).exists();
+        let _ = fs::remove_dir_all(&directory);
+
+        assert_eq!(current, "five\n");
+        assert_eq!(previous, "four\n");
+        assert_eq!(oldest, "three\n");
+        assert!(!dropped);
+    }
+}
@@ -0,0 +1,37 @@
+mod ansi;
+mod audit;
+mod batcher;
+mod decoder;
+mod encoding;
+mod highlight;
+mod history;
+mod logger;
+mod monitor;
+mod paste;
+mod recorder;
+mod screen;
+mod scrollback;
+mod search;
+mod shell;
+mod sink;
+mod stream;
+mod trigger;
+
+pub use ansi::AnsiStripper;
+pub use audit::{export_host_audit, InputAuditor};
+pub use encoding::SessionEncoding;
+pub use highlight::{HighlightRule, HighlightSet};
+pub use history::{load_host_history, CommandHistoryEntry, LOCAL_HOST};
+pub use logger::{
+    SessionLogContext, SessionLogInfo, SessionLogOptions, SessionLogSettings, SessionLogger,
+};
+pub use monitor::MonitorConfig;
+pub use paste::INPUT_CHUNK_SIZE;
+pub use recorder::{AsciicastRecorder, RecordingInfo};
+pub use screen::ScreenSnapshot;
+pub use scrollback::{Scrollback, ScrollbackLimit, ScrollbackReplay};
+pub use search::{SearchOptions, SearchResult};
+pub use shell::{ShellKind, ShellState};
+pub use sink::SessionSink;
+pub use stream::SessionStream;
+pub use trigger::{TriggerRule, TriggerSet};
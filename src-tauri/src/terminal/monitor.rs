@@ -0,0 +1,239 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// 세션 활동/무출력 감시 설정
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorConfig {
+    /// 이 시간 동안 출력이 없으면 session-silence 발행 (None이면 감시 안 함)
+    pub silence_ms: Option<u64>,
+    /// 이 시간 이상 조용하다가 출력이 오면 session-activity 발행 (None이면 감시 안 함)
+    pub activity_ms: Option<u64>,
+}
+
+/// session-activity 이벤트 페이로드
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionActivityEvent {
+    pub session_id: String,
+    /// 출력이 다시 오기 전까지 조용했던 시간
+    pub idle_ms: u64,
+}
+
+/// session-silence 이벤트 페이로드
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSilenceEvent {
+    pub session_id: String,
+    /// 마지막 출력 이후 지난 시간
+    pub silent_ms: u64,
+}
+
+/// session-bell 이벤트 페이로드
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionBellEvent {
+    pub session_id: String,
+    /// 한 출력 프레임에 포함된 BEL 수
+    pub count: usize,
+}
+
+/// 무출력 확인 결과
+#[derive(Debug, PartialEq)]
+enum SilenceCheck {
+    /// 임계값을 넘음 (마지막 출력 이후 지난 시간)
+    Silent(Duration),
+    /// 다음 확인까지 대기 (None이면 출력이나 설정 변경까지)
+    Wait(Option<Duration>),
+}
+
+/// 활동/무출력 판정 (시각을 인자로 받는 순수 상태)
+#[derive(Debug)]
+struct ActivityTracker {
+    config: MonitorConfig,
+    last_output: Instant,
+    /// 현재 조용한 구간에서 이미 silence를 알렸는지
+    silence_reported: bool,
+}
+
+impl ActivityTracker {
+    fn new(now: Instant) -> Self {
+        Self {
+            config: MonitorConfig::default(),
+            last_output: now,
+            silence_reported: false,
+        }
+    }
+
+    /// 출력 기록 후 activity를 알려야 하면 조용했던 시간 반환
+    fn output(&mut self, now: Instant) -> Option<Duration> {
+        let idle = now.saturating_duration_since(self.last_output);
+        self.last_output = now;
+        self.silence_reported = false;
+
+        let threshold = Duration::from_millis(self.config.activity_ms?);
+        (idle >= threshold).then_some(idle)
+    }
+
+    fn check_silence(&mut self, now: Instant) -> SilenceCheck {
+        let Some(silence_ms) = self.config.silence_ms else {
+            return SilenceCheck::Wait(None);
+        };
+        if self.silence_reported {
+            return SilenceCheck::Wait(None);
+        }
+
+        let deadline = self.last_output + Duration::from_millis(silence_ms);
+        if now >= deadline {
+            self.silence_reported = true;
+            SilenceCheck::Silent(now.saturating_duration_since(self.last_output))
+        } else {
+            SilenceCheck::Wait(Some(deadline - now))
+        }
+    }
+}
+
+/// 세션별 활동/무출력 감시
+///
+/// 활동은 출력 프레임마다 판정하고, 무출력은 설정된 동안만 도는 감시 스레드가 판정합니다.
+pub struct SessionMonitor {
+    state: Mutex<MonitorState>,
+    changed: Condvar,
+}
+
+struct MonitorState {
+    tracker: ActivityTracker,
+    /// 감시 스레드 실행 중 여부
+    watching: bool,
+    closed: bool,
+}
+
+impl SessionMonitor {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(MonitorState {
+                tracker: ActivityTracker::new(Instant::now()),
+                watching: false,
+                closed: false,
+            }),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// 출력 기록 (activity를 알려야 하면 조용했던 시간 반환)
+    pub fn output(&self) -> Option<Duration> {
+        let mut state = self.state.lock().unwrap();
+        let idle = state.tracker.output(Instant::now());
+        self.changed.notify_all();
+        idle
+    }
+
+    /// 설정 변경 (새 감시 스레드가 필요하면 true)
+    pub fn configure(&self, config: MonitorConfig) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let needs_watcher = config.silence_ms.is_some() && !state.watching && !state.closed;
+        state.tracker.config = config;
+        state.tracker.silence_reported = false;
+        if needs_watcher {
+            state.watching = true;
+        }
+        self.changed.notify_all();
+        needs_watcher
+    }
+
+    pub fn config(&self) -> MonitorConfig {
+        self.state.lock().unwrap().tracker.config.clone()
+    }
+
+    /// 세션 종료 (감시 스레드 종료)
+    pub fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.changed.notify_all();
+    }
+
+    /// 감시 스레드 본문 (세션이 닫히거나 silence 감시가 꺼지면 반환)
+    pub fn watch(&self, mut on_silence: impl FnMut(Duration)) {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if state.closed || state.tracker.config.silence_ms.is_none() {
+                state.watching = false;
+                return;
+            }
+
+            match state.tracker.check_silence(Instant::now()) {
+                SilenceCheck::Silent(silent) => {
+                    drop(state);
+                    on_silence(silent);
+                    state = self.state.lock().unwrap();
+                }
+                SilenceCheck::Wait(Some(timeout)) => {
+                    state = self.changed.wait_timeout(state, timeout).unwrap().0;
+                }
+                SilenceCheck::Wait(None) => {
+                    state = self.changed.wait(state).unwrap();
+                }
+            }
+        }
+    }
+}
+
+impl Default for SessionMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker(silence_ms: Option<u64>, activity_ms: Option<u64>, now: Instant) -> ActivityTracker {
+        let mut tracker = ActivityTracker::new(now);
+        tracker.config = MonitorConfig {
+            silence_ms,
+            activity_ms,
+        };
+        tracker
+    }
+
+    #[test]
+    fn test_silence_reported_once_per_quiet_period() {
+        let start = Instant::now();
+        let mut tracker = tracker(Some(30_000), None, start);
+
+        assert_eq!(
+            tracker.check_silence(start + Duration::from_secs(10)),
+            SilenceCheck::Wait(Some(Duration::from_secs(20)))
+        );
+        assert_eq!(
+            tracker.check_silence(start + Duration::from_secs(31)),
+            SilenceCheck::Silent(Duration::from_secs(31))
+        );
+        assert_eq!(
+            tracker.check_silence(start + Duration::from_secs(40)),
+            SilenceCheck::Wait(None)
+        );
+
+        // 출력이 오면 다시 감시
+        tracker.output(start + Duration::from_secs(50));
+        assert!(matches!(
+            tracker.check_silence(start + Duration::from_secs(81)),
+            SilenceCheck::Silent(_)
+        ));
+    }
+
+    #[test]
+    fn test_activity_after_idle_threshold() {
+        let start = Instant::now();
+        let mut tracker = tracker(None, Some(5_000), start);
+
+        assert_eq!(tracker.output(start + Duration::from_secs(1)), None);
+        assert_eq!(
+            tracker.output(start + Duration::from_secs(7)),
+            Some(Duration::from_secs(6))
+        );
+        assert_eq!(tracker.output(start + Duration::from_secs(8)), None);
+    }
+}
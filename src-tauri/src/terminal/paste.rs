@@ -0,0 +1,68 @@
+//! 붙여넣기 처리 (bracketed paste, DEC 모드 2004)
+
+/// 붙여넣기 시작 시퀀스
+const PASTE_START: &[u8] = b"\x1b[200~";
+
+/// 붙여넣기 종료 시퀀스
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// 큰 입력을 나눠 쓸 때의 청크 크기
+pub const INPUT_CHUNK_SIZE: usize = 4096;
+
+/// 붙여넣을 내용을 bracketed paste 시퀀스로 감쌈
+///
+/// 내용 안의 시작/종료 시퀀스는 제거해 붙여넣은 내용이 괄호 밖으로 빠져나와
+/// 명령으로 실행되지 않도록 합니다.
+pub fn bracket_paste(data: &[u8]) -> Vec<u8> {
+    let mut content = data.to_vec();
+    let mut from = 0;
+    while let Some(position) = find_marker(&content[from..]) {
+        let start = from + position;
+        content.drain(start..start + PASTE_START.len());
+        // 제거한 뒤 앞뒤가 이어져 새 시퀀스가 생길 수 있으므로 조금 앞에서 다시 찾음
+        from = start.saturating_sub(PASTE_START.len() - 1);
+    }
+
+    let mut output = Vec::with_capacity(content.len() + PASTE_START.len() + PASTE_END.len());
+    output.extend_from_slice(PASTE_START);
+    output.extend_from_slice(&content);
+    output.extend_from_slice(PASTE_END);
+    output
+}
+
+/// 시작 또는 종료 시퀀스 위치 (두 시퀀스의 길이는 같음)
+fn find_marker(data: &[u8]) -> Option<usize> {
+    data.windows(PASTE_START.len())
+        .position(|window| window == PASTE_START || window == PASTE_END)
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bracket_paste() {
+        assert_eq!(
+            bracket_paste(b"echo hi\n"),
+            b"\x1b[200~echo hi\n\x1b[201~".to_vec()
+        );
+        assert_eq!(bracket_paste(b""), b"\x1b[200~\x1b[201~".to_vec());
+    }
+
+    #[test]
+    fn test_bracket_paste_strips_markers() {
+        assert_eq!(
+            bracket_paste(b"a\x1b[201~rm -rf ~\n\x1b[200~b"),
+            b"\x1b[200~arm -rf ~\nb\x1b[201~".to_vec()
+        );
+        // 제거 후 다시 만들어지는 시퀀스도 제거
+        assert_eq!(
+            bracket_paste(b"\x1b[20\x1b[201~1~x"),
+            b"\x1b[200~x\x1b[201~".to_vec()
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// asciicast v2 헤더
+#[derive(Debug, Serialize)]
+struct AsciicastHeader {
+    version: u32,
+    width: u16,
+    height: u16,
+    timestamp: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    env: HashMap<String, String>,
+}
+
+/// 녹화 시작/종료 응답
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingInfo {
+    pub session_id: String,
+    pub path: String,
+}
+
+/// asciicast v2 (.cast) 녹화기
+///
+/// 첫 줄은 헤더(JSON 객체), 이후 각 줄은 `[경과 시간(초), 이벤트 타입, 데이터]` 형식입니다.
+/// - "o": 출력, "i": 입력, "r": 리사이즈 ("COLSxROWS")
+pub struct AsciicastRecorder {
+    writer: BufWriter<File>,
+    path: PathBuf,
+    started_at: Instant,
+    record_input: bool,
+}
+
+impl AsciicastRecorder {
+    /// 녹화 파일 생성 및 헤더 기록
+    pub fn create(
+        path: &Path,
+        cols: u16,
+        rows: u16,
+        title: Option<String>,
+        env: HashMap<String, String>,
+        record_input: bool,
+    ) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        let header = AsciicastHeader {
+            version: 2,
+            width: cols,
+            height: rows,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            title,
+            env,
+        };
+        serde_json::to_writer(&mut writer, &header)?;
+        writer.write_all(b"\n")?;
+
+        Ok(Self {
+            writer,
+            path: path.to_path_buf(),
+            started_at: Instant::now(),
+            record_input,
+        })
+    }
+
+    /// 기본 녹화 파일 경로 (설정 디렉토리/rusterm/recordings/{session_id}-{unix time}.cast)
+    pub fn default_path(session_id: &str) -> Option<PathBuf> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        dirs::config_dir().map(|dir| {
+            dir.join("rusterm")
+                .join("recordings")
+                .join(format!("{}-{}.cast", session_id, timestamp))
+        })
+    }
+
+    /// 녹화 파일 경로
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 출력 이벤트 기록
+    pub fn write_output(&mut self, data: &str) {
+        self.write_event("o", data);
+    }
+
+    /// 입력 이벤트 기록 (입력 녹화가 켜진 경우에만)
+    pub fn write_input(&mut self, data: &str) {
+        if self.record_input {
+            self.write_event("i", data);
+        }
+    }
+
+    /// 리사이즈 이벤트 기록
+    pub fn write_resize(&mut self, cols: u16, rows: u16) {
+        self.write_event("r", &format!("{}x{}", cols, rows));
+    }
+
+    /// 녹화 종료 (버퍼를 디스크에 기록)
+    pub fn finish(mut self) -> std::io::Result<PathBuf> {
+        self.writer.flush()?;
+        Ok(self.path)
+    }
+
+    fn write_event(&mut self, kind: &str, data: &str) {
+        if data.is_empty() {
+            return;
+        }
+
+        // 마이크로초 단위로 반올림한 경과 시간
+        let elapsed = self.started_at.elapsed().as_micros() as f64 / 1_000_000.0;
+        let result = serde_json::to_writer(&mut self.writer, &(elapsed, kind, data))
+            .map_err(std::io::Error::from)
+            .and_then(|_| self.writer.write_all(b"\n"));
+
+        if let Err(e) = result {
+            eprintln!("Failed to write recording {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// 세션의 녹화 상태 (reader 스레드와 세션이 공유)
+#[derive(Default)]
+pub struct RecordingSlot {
+    recorder: Mutex<Option<AsciicastRecorder>>,
+}
+
+impl RecordingSlot {
+    /// 녹화기 등록 (이미 녹화 중이면 실패)
+    pub fn start(&self, recorder: AsciicastRecorder) -> Result<(), String> {
+        let mut slot = self.recorder.lock().unwrap();
+        if let Some(current) = slot.as_ref() {
+            return Err(format!("Already recording to {}", current.path().display()));
+        }
+        *slot = Some(recorder);
+        Ok(())
+    }
+
+    /// 녹화 종료 (녹화 중이 아니면 None)
+    pub fn stop(&self) -> Option<std::io::Result<PathBuf>> {
+        let recorder = self.recorder.lock().unwrap().take();
+        recorder.map(AsciicastRecorder::finish)
+    }
+
+    /// 출력 기록
+    pub fn output(&self, data: &str) {
+        if let Some(recorder) = self.recorder.lock().unwrap().as_mut() {
+            recorder.write_output(data);
+        }
+    }
+
+    /// 입력 기록
+    pub fn input(&self, data: &str) {
+        if let Some(recorder) = self.recorder.lock().unwrap().as_mut() {
+            recorder.write_input(data);
+        }
+    }
+
+    /// 리사이즈 기록
+    pub fn resize(&self, cols: u16, rows: u16) {
+        if let Some(recorder) = self.recorder.lock().unwrap().as_mut() {
+            recorder.write_resize(cols, rows);
+        }
+    }
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asciicast_format() {
+        let path = std::env::temp_dir().join(format!("rusterm-test-{}.cast", std::process::id()));
+        let mut recorder =
+            AsciicastRecorder::create(&path, 80, 24, None, HashMap::new(), false).unwrap();
+        recorder.write_output("hello\r\n");
+        recorder.write_input("ls\r");
+        recorder.write_resize(100, 30);
+        recorder.finish().unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 3); // 입력 녹화가 꺼져 있으므로 "i" 이벤트 없음
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["width"], 80);
+        assert_eq!(lines[1][1], "o");
+        assert_eq!(lines[1][2], "hello\r\n");
+        assert_eq!(lines[2][1], "r");
+        assert_eq!(lines[2][2], "100x30");
+    }
+}
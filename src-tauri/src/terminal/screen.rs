@@ -0,0 +1,817 @@
+use super::shell::ShellMarker;
+use serde::{Deserialize, Serialize};
+use unicode_width::UnicodeWidthChar;
+use vte::{Params, Parser, Perform};
+
+/// 셀 색상
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum CellColor {
+    /// 테마 기본 색상
+    #[default]
+    Default,
+    /// 256색 팔레트 인덱스 (0-15는 ANSI 색상)
+    Indexed(u8),
+    /// 24비트 색상
+    Rgb(u8, u8, u8),
+}
+
+impl CellColor {
+    fn is_default(&self) -> bool {
+        *self == CellColor::Default
+    }
+}
+
+/// 셀 속성 (SGR)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CellAttributes {
+    #[serde(skip_serializing_if = "CellColor::is_default")]
+    pub fg: CellColor,
+    #[serde(skip_serializing_if = "CellColor::is_default")]
+    pub bg: CellColor,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub bold: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub dim: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub italic: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub underline: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub blink: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub inverse: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub strikethrough: bool,
+}
+
+/// 화면 셀 스냅샷
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenCell {
+    /// 셀 문자 (넓은 문자의 두 번째 칸은 빈 문자열)
+    pub text: String,
+    /// 표시 폭 (0: 넓은 문자의 두 번째 칸, 1: 일반, 2: 넓은 문자)
+    pub width: u8,
+    #[serde(flatten)]
+    pub attrs: CellAttributes,
+}
+
+/// 커서 상태 (0부터 시작하는 위치)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenCursor {
+    pub row: u16,
+    pub col: u16,
+    pub visible: bool,
+}
+
+/// 화면 전체 스냅샷 (get_screen_cells 응답)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenSnapshot {
+    pub cols: u16,
+    pub rows: u16,
+    pub cursor: ScreenCursor,
+    /// 대체 화면(vim, less 등 전체 화면 앱) 사용 중 여부
+    pub alternate_screen: bool,
+    pub lines: Vec<Vec<ScreenCell>>,
+}
+
+/// 넓은 문자의 두 번째 칸 표시
+const WIDE_SPACER: char = '\0';
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Cell {
+    c: char,
+    attrs: CellAttributes,
+}
+
+impl Cell {
+    fn blank(bg: CellColor) -> Self {
+        Cell {
+            c: ' ',
+            attrs: CellAttributes {
+                bg,
+                ..CellAttributes::default()
+            },
+        }
+    }
+}
+
+type Grid = Vec<Vec<Cell>>;
+
+#[derive(Debug, Clone, Copy)]
+struct SavedCursor {
+    row: usize,
+    col: usize,
+    pen: CellAttributes,
+}
+
+/// 헤드리스 VT100/xterm 화면 모델
+///
+/// 세션 출력을 vte 파서로 해석해 현재 화면 내용과 커서, 셀 속성을 유지합니다.
+/// 프론트엔드 렌더링과 별개로 자동화, 테스트, IPC 클라이언트가 화면을 조회할 때 사용합니다.
+pub struct VirtualScreen {
+    parser: Parser,
+    state: ScreenState,
+}
+
+impl VirtualScreen {
+    /// 새 화면 생성
+    pub fn new(cols: u16, rows: u16) -> Self {
+        Self {
+            parser: Parser::new(),
+            state: ScreenState::new(cols.max(1) as usize, rows.max(1) as usize),
+        }
+    }
+
+    /// 출력 바이트 처리
+    pub fn feed(&mut self, data: &[u8]) {
+        self.parser.advance(&mut self.state, data);
+    }
+
+    /// 화면 크기 변경
+    pub fn resize(&mut self, cols: u16, rows: u16) {
+        self.state
+            .resize(cols.max(1) as usize, rows.max(1) as usize);
+    }
+
+    /// 출력에서 인식한 셸 통합 마커를 꺼냄
+    pub fn take_markers(&mut self) -> Vec<ShellMarker> {
+        std::mem::take(&mut self.state.markers)
+    }
+
+    /// 마지막 호출 이후 받은 BEL 수를 꺼냄 (OSC 종료 문자로 쓰인 BEL은 제외)
+    pub fn take_bells(&mut self) -> usize {
+        std::mem::take(&mut self.state.bells)
+    }
+
+    /// 대체 화면 사용 중 여부
+    pub fn alternate_screen(&self) -> bool {
+        self.state.saved_primary.is_some()
+    }
+
+    /// 원격 프로그램이 bracketed paste 모드를 켰는지 여부
+    pub fn bracketed_paste(&self) -> bool {
+        self.state.bracketed_paste
+    }
+
+    /// 화면 텍스트 (줄 끝 공백 제거, 줄마다 개행)
+    pub fn text(&self) -> String {
+        self.state
+            .grid
+            .iter()
+            .map(|row| {
+                let line: String = row
+                    .iter()
+                    .filter(|cell| cell.c != WIDE_SPACER)
+                    .map(|cell| cell.c)
+                    .collect();
+                line.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// 셀 단위 스냅샷
+    pub fn snapshot(&self) -> ScreenSnapshot {
+        let state = &self.state;
+        let lines = state
+            .grid
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(col, cell)| {
+                        let width = if cell.c == WIDE_SPACER {
+                            0
+                        } else if row.get(col + 1).map(|next| next.c) == Some(WIDE_SPACER) {
+                            2
+                        } else {
+                            1
+                        };
+                        ScreenCell {
+                            text: if width == 0 {
+                                String::new()
+                            } else {
+                                cell.c.to_string()
+                            },
+                            width,
+                            attrs: cell.attrs,
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        ScreenSnapshot {
+            cols: state.cols as u16,
+            rows: state.rows as u16,
+            cursor: ScreenCursor {
+                row: state.row as u16,
+                col: state.col as u16,
+                visible: state.cursor_visible,
+            },
+            alternate_screen: self.alternate_screen(),
+            lines,
+        }
+    }
+}
+
+/// 파서가 갱신하는 화면 상태
+struct ScreenState {
+    cols: usize,
+    rows: usize,
+    grid: Grid,
+    /// 대체 화면 사용 중일 때 보관한 기본 화면
+    saved_primary: Option<Grid>,
+    row: usize,
+    col: usize,
+    /// 마지막 열에 문자를 쓴 뒤 다음 문자에서 줄바꿈할지 여부
+    wrap_pending: bool,
+    pen: CellAttributes,
+    saved_cursor: Option<SavedCursor>,
+    /// 스크롤 영역 (포함 범위)
+    scroll_top: usize,
+    scroll_bottom: usize,
+    autowrap: bool,
+    cursor_visible: bool,
+    /// bracketed paste 모드 (DEC 2004)
+    bracketed_paste: bool,
+    /// 아직 꺼내지 않은 셸 통합 마커 (OSC 133 / OSC 7)
+    markers: Vec<ShellMarker>,
+    /// 아직 꺼내지 않은 BEL 수
+    bells: usize,
+}
+
+impl ScreenState {
+    fn new(cols: usize, rows: usize) -> Self {
+        Self {
+            cols,
+            rows,
+            grid: blank_grid(cols, rows),
+            saved_primary: None,
+            row: 0,
+            col: 0,
+            wrap_pending: false,
+            pen: CellAttributes::default(),
+            saved_cursor: None,
+            scroll_top: 0,
+            scroll_bottom: rows - 1,
+            autowrap: true,
+            cursor_visible: true,
+            bracketed_paste: false,
+            markers: Vec::new(),
+            bells: 0,
+        }
+    }
+
+    fn resize(&mut self, cols: usize, rows: usize) {
+        // 커서가 화면 밖으로 나가면 위쪽 줄을 버려 커서 줄을 유지
+        let overflow = (self.row + 1).saturating_sub(rows);
+        resize_grid(&mut self.grid, cols, rows, overflow);
+        if let Some(primary) = self.saved_primary.as_mut() {
+            resize_grid(primary, cols, rows, 0);
+        }
+
+        self.cols = cols;
+        self.rows = rows;
+        self.row -= overflow;
+        self.col = self.col.min(cols - 1);
+        self.wrap_pending = false;
+        self.scroll_top = 0;
+        self.scroll_bottom = rows - 1;
+    }
+
+    fn blank(&self) -> Cell {
+        Cell::blank(self.pen.bg)
+    }
+
+    fn put_char(&mut self, c: char) {
+        // 표시 폭 (결합 문자 0, 동아시아 넓은 문자 2)
+        let width = c.width().unwrap_or(0);
+        if width == 0 {
+            return;
+        }
+
+        if self.wrap_pending && self.autowrap {
+            self.col = 0;
+            self.linefeed();
+        }
+        self.wrap_pending = false;
+
+        // 넓은 문자가 마지막 열에 걸치면 다음 줄로 넘김
+        if width == 2 && self.col + 1 >= self.cols {
+            if !self.autowrap || self.cols < 2 {
+                return;
+            }
+            let blank = self.blank();
+            self.grid[self.row][self.col] = blank;
+            self.col = 0;
+            self.linefeed();
+        }
+
+        let row = self.row;
+        self.clear_wide_pair(row, self.col);
+        self.grid[row][self.col] = Cell { c, attrs: self.pen };
+        if width == 2 {
+            self.clear_wide_pair(row, self.col + 1);
+            self.grid[row][self.col + 1] = Cell {
+                c: WIDE_SPACER,
+                attrs: self.pen,
+            };
+        }
+
+        if self.col + width >= self.cols {
+            self.col = self.cols - 1;
+            self.wrap_pending = self.autowrap;
+        } else {
+            self.col += width;
+        }
+    }
+
+    /// 넓은 문자의 절반을 덮어쓸 때 나머지 절반을 공백으로 정리
+    fn clear_wide_pair(&mut self, row: usize, col: usize) {
+        let blank = self.blank();
+        if self.grid[row][col].c == WIDE_SPACER && col > 0 {
+            self.grid[row][col - 1] = blank;
+        } else if self.grid[row].get(col + 1).map(|cell| cell.c) == Some(WIDE_SPACER) {
+            self.grid[row][col + 1] = blank;
+        }
+    }
+
+    fn linefeed(&mut self) {
+        if self.row == self.scroll_bottom {
+            self.scroll_up(1);
+        } else if self.row + 1 < self.rows {
+            self.row += 1;
+        }
+    }
+
+    fn reverse_index(&mut self) {
+        if self.row == self.scroll_top {
+            self.scroll_down(1);
+        } else if self.row > 0 {
+            self.row -= 1;
+        }
+    }
+
+    /// 스크롤 영역을 n줄 위로 올림
+    fn scroll_up(&mut self, n: usize) {
+        let n = n.min(self.scroll_bottom - self.scroll_top + 1);
+        let blank = self.blank();
+        self.grid.drain(self.scroll_top..self.scroll_top + n);
+        for _ in 0..n {
+            self.grid
+                .insert(self.scroll_bottom + 1 - n, vec![blank; self.cols]);
+        }
+    }
+
+    /// 스크롤 영역을 n줄 아래로 내림
+    fn scroll_down(&mut self, n: usize) {
+        let n = n.min(self.scroll_bottom - self.scroll_top + 1);
+        let blank = self.blank();
+        self.grid
+            .drain(self.scroll_bottom + 1 - n..=self.scroll_bottom);
+        for _ in 0..n {
+            self.grid.insert(self.scroll_top, vec![blank; self.cols]);
+        }
+    }
+
+    fn move_to(&mut self, row: usize, col: usize) {
+        self.row = row.min(self.rows - 1);
+        self.col = col.min(self.cols - 1);
+        self.wrap_pending = false;
+    }
+
+    fn erase_cells(&mut self, row: usize, from: usize, to: usize) {
+        let blank = self.blank();
+        let to = to.min(self.cols);
+        for cell in &mut self.grid[row][from.min(to)..to] {
+            *cell = blank;
+        }
+    }
+
+    fn erase_display(&mut self, mode: u16) {
+        match mode {
+            0 => {
+                self.erase_cells(self.row, self.col, self.cols);
+                for row in self.row + 1..self.rows {
+                    self.erase_cells(row, 0, self.cols);
+                }
+            }
+            1 => {
+                for row in 0..self.row {
+                    self.erase_cells(row, 0, self.cols);
+                }
+                self.erase_cells(self.row, 0, self.col + 1);
+            }
+            2 | 3 => {
+                for row in 0..self.rows {
+                    self.erase_cells(row, 0, self.cols);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn erase_line(&mut self, mode: u16) {
+        match mode {
+            0 => self.erase_cells(self.row, self.col, self.cols),
+            1 => self.erase_cells(self.row, 0, self.col + 1),
+            2 => self.erase_cells(self.row, 0, self.cols),
+            _ => {}
+        }
+    }
+
+    /// 커서 줄부터 n줄 삽입 (스크롤 영역 안에서만)
+    fn insert_lines(&mut self, n: usize) {
+        if self.row < self.scroll_top || self.row > self.scroll_bottom {
+            return;
+        }
+        let top = self.scroll_top;
+        self.scroll_top = self.row;
+        self.scroll_down(n);
+        self.scroll_top = top;
+        self.col = 0;
+    }
+
+    /// 커서 줄부터 n줄 삭제 (스크롤 영역 안에서만)
+    fn delete_lines(&mut self, n: usize) {
+        if self.row < self.scroll_top || self.row > self.scroll_bottom {
+            return;
+        }
+        let top = self.scroll_top;
+        self.scroll_top = self.row;
+        self.scroll_up(n);
+        self.scroll_top = top;
+        self.col = 0;
+    }
+
+    fn insert_chars(&mut self, n: usize) {
+        let blank = self.blank();
+        let n = n.min(self.cols - self.col);
+        let line = &mut self.grid[self.row];
+        line.truncate(self.cols - n);
+        for _ in 0..n {
+            line.insert(self.col, blank);
+        }
+    }
+
+    fn delete_chars(&mut self, n: usize) {
+        let blank = self.blank();
+        let n = n.min(self.cols - self.col);
+        let line = &mut self.grid[self.row];
+        line.drain(self.col..self.col + n);
+        line.resize(self.cols, blank);
+    }
+
+    fn save_cursor(&mut self) {
+        self.saved_cursor = Some(SavedCursor {
+            row: self.row,
+            col: self.col,
+            pen: self.pen,
+        });
+    }
+
+    fn restore_cursor(&mut self) {
+        if let Some(saved) = self.saved_cursor {
+            self.move_to(saved.row, saved.col);
+            self.pen = saved.pen;
+        }
+    }
+
+    fn enter_alternate_screen(&mut self, save_cursor: bool) {
+        if self.saved_primary.is_some() {
+            return;
+        }
+        if save_cursor {
+            self.save_cursor();
+        }
+        let alternate = blank_grid(self.cols, self.rows);
+        self.saved_primary = Some(std::mem::replace(&mut self.grid, alternate));
+    }
+
+    fn leave_alternate_screen(&mut self, restore_cursor: bool) {
+        if let Some(primary) = self.saved_primary.take() {
+            self.grid = primary;
+            if restore_cursor {
+                self.restore_cursor();
+            }
+        }
+    }
+
+    fn set_private_mode(&mut self, mode: u16, enabled: bool) {
+        match mode {
+            7 => self.autowrap = enabled,
+            25 => self.cursor_visible = enabled,
+            2004 => self.bracketed_paste = enabled,
+            47 | 1047 | 1049 => {
+                let with_cursor = mode == 1049;
+                if enabled {
+                    self.enter_alternate_screen(with_cursor);
+                } else {
+                    self.leave_alternate_screen(with_cursor);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// SGR (Select Graphic Rendition) 처리
+    fn set_graphic_rendition(&mut self, params: &Params) {
+        let groups: Vec<&[u16]> = params.iter().collect();
+        if groups.is_empty() {
+            self.pen = CellAttributes::default();
+            return;
+        }
+
+        let mut i = 0;
+        while i < groups.len() {
+            let group = groups[i];
+            let mut consumed = 0;
+            match group[0] {
+                0 => self.pen = CellAttributes::default(),
+                1 => self.pen.bold = true,
+                2 => self.pen.dim = true,
+                3 => self.pen.italic = true,
+                4 => self.pen.underline = true,
+                5 | 6 => self.pen.blink = true,
+                7 => self.pen.inverse = true,
+                8 => self.pen.hidden = true,
+                9 => self.pen.strikethrough = true,
+                21 | 22 => {
+                    self.pen.bold = false;
+                    self.pen.dim = false;
+                }
+                23 => self.pen.italic = false,
+                24 => self.pen.underline = false,
+                25 => self.pen.blink = false,
+                27 => self.pen.inverse = false,
+                28 => self.pen.hidden = false,
+                29 => self.pen.strikethrough = false,
+                n @ 30..=37 => self.pen.fg = CellColor::Indexed((n - 30) as u8),
+                39 => self.pen.fg = CellColor::Default,
+                n @ 40..=47 => self.pen.bg = CellColor::Indexed((n - 40) as u8),
+                49 => self.pen.bg = CellColor::Default,
+                n @ 90..=97 => self.pen.fg = CellColor::Indexed((n - 90 + 8) as u8),
+                n @ 100..=107 => self.pen.bg = CellColor::Indexed((n - 100 + 8) as u8),
+                n @ (38 | 48) => {
+                    // 콜론 형식(38:2:r:g:b)은 한 그룹, 세미콜론 형식(38;2;r;g;b)은 여러 그룹
+                    let color = if group.len() > 1 {
+                        extended_color(&group[1..]).map(|(color, _)| color)
+                    } else {
+                        let rest: Vec<u16> = groups[i + 1..].iter().map(|g| g[0]).collect();
+                        extended_color(&rest).map(|(color, used)| {
+                            consumed = used;
+                            color
+                        })
+                    };
+                    if let Some(color) = color {
+                        if n == 38 {
+                            self.pen.fg = color;
+                        } else {
+                            self.pen.bg = color;
+                        }
+                    }
+                }
+                _ => {}
+            }
+            i += 1 + consumed;
+        }
+    }
+}
+
+impl Perform for ScreenState {
+    fn print(&mut self, c: char) {
+        self.put_char(c);
+    }
+
+    fn execute(&mut self, byte: u8) {
+        match byte {
+            0x07 => self.bells += 1,
+            0x08 => {
+                self.col = self.col.saturating_sub(1);
+                self.wrap_pending = false;
+            }
+            0x09 => {
+                let next_tab = (self.col / 8 + 1) * 8;
+                self.move_to(self.row, next_tab);
+            }
+            0x0A..=0x0C => {
+                self.wrap_pending = false;
+                self.linefeed();
+            }
+            0x0D => {
+                self.col = 0;
+                self.wrap_pending = false;
+            }
+            _ => {}
+        }
+    }
+
+    fn csi_dispatch(&mut self, params: &Params, intermediates: &[u8], ignore: bool, action: char) {
+        if ignore {
+            return;
+        }
+
+        let values: Vec<u16> = params.iter().map(|group| group[0]).collect();
+        let raw = |i: usize| values.get(i).copied().unwrap_or(0);
+        // 이동/개수 인자는 0을 1로 취급
+        let count = |i: usize| raw(i).max(1) as usize;
+
+        if intermediates.first() == Some(&b'?') {
+            if matches!(action, 'h' | 'l') {
+                for mode in &values {
+                    self.set_private_mode(*mode, action == 'h');
+                }
+            }
+            return;
+        }
+        if !intermediates.is_empty() {
+            return;
+        }
+
+        match action {
+            'A' => self.move_to(self.row.saturating_sub(count(0)), self.col),
+            'B' | 'e' => self.move_to(self.row + count(0), self.col),
+            'C' | 'a' => self.move_to(self.row, self.col + count(0)),
+            'D' => self.move_to(self.row, self.col.saturating_sub(count(0))),
+            'E' => self.move_to(self.row + count(0), 0),
+            'F' => self.move_to(self.row.saturating_sub(count(0)), 0),
+            'G' | '`' => self.move_to(self.row, count(0) - 1),
+            'd' => self.move_to(count(0) - 1, self.col),
+            'H' | 'f' => self.move_to(count(0) - 1, count(1) - 1),
+            'J' => self.erase_display(raw(0)),
+            'K' => self.erase_line(raw(0)),
+            'L' => self.insert_lines(count(0)),
+            'M' => self.delete_lines(count(0)),
+            '@' => self.insert_chars(count(0)),
+            'P' => self.delete_chars(count(0)),
+            'X' => self.erase_cells(self.row, self.col, self.col + count(0)),
+            'S' => self.scroll_up(count(0)),
+            'T' => self.scroll_down(count(0)),
+            'm' => self.set_graphic_rendition(params),
+            'r' => {
+                let top = count(0) - 1;
+                let bottom = if raw(1) == 0 {
+                    self.rows
+                } else {
+                    raw(1) as usize
+                };
+                if top < bottom.min(self.rows) - 1 {
+                    self.scroll_top = top;
+                    self.scroll_bottom = bottom.min(self.rows) - 1;
+                    self.move_to(0, 0);
+                }
+            }
+            's' => self.save_cursor(),
+            'u' => self.restore_cursor(),
+            _ => {}
+        }
+    }
+
+    fn osc_dispatch(&mut self, params: &[&[u8]], _bell_terminated: bool) {
+        if let Some(marker) = ShellMarker::from_osc(params) {
+            self.markers.push(marker);
+        }
+    }
+
+    fn esc_dispatch(&mut self, intermediates: &[u8], _ignore: bool, byte: u8) {
+        if !intermediates.is_empty() {
+            return;
+        }
+
+        match byte {
+            b'7' => self.save_cursor(),
+            b'8' => self.restore_cursor(),
+            b'D' => self.linefeed(),
+            b'E' => {
+                self.col = 0;
+                self.linefeed();
+            }
+            b'M' => self.reverse_index(),
+            b'c' => {
+                let markers = std::mem::take(&mut self.markers);
+                let bells = self.bells;
+                *self = ScreenState::new(self.cols, self.rows);
+                self.markers = markers;
+                self.bells = bells;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn blank_grid(cols: usize, rows: usize) -> Grid {
+    vec![vec![Cell::blank(CellColor::Default); cols]; rows]
+}
+
+/// 그리드 크기 변경 (drop_top만큼 위쪽 줄 제거)
+fn resize_grid(grid: &mut Grid, cols: usize, rows: usize, drop_top: usize) {
+    grid.drain(..drop_top.min(grid.len()));
+    grid.resize(rows, vec![Cell::blank(CellColor::Default); cols]);
+    for line in grid.iter_mut() {
+        line.resize(cols, Cell::blank(CellColor::Default));
+    }
+}
+
+/// 확장 색상 인자 파싱 (5;n 또는 2;r;g;b), 사용한 인자 수 반환
+fn extended_color(values: &[u16]) -> Option<(CellColor, usize)> {
+    match values {
+        [5, index, ..] => Some((CellColor::Indexed(*index as u8), 2)),
+        // 콜론 형식은 색 공간 ID가 들어갈 수 있음 (38:2::r:g:b)
+        [2, _, r, g, b] => Some((CellColor::Rgb(*r as u8, *g as u8, *b as u8), 5)),
+        [2, r, g, b, ..] => Some((CellColor::Rgb(*r as u8, *g as u8, *b as u8), 4)),
+        _ => None,
+    }
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_and_cursor() {
+        let mut screen = VirtualScreen::new(10, 3);
+        screen.feed(b"hello\r\nworld\x1b[1;3HX");
+
+        assert_eq!(screen.text(), "heXlo\nworld\n");
+        let snapshot = screen.snapshot();
+        assert_eq!((snapshot.cursor.row, snapshot.cursor.col), (0, 3));
+    }
+
+    #[test]
+    fn test_wrap_and_scroll() {
+        let mut screen = VirtualScreen::new(4, 2);
+        screen.feed(b"abcdefgh\r\nij");
+
+        // 첫 줄 "abcd"가 스크롤되어 사라짐
+        assert_eq!(screen.text(), "efgh\nij");
+    }
+
+    #[test]
+    fn test_sgr_attributes() {
+        let mut screen = VirtualScreen::new(10, 1);
+        screen.feed(b"\x1b[1;31mA\x1b[38;2;1;2;3mB\x1b[0mC");
+
+        let cells = &screen.snapshot().lines[0];
+        assert!(cells[0].attrs.bold);
+        assert_eq!(cells[0].attrs.fg, CellColor::Indexed(1));
+        assert_eq!(cells[1].attrs.fg, CellColor::Rgb(1, 2, 3));
+        assert_eq!(cells[2].attrs, CellAttributes::default());
+    }
+
+    #[test]
+    fn test_wide_characters() {
+        let mut screen = VirtualScreen::new(5, 1);
+        screen.feed("한글".as_bytes());
+
+        assert_eq!(screen.text(), "한글");
+        let snapshot = screen.snapshot();
+        assert_eq!(snapshot.lines[0][0].width, 2);
+        assert_eq!(snapshot.lines[0][1].width, 0);
+        assert_eq!(snapshot.cursor.col, 4);
+    }
+
+    #[test]
+    fn test_alternate_screen() {
+        let mut screen = VirtualScreen::new(10, 2);
+        screen.feed(b"shell\x1b[?1049h\x1b[Hvim");
+        assert!(screen.alternate_screen());
+        assert_eq!(screen.text(), "vim\n");
+
+        screen.feed(b"\x1b[?1049l");
+        assert!(!screen.alternate_screen());
+        assert_eq!(screen.text(), "shell\n");
+    }
+
+    #[test]
+    fn test_bracketed_paste_mode() {
+        let mut screen = VirtualScreen::new(10, 2);
+        assert!(!screen.bracketed_paste());
+
+        screen.feed(b"\x1b[?2004h$ ");
+        assert!(screen.bracketed_paste());
+
+        // 전체 초기화(RIS)로도 꺼짐
+        screen.feed(b"\x1bc");
+        assert!(!screen.bracketed_paste());
+        screen.feed(b"\x1b[?2004h\x1b[?2004l");
+        assert!(!screen.bracketed_paste());
+    }
+
+    #[test]
+    fn test_bells() {
+        let mut screen = VirtualScreen::new(10, 2);
+        // OSC 종료 문자로 쓰인 BEL은 세지 않음
+        screen.feed(b"\x07\x1b]0;title\x07done\x07");
+        assert_eq!(screen.take_bells(), 2);
+        assert_eq!(screen.take_bells(), 0);
+    }
+}
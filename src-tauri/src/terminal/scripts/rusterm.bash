@@ -0,0 +1,35 @@
+# rusterm shell integration for bash (4.4+)
+# Emits OSC 133 command markers and OSC 7 working directory reports.
+if [ -z "${RUSTERM_SHELL_INTEGRATION:-}" ]; then
+  RUSTERM_SHELL_INTEGRATION=1
+
+  __rusterm_urlencode() {
+    local LC_ALL=C s="$1" out="" c i
+    for ((i = 0; i < ${#s}; i++)); do
+      c="${s:i:1}"
+      case "$c" in
+        [a-zA-Z0-9./_~-]) out+="$c" ;;
+        *) printf -v c '%%%02X' "'$c"; out+="$c" ;;
+      esac
+    done
+    printf '%s' "$out"
+  }
+
+  __rusterm_precmd() {
+    local status=$?
+    printf '\033]133;D;%s\007' "$status"
+    printf '\033]7;file://%s%s\007' "${HOSTNAME:-localhost}" "$(__rusterm_urlencode "$PWD")"
+    printf '\033]133;A\007'
+    return $status
+  }
+
+  __rusterm_preexec() {
+    local cmd
+    cmd=$(HISTTIMEFORMAT= builtin history 1 | sed 's/^ *[0-9]* *//')
+    printf '\033]133;C;cmdline_url=%s\007' "$(__rusterm_urlencode "$cmd")"
+  }
+
+  PROMPT_COMMAND="__rusterm_precmd${PROMPT_COMMAND:+; $PROMPT_COMMAND}"
+  PS0="${PS0:-}\$(__rusterm_preexec)"
+  PS1="${PS1}\[\033]133;B\007\]"
+fi
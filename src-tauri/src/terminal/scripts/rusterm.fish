@@ -0,0 +1,24 @@
+# rusterm shell integration for fish
+# Emits OSC 133 command markers and OSC 7 working directory reports.
+if not set -q RUSTERM_SHELL_INTEGRATION
+    set -g RUSTERM_SHELL_INTEGRATION 1
+
+    function __rusterm_preexec --on-event fish_preexec
+        printf '\e]133;C;cmdline_url=%s\a' (string escape --style=url -- "$argv")
+    end
+
+    function __rusterm_postexec --on-event fish_postexec
+        printf '\e]133;D;%s\a' $status
+    end
+
+    function __rusterm_prompt --on-event fish_prompt
+        printf '\e]7;file://%s%s\a' (hostname) "$PWD"
+        printf '\e]133;A\a'
+    end
+
+    functions -c fish_prompt __rusterm_original_prompt
+    function fish_prompt
+        __rusterm_original_prompt
+        printf '\e]133;B\a'
+    end
+end
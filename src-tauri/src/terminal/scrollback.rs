@@ -0,0 +1,270 @@
+use super::search::{search_lines, SearchOptions, SearchResult};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// 줄 수 제한을 사용할 때도 넘지 않는 최대 바이트 수
+/// (개행 없이 출력되는 스트림이 무한히 쌓이는 것을 방지)
+const MAX_SCROLLBACK_BYTES: usize = 8 * 1024 * 1024;
+
+/// 스크롤백 보관 한도
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum ScrollbackLimit {
+    /// 최근 N 바이트 보관
+    Bytes(usize),
+    /// 최근 N 줄(개행 기준) + 현재 입력 중인 줄 보관
+    Lines(usize),
+}
+
+impl Default for ScrollbackLimit {
+    fn default() -> Self {
+        ScrollbackLimit::Lines(10_000)
+    }
+}
+
+/// 스크롤백 재생 응답
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrollbackReplay {
+    /// start_offset부터 end_offset까지의 출력
+    pub data: String,
+    /// 반환한 첫 바이트의 스트림 오프셋
+    pub start_offset: u64,
+    /// 반환한 마지막 바이트 다음의 스트림 오프셋 (다음 재생 요청에 사용)
+    pub end_offset: u64,
+    /// 다음 출력 이벤트의 시퀀스 번호 (이보다 작은 seq 이벤트는 이미 data에 포함됨)
+    pub next_seq: u64,
+    /// 요청한 오프셋이 이미 버퍼에서 밀려나 일부 출력이 누락된 경우 true
+    pub truncated: bool,
+}
+
+/// 세션별 출력 링 버퍼
+///
+/// 세션이 내보낸 출력 청크를 한도 내에서 보관합니다.
+/// 오프셋은 세션 시작 이후 누적된 바이트 위치이며, 청크마다 시퀀스 번호가 부여됩니다.
+#[derive(Debug)]
+pub struct Scrollback {
+    limit: ScrollbackLimit,
+    chunks: VecDeque<Vec<u8>>,
+    /// 보관 중인 첫 바이트의 스트림 오프셋
+    start_offset: u64,
+    /// 보관 중인 첫 줄의 줄 번호 (제거된 개행 수)
+    start_line: u64,
+    /// 보관 중인 바이트 수
+    bytes: usize,
+    /// 보관 중인 개행 문자 수
+    lines: usize,
+    /// 다음 청크에 부여할 시퀀스 번호
+    next_seq: u64,
+}
+
+impl Scrollback {
+    /// 새 스크롤백 버퍼 생성
+    pub fn new(limit: ScrollbackLimit) -> Self {
+        Self {
+            limit,
+            chunks: VecDeque::new(),
+            start_offset: 0,
+            start_line: 0,
+            bytes: 0,
+            lines: 0,
+            next_seq: 0,
+        }
+    }
+
+    /// 출력 청크 추가 후 청크의 시퀀스 번호 반환
+    pub fn push(&mut self, data: &[u8]) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+
+        if !data.is_empty() {
+            self.bytes += data.len();
+            self.lines += count_newlines(data);
+            self.chunks.push_back(data.to_vec());
+            self.trim();
+        }
+
+        seq
+    }
+
+    /// 보관 중인 마지막 바이트 다음의 스트림 오프셋
+    pub fn end_offset(&self) -> u64 {
+        self.start_offset + self.bytes as u64
+    }
+
+    /// 다음 청크에 부여할 시퀀스 번호
+    pub fn next_seq(&self) -> u64 {
+        self.next_seq
+    }
+
+    /// from_offset 이후의 출력 반환
+    pub fn replay(&self, from_offset: u64) -> ScrollbackReplay {
+        let (start_offset, data) = self.read_bytes(from_offset);
+
+        ScrollbackReplay {
+            data: String::from_utf8_lossy(&data).into_owned(),
+            start_offset,
+            end_offset: self.end_offset(),
+            next_seq: self.next_seq,
+            truncated: from_offset < self.start_offset,
+        }
+    }
+
+    /// from_offset 이후의 원시 출력과 실제 시작 오프셋 반환
+    ///
+    /// 시작 오프셋이 from_offset보다 크면 요청한 출력 일부가 이미 밀려난 것입니다.
+    pub fn read_bytes(&self, from_offset: u64) -> (u64, Vec<u8>) {
+        let end_offset = self.end_offset();
+        let start_offset = from_offset.clamp(self.start_offset, end_offset);
+
+        let mut data = Vec::with_capacity((end_offset - start_offset) as usize);
+        let mut chunk_offset = self.start_offset;
+        for chunk in &self.chunks {
+            let chunk_end = chunk_offset + chunk.len() as u64;
+            if chunk_end > start_offset {
+                let skip = start_offset.saturating_sub(chunk_offset) as usize;
+                data.extend_from_slice(&chunk[skip..]);
+            }
+            chunk_offset = chunk_end;
+        }
+
+        (start_offset, data)
+    }
+
+    /// 보관 중인 출력에서 줄 단위 검색 (ANSI 제거 후 비교)
+    pub fn search(&self, matcher: &Regex, options: &SearchOptions) -> SearchResult {
+        let data: Vec<u8> = self.chunks.iter().flatten().copied().collect();
+        search_lines(&data, self.start_offset, self.start_line, matcher, options)
+    }
+
+    /// 한도를 넘는 오래된 출력 제거
+    fn trim(&mut self) {
+        let excess = match self.limit {
+            ScrollbackLimit::Bytes(max) => self.bytes.saturating_sub(max),
+            ScrollbackLimit::Lines(max) => {
+                let line_excess = self.bytes_before_newline(self.lines.saturating_sub(max));
+                line_excess.max(self.bytes.saturating_sub(MAX_SCROLLBACK_BYTES))
+            }
+        };
+        self.drop_front(excess);
+    }
+
+    /// 앞에서부터 n번째 개행 문자까지(포함)의 바이트 수
+    fn bytes_before_newline(&self, n: usize) -> usize {
+        if n == 0 {
+            return 0;
+        }
+
+        let mut remaining = n;
+        let mut count = 0;
+        for chunk in &self.chunks {
+            for (i, byte) in chunk.iter().enumerate() {
+                if *byte == b'\n' {
+                    remaining -= 1;
+                    if remaining == 0 {
+                        return count + i + 1;
+                    }
+                }
+            }
+            count += chunk.len();
+        }
+        count
+    }
+
+    /// 앞에서부터 count 바이트 제거 (UTF-8 문자 중간에서 자르지 않음)
+    fn drop_front(&mut self, mut count: usize) {
+        while count > 0 {
+            let Some(front) = self.chunks.front_mut() else {
+                break;
+            };
+
+            let removed = if front.len() <= count {
+                self.chunks.pop_front().unwrap_or_default()
+            } else {
+                let mut cut = count;
+                while cut < front.len() && (front[cut] & 0xC0) == 0x80 {
+                    cut += 1;
+                }
+                let removed: Vec<u8> = front.drain(..cut).collect();
+                if front.is_empty() {
+                    self.chunks.pop_front();
+                }
+                removed
+            };
+
+            count = count.saturating_sub(removed.len());
+            self.bytes -= removed.len();
+            let removed_lines = count_newlines(&removed);
+            self.lines -= removed_lines;
+            self.start_line += removed_lines as u64;
+            self.start_offset += removed.len() as u64;
+        }
+    }
+}
+
+fn count_newlines(data: &[u8]) -> usize {
+    data.iter().filter(|b| **b == b'\n').count()
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_limit_keeps_latest_output() {
+        let mut scrollback = Scrollback::new(ScrollbackLimit::Bytes(8));
+        assert_eq!(scrollback.push(b"hello "), 0);
+        assert_eq!(scrollback.push(b"world"), 1);
+
+        let replay = scrollback.replay(0);
+        assert_eq!(replay.data, "lo world");
+        assert_eq!(replay.start_offset, 3);
+        assert_eq!(replay.end_offset, 11);
+        assert_eq!(replay.next_seq, 2);
+        assert!(replay.truncated);
+    }
+
+    #[test]
+    fn test_line_limit() {
+        let mut scrollback = Scrollback::new(ScrollbackLimit::Lines(2));
+        scrollback.push(b"one\ntwo\n");
+        scrollback.push(b"three\nfour");
+
+        // 개행 2개(완성된 줄 2개)와 현재 줄만 남음
+        assert_eq!(scrollback.replay(0).data, "two\nthree\nfour");
+    }
+
+    #[test]
+    fn test_replay_from_offset() {
+        let mut scrollback = Scrollback::new(ScrollbackLimit::default());
+        scrollback.push(b"abc");
+        scrollback.push(b"def");
+
+        let replay = scrollback.replay(4);
+        assert_eq!(replay.data, "ef");
+        assert!(!replay.truncated);
+        assert_eq!(scrollback.replay(100).data, "");
+    }
+
+    #[test]
+    fn test_read_bytes_keeps_raw_output() {
+        let mut scrollback = Scrollback::new(ScrollbackLimit::Bytes(4));
+        scrollback.push(b"ab");
+        scrollback.push(&[0xFF, 0xFE, b'c']);
+
+        assert_eq!(scrollback.read_bytes(0), (1, vec![b'b', 0xFF, 0xFE, b'c']));
+        assert_eq!(scrollback.read_bytes(3), (3, vec![0xFE, b'c']));
+    }
+
+    #[test]
+    fn test_trim_does_not_split_characters() {
+        let mut scrollback = Scrollback::new(ScrollbackLimit::Bytes(4));
+        scrollback.push("가나".as_bytes());
+
+        assert_eq!(scrollback.replay(0).data, "나");
+    }
+}
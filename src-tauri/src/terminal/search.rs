@@ -0,0 +1,186 @@
+use super::ansi::strip_ansi;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+/// 정규식 크기 제한 (지나치게 큰 패턴 방지)
+const REGEX_SIZE_LIMIT: usize = 1024 * 1024;
+
+/// 스크롤백 검색 옵션
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SearchOptions {
+    /// 패턴을 정규식으로 해석 (false면 일반 텍스트)
+    pub regex: bool,
+    pub case_sensitive: bool,
+    /// 일치한 줄 앞뒤로 함께 반환할 줄 수
+    pub context_lines: usize,
+    /// 최대 결과 수
+    pub max_results: usize,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            regex: false,
+            case_sensitive: false,
+            context_lines: 2,
+            max_results: 1000,
+        }
+    }
+}
+
+/// 검색 결과 항목
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchMatch {
+    /// 세션 시작 이후의 줄 번호 (0부터)
+    pub line: u64,
+    /// 줄 시작 위치의 스트림 오프셋 (replay_session의 from_offset으로 사용 가능)
+    pub offset: u64,
+    /// 줄 안에서 일치 시작 위치 (문자 단위, ANSI 제거 후)
+    pub column: usize,
+    /// 일치한 텍스트
+    pub text: String,
+    /// 일치한 줄 (ANSI 제거)
+    pub line_text: String,
+    pub context_before: Vec<String>,
+    pub context_after: Vec<String>,
+}
+
+/// 검색 응답
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub matches: Vec<SearchMatch>,
+    /// max_results에 도달해 검색을 중단했는지 여부
+    pub truncated: bool,
+}
+
+/// 검색 패턴 컴파일
+pub fn build_matcher(pattern: &str, options: &SearchOptions) -> Result<Regex, regex::Error> {
+    let pattern = if options.regex {
+        pattern.to_string()
+    } else {
+        regex::escape(pattern)
+    };
+
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!options.case_sensitive)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+}
+
+/// 보관 중인 출력에서 줄 단위 검색
+///
+/// data는 start_offset 위치에서 시작하며, 첫 줄의 줄 번호가 start_line입니다.
+pub fn search_lines(
+    data: &[u8],
+    start_offset: u64,
+    start_line: u64,
+    matcher: &Regex,
+    options: &SearchOptions,
+) -> SearchResult {
+    // (스트림 오프셋, ANSI 제거한 줄)
+    let mut lines = Vec::new();
+    let mut offset = start_offset;
+    for raw in data.split(|b| *b == b'\n') {
+        lines.push((offset, strip_ansi(raw)));
+        offset += raw.len() as u64 + 1;
+    }
+
+    let mut matches = Vec::new();
+    for (index, (offset, text)) in lines.iter().enumerate() {
+        for found in matcher.find_iter(text) {
+            if found.as_str().is_empty() {
+                continue;
+            }
+            if matches.len() >= options.max_results {
+                return SearchResult {
+                    matches,
+                    truncated: true,
+                };
+            }
+
+            let before = index.saturating_sub(options.context_lines);
+            let after = (index + 1 + options.context_lines).min(lines.len());
+            matches.push(SearchMatch {
+                line: start_line + index as u64,
+                offset: *offset,
+                column: text[..found.start()].chars().count(),
+                text: found.as_str().to_string(),
+                line_text: text.clone(),
+                context_before: lines[before..index]
+                    .iter()
+                    .map(|(_, l)| l.clone())
+                    .collect(),
+                context_after: lines[index + 1..after]
+                    .iter()
+                    .map(|(_, l)| l.clone())
+                    .collect(),
+            });
+        }
+    }
+
+    SearchResult {
+        matches,
+        truncated: false,
+    }
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &[u8] = b"start\n\x1b[31mError\x1b[0m: boom\nat main.rs:10\nok\n";
+
+    #[test]
+    fn test_plain_text_case_insensitive() {
+        let options = SearchOptions {
+            context_lines: 1,
+            ..SearchOptions::default()
+        };
+        let matcher = build_matcher("error", &options).unwrap();
+        let result = search_lines(OUTPUT, 100, 5, &matcher, &options);
+
+        assert_eq!(result.matches.len(), 1);
+        let found = &result.matches[0];
+        assert_eq!(found.line, 6);
+        assert_eq!(found.offset, 106);
+        assert_eq!(found.text, "Error");
+        assert_eq!(found.line_text, "Error: boom");
+        assert_eq!(found.context_before, vec!["start"]);
+        assert_eq!(found.context_after, vec!["at main.rs:10"]);
+    }
+
+    #[test]
+    fn test_regex_and_case_sensitive() {
+        let options = SearchOptions {
+            regex: true,
+            case_sensitive: true,
+            ..SearchOptions::default()
+        };
+        let matcher = build_matcher(r"\w+\.rs:\d+", &options).unwrap();
+        let result = search_lines(OUTPUT, 0, 0, &matcher, &options);
+        assert_eq!(result.matches[0].text, "main.rs:10");
+        assert_eq!(result.matches[0].column, 3);
+
+        let matcher = build_matcher("error", &options).unwrap();
+        assert!(search_lines(OUTPUT, 0, 0, &matcher, &options)
+            .matches
+            .is_empty());
+    }
+
+    #[test]
+    fn test_max_results() {
+        let options = SearchOptions {
+            max_results: 1,
+            ..SearchOptions::default()
+        };
+        let matcher = build_matcher("o", &options).unwrap();
+        let result = search_lines(OUTPUT, 0, 0, &matcher, &options);
+        assert_eq!(result.matches.len(), 1);
+        assert!(result.truncated);
+    }
+}
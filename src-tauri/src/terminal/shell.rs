@@ -0,0 +1,341 @@
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 셸 통합 스크립트 (OSC 133 / OSC 7 출력)
+const BASH_SCRIPT: &str = include_str!("scripts/rusterm.bash");
+const ZSH_SCRIPT: &str = include_str!("scripts/rusterm.zsh");
+const FISH_SCRIPT: &str = include_str!("scripts/rusterm.fish");
+
+/// 셸 통합을 지원하는 셸 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShellKind {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl ShellKind {
+    /// 셸 경로 또는 이름으로 종류 판별 (예: "/bin/zsh", "bash.exe")
+    pub fn from_path(path: &str) -> Option<Self> {
+        let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+        match name.trim_end_matches(".exe") {
+            "bash" => Some(ShellKind::Bash),
+            "zsh" => Some(ShellKind::Zsh),
+            "fish" => Some(ShellKind::Fish),
+            _ => None,
+        }
+    }
+
+    /// 통합 스크립트 원문
+    pub fn script(&self) -> &'static str {
+        match self {
+            ShellKind::Bash => BASH_SCRIPT,
+            ShellKind::Zsh => ZSH_SCRIPT,
+            ShellKind::Fish => FISH_SCRIPT,
+        }
+    }
+
+    /// 실행 중인 셸에 입력으로 보내 스크립트를 로드하는 한 줄 명령
+    ///
+    /// 원격 호스트에도 파일 없이 적용되도록 base64로 인코딩해 전달하며,
+    /// 앞의 공백은 HISTCONTROL=ignorespace 환경에서 히스토리에 남지 않게 합니다.
+    pub fn injection_command(&self) -> String {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(self.script());
+        match self {
+            ShellKind::Bash | ShellKind::Zsh => {
+                format!(" eval \"$(printf '%s' '{}' | base64 -d)\"\r", encoded)
+            }
+            ShellKind::Fish => format!(" printf '%s' '{}' | base64 -d | source\r", encoded),
+        }
+    }
+}
+
+/// 출력에서 인식한 셸 통합 마커
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShellMarker {
+    /// OSC 133;A - 프롬프트 시작
+    PromptStart,
+    /// OSC 133;B - 프롬프트 끝, 명령 입력 시작
+    CommandStart,
+    /// OSC 133;C - 명령 실행 (출력 시작)
+    CommandExecuted { command: Option<String> },
+    /// OSC 133;D - 명령 종료
+    CommandFinished { exit_code: Option<i32> },
+    /// OSC 7 - 현재 작업 디렉토리
+    WorkingDirectory { host: Option<String>, path: String },
+}
+
+impl ShellMarker {
+    /// OSC 파라미터(';'로 나뉜 값)를 마커로 변환
+    pub fn from_osc(params: &[&[u8]]) -> Option<Self> {
+        let text = |i: usize| {
+            params
+                .get(i)
+                .map(|p| String::from_utf8_lossy(p).into_owned())
+        };
+
+        match params.first().copied()? {
+            b"133" => match params.get(1).copied()? {
+                b"A" => Some(ShellMarker::PromptStart),
+                b"B" => Some(ShellMarker::CommandStart),
+                b"C" => {
+                    let command = params[2..].iter().find_map(|p| {
+                        let p = String::from_utf8_lossy(p);
+                        p.strip_prefix("cmdline_url=").map(percent_decode)
+                    });
+                    Some(ShellMarker::CommandExecuted { command })
+                }
+                b"D" => Some(ShellMarker::CommandFinished {
+                    exit_code: text(2).and_then(|code| code.trim().parse().ok()),
+                }),
+                _ => None,
+            },
+            b"7" => {
+                let url = text(1)?;
+                let rest = url.strip_prefix("file://")?;
+                let (host, path) = match rest.find('/') {
+                    Some(index) => (&rest[..index], &rest[index..]),
+                    None => return None,
+                };
+                Some(ShellMarker::WorkingDirectory {
+                    host: (!host.is_empty()).then(|| host.to_string()),
+                    path: percent_decode(path),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 실행 중인 명령
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningCommand {
+    pub command: Option<String>,
+    pub cwd: Option<String>,
+    /// 시작 시각 (Unix epoch 밀리초)
+    pub started_at: u64,
+    /// 출력 시작 위치의 스트림 오프셋
+    pub output_start: u64,
+}
+
+/// 종료된 명령
+#[derive(Debug, Clone, Serialize)]
+pub struct FinishedCommand {
+    pub command: Option<String>,
+    pub cwd: Option<String>,
+    pub started_at: u64,
+    pub duration_ms: u64,
+    pub exit_code: Option<i32>,
+    /// 명령 출력의 스트림 오프셋 범위 [output_start, output_end)
+    pub output_start: u64,
+    pub output_end: u64,
+}
+
+/// 셸 통합 상태 변화
+#[derive(Debug, Clone)]
+pub enum ShellEvent {
+    CommandStarted(RunningCommand),
+    CommandFinished(FinishedCommand),
+}
+
+/// command-started 이벤트 페이로드
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandStartedEvent {
+    pub session_id: String,
+    #[serde(flatten)]
+    pub command: RunningCommand,
+}
+
+/// command-finished 이벤트 페이로드
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandFinishedEvent {
+    pub session_id: String,
+    #[serde(flatten)]
+    pub command: FinishedCommand,
+}
+
+/// 세션의 셸 상태 (get_shell_state 응답)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShellState {
+    /// 셸 통합 마커를 한 번이라도 받았는지 여부
+    pub integration_active: bool,
+    pub cwd: Option<String>,
+    pub host: Option<String>,
+    pub running: Option<RunningCommand>,
+    pub last_exit_code: Option<i32>,
+}
+
+/// 셸 통합 마커로 명령 경계, 종료 코드, 작업 디렉토리를 추적
+#[derive(Debug, Default)]
+pub struct ShellTracker {
+    state: ShellState,
+}
+
+impl ShellTracker {
+    /// 현재 상태
+    pub fn state(&self) -> &ShellState {
+        &self.state
+    }
+
+    /// 마커 적용 (offset은 마커 위치의 스트림 오프셋)
+    pub fn apply(
+        &mut self,
+        marker: ShellMarker,
+        offset: u64,
+        now: SystemTime,
+    ) -> Option<ShellEvent> {
+        self.state.integration_active = true;
+
+        match marker {
+            ShellMarker::PromptStart | ShellMarker::CommandStart => None,
+            ShellMarker::WorkingDirectory { host, path } => {
+                self.state.host = host;
+                self.state.cwd = Some(path);
+                None
+            }
+            ShellMarker::CommandExecuted { command } => {
+                let running = RunningCommand {
+                    command,
+                    cwd: self.state.cwd.clone(),
+                    started_at: unix_millis(now),
+                    output_start: offset,
+                };
+                self.state.running = Some(running.clone());
+                Some(ShellEvent::CommandStarted(running))
+            }
+            ShellMarker::CommandFinished { exit_code } => {
+                // 실행 중인 명령 없이 받은 D(빈 입력 후 프롬프트 등)는 무시
+                let running = self.state.running.take()?;
+                self.state.last_exit_code = exit_code;
+                Some(ShellEvent::CommandFinished(FinishedCommand {
+                    duration_ms: unix_millis(now).saturating_sub(running.started_at),
+                    command: running.command,
+                    cwd: running.cwd,
+                    started_at: running.started_at,
+                    exit_code,
+                    output_start: running.output_start,
+                    output_end: offset,
+                }))
+            }
+        }
+    }
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// URL 퍼센트 인코딩 해제 (잘못된 시퀀스는 그대로 유지)
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Some(byte) = text
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_markers() {
+        assert_eq!(
+            ShellMarker::from_osc(&[b"133", b"C", b"cmdline_url=ls%20-la%3B%20%ED%95%9C"]),
+            Some(ShellMarker::CommandExecuted {
+                command: Some("ls -la; 한".to_string())
+            })
+        );
+        assert_eq!(
+            ShellMarker::from_osc(&[b"133", b"D", b"127"]),
+            Some(ShellMarker::CommandFinished {
+                exit_code: Some(127)
+            })
+        );
+        assert_eq!(
+            ShellMarker::from_osc(&[b"7", b"file://host/home/user/my%20dir"]),
+            Some(ShellMarker::WorkingDirectory {
+                host: Some("host".to_string()),
+                path: "/home/user/my dir".to_string()
+            })
+        );
+        assert_eq!(ShellMarker::from_osc(&[b"0", b"title"]), None);
+    }
+
+    #[test]
+    fn test_tracker_command_lifecycle() {
+        let mut tracker = ShellTracker::default();
+        let start = UNIX_EPOCH + Duration::from_secs(1000);
+
+        tracker.apply(
+            ShellMarker::WorkingDirectory {
+                host: None,
+                path: "/tmp".to_string(),
+            },
+            0,
+            start,
+        );
+        // 실행 중인 명령이 없으면 D는 무시
+        assert!(tracker
+            .apply(
+                ShellMarker::CommandFinished { exit_code: Some(0) },
+                5,
+                start
+            )
+            .is_none());
+
+        let started = tracker.apply(
+            ShellMarker::CommandExecuted {
+                command: Some("make".to_string()),
+            },
+            10,
+            start,
+        );
+        assert!(matches!(started, Some(ShellEvent::CommandStarted(_))));
+
+        let finished = tracker.apply(
+            ShellMarker::CommandFinished { exit_code: Some(2) },
+            50,
+            start + Duration::from_millis(1500),
+        );
+        let Some(ShellEvent::CommandFinished(finished)) = finished else {
+            panic!("expected CommandFinished");
+        };
+        assert_eq!(finished.command.as_deref(), Some("make"));
+        assert_eq!(finished.cwd.as_deref(), Some("/tmp"));
+        assert_eq!(finished.duration_ms, 1500);
+        assert_eq!((finished.output_start, finished.output_end), (10, 50));
+        assert_eq!(tracker.state().last_exit_code, Some(2));
+        assert!(tracker.state().running.is_none());
+    }
+
+    #[test]
+    fn test_shell_kind_from_path() {
+        assert_eq!(ShellKind::from_path("/usr/bin/zsh"), Some(ShellKind::Zsh));
+        assert_eq!(ShellKind::from_path("bash.exe"), Some(ShellKind::Bash));
+        assert_eq!(ShellKind::from_path("/bin/sh"), None);
+        assert_eq!(ShellKind::from_path("powershell.exe"), None);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::automation::{AutomationTarget, SessionTarget};
+use crate::pty::PtyManager;
+use crate::settings::SettingsManager;
+use crate::ssh::SshManager;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// 세션 출력 스트림이 이벤트, 알림, 자동 응답을 보내는 곳
+///
+/// GUI 프로세스에서는 AppHandle이 프론트엔드로 바로 보내고,
+/// 세션 호스트에서는 연결된 GUI로 전달합니다.
+pub trait SessionSink: Send + Sync {
+    /// 프론트엔드 이벤트 발행
+    fn emit_value(&self, event: &str, payload: serde_json::Value) -> Result<(), String>;
+
+    /// 데스크톱 알림 표시
+    fn notify(&self, title: &str, body: &str) -> Result<(), String>;
+
+    /// 트리거, 하이라이트, 입력 감사 설정
+    fn settings(&self) -> Option<&SettingsManager>;
+
+    /// 트리거 자동 응답을 세션에 입력 (flusher 스레드를 막지 않도록 비동기로 처리)
+    fn respond(&self, session_id: String, data: String);
+}
+
+impl dyn SessionSink {
+    /// 페이로드를 직렬화해 이벤트 발행
+    pub fn emit_event<T: Serialize>(&self, event: &str, payload: T) -> Result<(), String> {
+        let payload = serde_json::to_value(payload).map_err(|e| e.to_string())?;
+        self.emit_value(event, payload)
+    }
+}
+
+impl SessionSink for AppHandle {
+    fn emit_value(&self, event: &str, payload: serde_json::Value) -> Result<(), String> {
+        Emitter::emit(self, event, payload).map_err(|e| e.to_string())
+    }
+
+    fn notify(&self, title: &str, body: &str) -> Result<(), String> {
+        self.notification()
+            .builder()
+            .title(title)
+            .body(body)
+            .show()
+            .map_err(|e| e.to_string())
+    }
+
+    fn settings(&self) -> Option<&SettingsManager> {
+        self.try_state::<SettingsManager>().map(|state| state.inner())
+    }
+
+    fn respond(&self, session_id: String, data: String) {
+        // 세션 쓰기는 비동기 매니저를 거치므로 flusher 스레드를 막지 않도록 분리
+        let app_handle = self.clone();
+        tauri::async_runtime::spawn(async move {
+            let pty_manager = app_handle.state::<PtyManager>();
+            let ssh_manager = app_handle.state::<SshManager>();
+            let target = SessionTarget::new(session_id, &pty_manager, &ssh_manager);
+            if let Err(e) = target.write(&data).await {
+                eprintln!("Failed to send trigger response: {}", e);
+            }
+        });
+    }
+}
@@ -0,0 +1,380 @@
+use super::audit::InputAuditor;
+use super::batcher::OutputBatcher;
+use super::highlight::Highlighter;
+use super::history::{CommandHistory, CommandHistoryEntry};
+use super::logger::LogSlot;
+use super::monitor::{
+    MonitorConfig, SessionActivityEvent, SessionBellEvent, SessionMonitor, SessionSilenceEvent,
+};
+use super::paste::bracket_paste;
+use super::recorder::RecordingSlot;
+use super::screen::{ScreenSnapshot, VirtualScreen};
+use super::scrollback::{Scrollback, ScrollbackLimit, ScrollbackReplay};
+use super::search::{build_matcher, SearchOptions, SearchResult};
+use super::shell::{
+    CommandFinishedEvent, CommandStartedEvent, ShellEvent, ShellMarker, ShellState, ShellTracker,
+};
+use super::sink::SessionSink;
+use super::trigger::{TriggerMatch, TriggerMatchedEvent, TriggerMatcher};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime};
+
+/// 세션 출력 스트림 공유 상태
+///
+/// PTY/SSH reader 스레드가 읽은 출력은 배처에 모였다가 flusher 스레드에서 프레임 단위로
+/// 스크롤백, 화면 모델, 트리거, 녹화/로그 파일, 프론트엔드 이벤트로 나눠 보내집니다.
+/// reader 스레드와 세션(커맨드 처리)이 `Arc`로 공유합니다.
+pub struct SessionStream {
+    session_id: String,
+    scrollback: Mutex<Scrollback>,
+    screen: Mutex<VirtualScreen>,
+    recording: RecordingSlot,
+    /// 세션 로그 파일
+    logging: LogSlot,
+    batcher: OutputBatcher,
+    /// 셸 통합 마커로 추적한 명령/작업 디렉토리 상태
+    shell: Mutex<ShellTracker>,
+    /// 종료된 명령 히스토리
+    history: Mutex<CommandHistory>,
+    /// 설정의 트리거 규칙 매칭 상태
+    triggers: Mutex<TriggerMatcher>,
+    /// 설정의 하이라이트 규칙 적용 상태
+    highlighter: Mutex<Highlighter>,
+    /// 활동/무출력 감시
+    monitor: SessionMonitor,
+    /// 입력 감사 (켜진 세션만)
+    audit: Mutex<Option<InputAuditor>>,
+    /// 이벤트, 알림, 설정을 주고받는 곳 (GUI 프로세스 또는 세션 호스트)
+    sink: Arc<dyn SessionSink>,
+}
+
+impl SessionStream {
+    /// 새 출력 스트림 생성
+    ///
+    /// host는 명령 히스토리를 저장할 호스트 이름입니다 (로컬 PTY는 `LOCAL_HOST`).
+    pub fn new(
+        session_id: String,
+        host: String,
+        scrollback_limit: ScrollbackLimit,
+        cols: u16,
+        rows: u16,
+        sink: Arc<dyn SessionSink>,
+    ) -> Self {
+        let history_path = CommandHistory::default_path(&host);
+        Self {
+            history: Mutex::new(CommandHistory::new(session_id.clone(), host, history_path)),
+            session_id,
+            scrollback: Mutex::new(Scrollback::new(scrollback_limit)),
+            screen: Mutex::new(VirtualScreen::new(cols, rows)),
+            recording: RecordingSlot::default(),
+            logging: LogSlot::default(),
+            batcher: OutputBatcher::new(),
+            shell: Mutex::new(ShellTracker::default()),
+            triggers: Mutex::new(TriggerMatcher::default()),
+            highlighter: Mutex::new(Highlighter::new()),
+            monitor: SessionMonitor::new(),
+            audit: Mutex::new(None),
+            sink,
+        }
+    }
+
+    /// reader 스레드에서 읽은 출력을 전송 대기열에 추가
+    pub fn queue_output(&self, data: &[u8]) {
+        self.batcher.push(data);
+    }
+
+    /// 프론트엔드가 따라올 때까지 대기 (흐름 제어, timeout 안에 여유가 없으면 false)
+    pub fn wait_ready(&self, timeout: Option<Duration>) -> bool {
+        self.batcher.wait_ready(timeout)
+    }
+
+    /// 프론트엔드가 seq까지의 출력 이벤트를 처리했음을 확인
+    pub fn ack_output(&self, seq: u64) {
+        self.batcher.ack(seq);
+    }
+
+    /// 흐름 제어 해제 (출력을 확인할 프론트엔드가 없어져도 읽기가 멈추지 않게 함)
+    pub fn release_output(&self) {
+        self.batcher.release();
+    }
+
+    /// 출력 종료 (남은 출력을 모두 보내고 flusher 스레드와 감시 스레드 종료)
+    pub fn close_output(&self) {
+        self.batcher.close();
+        self.monitor.close();
+    }
+
+    /// 프레임 단위로 출력을 내보내는 flusher 스레드 시작
+    ///
+    /// 각 프레임은 스크롤백, 녹화, 로그에 기록된 뒤 하이라이트 규칙을 적용해 시퀀스 번호와 함께
+    /// emit으로 전달됩니다.
+    pub fn spawn_flusher<F>(self: &Arc<Self>, mut emit: F) -> JoinHandle<()>
+    where
+        F: FnMut(u64, Vec<u8>) + Send + 'static,
+    {
+        let stream = self.clone();
+        thread::spawn(move || {
+            while let Some(frame) = stream.batcher.next_frame() {
+                let seq = stream.push_output(&frame);
+                let frame = stream.highlight(frame);
+                stream.batcher.sent(seq, frame.len());
+                emit(seq, frame);
+            }
+        })
+    }
+
+    /// 출력 프레임 처리 후 이벤트에 붙일 시퀀스 번호 반환
+    fn push_output(&self, data: &[u8]) -> u64 {
+        let (seq, base_offset) = {
+            let mut scrollback = self.scrollback.lock().unwrap();
+            let base_offset = scrollback.end_offset();
+            (scrollback.push(data), base_offset)
+        };
+
+        // OSC 시작(ESC ])마다 나눠 넣어 셸 통합 마커의 스트림 오프셋을 구함
+        let mut markers = Vec::new();
+        let bells = {
+            let mut screen = self.screen.lock().unwrap();
+            let mut start = 0;
+            while start < data.len() {
+                let end = data[start + 1..]
+                    .windows(2)
+                    .position(|w| w == b"\x1b]")
+                    .map_or(data.len(), |i| start + 1 + i);
+                screen.feed(&data[start..end]);
+                let offset = base_offset + start as u64;
+                markers.extend(screen.take_markers().into_iter().map(|m| (m, offset)));
+                start = end;
+            }
+            screen.take_bells()
+        };
+        if !markers.is_empty() {
+            self.apply_markers(markers);
+        }
+        self.emit_activity(bells);
+        self.apply_triggers(data);
+
+        self.recording.output(&String::from_utf8_lossy(data));
+        self.logging.output(data);
+        if let Some(audit) = self.audit.lock().unwrap().as_mut() {
+            audit.output(data);
+        }
+        seq
+    }
+
+    /// 셸 통합 마커 적용 후 명령 시작/종료 이벤트 발행
+    fn apply_markers(&self, markers: Vec<(ShellMarker, u64)>) {
+        let now = SystemTime::now();
+        let events: Vec<ShellEvent> = {
+            let mut shell = self.shell.lock().unwrap();
+            markers
+                .into_iter()
+                .filter_map(|(marker, offset)| shell.apply(marker, offset, now))
+                .collect()
+        };
+
+        for event in events {
+            let result = match event {
+                ShellEvent::CommandStarted(command) => self.sink.emit_event(
+                    "command-started",
+                    CommandStartedEvent {
+                        session_id: self.session_id.clone(),
+                        command,
+                    },
+                ),
+                ShellEvent::CommandFinished(command) => {
+                    self.history.lock().unwrap().record(command.clone());
+                    self.sink.emit_event(
+                        "command-finished",
+                        CommandFinishedEvent {
+                            session_id: self.session_id.clone(),
+                            command,
+                        },
+                    )
+                }
+            };
+            if let Err(e) = result {
+                eprintln!("Failed to emit shell integration event: {}", e);
+            }
+        }
+    }
+
+    /// 출력 활동 기록 후 session-activity / session-bell 이벤트 발행
+    fn emit_activity(&self, bells: usize) {
+        if let Some(idle) = self.monitor.output() {
+            let event = SessionActivityEvent {
+                session_id: self.session_id.clone(),
+                idle_ms: idle.as_millis() as u64,
+            };
+            if let Err(e) = self.sink.emit_event("session-activity", event) {
+                eprintln!("Failed to emit session-activity event: {}", e);
+            }
+        }
+
+        if bells > 0 {
+            let event = SessionBellEvent {
+                session_id: self.session_id.clone(),
+                count: bells,
+            };
+            if let Err(e) = self.sink.emit_event("session-bell", event) {
+                eprintln!("Failed to emit session-bell event: {}", e);
+            }
+        }
+    }
+
+    /// 활동/무출력 감시 설정 (무출력 감시가 켜지면 감시 스레드 시작)
+    pub fn set_monitor(self: &Arc<Self>, config: MonitorConfig) {
+        if !self.monitor.configure(config) {
+            return;
+        }
+
+        let stream = self.clone();
+        thread::spawn(move || {
+            stream.monitor.watch(|silent| {
+                let event = SessionSilenceEvent {
+                    session_id: stream.session_id.clone(),
+                    silent_ms: silent.as_millis() as u64,
+                };
+                if let Err(e) = stream.sink.emit_event("session-silence", event) {
+                    eprintln!("Failed to emit session-silence event: {}", e);
+                }
+            });
+        });
+    }
+
+    /// 활동/무출력 감시 설정 조회
+    pub fn monitor_config(&self) -> MonitorConfig {
+        self.monitor.config()
+    }
+
+    /// 하이라이트 규칙 적용 (스크롤백과 녹화에는 원본 출력이 남음)
+    fn highlight(&self, frame: Vec<u8>) -> Vec<u8> {
+        let Some(settings) = self.sink.settings() else {
+            return frame;
+        };
+        let highlights = settings.highlights();
+        self.highlighter.lock().unwrap().process(&highlights, frame)
+    }
+
+    /// 트리거 규칙 검사 후 이벤트 발행, 알림, 자동 응답 처리
+    ///
+    /// flusher 스레드에서 실행되므로 탭이 보이지 않아도 동작합니다.
+    fn apply_triggers(&self, data: &[u8]) {
+        let Some(settings) = self.sink.settings() else {
+            return;
+        };
+        let triggers = settings.triggers();
+        if triggers.is_empty() {
+            return;
+        }
+
+        let matches = self
+            .triggers
+            .lock()
+            .unwrap()
+            .feed(&triggers, data, Instant::now());
+        for found in matches {
+            self.run_trigger(found);
+        }
+    }
+
+    fn run_trigger(&self, found: TriggerMatch) {
+        let TriggerMatch {
+            rule,
+            matched,
+            line,
+        } = found;
+
+        if rule.notify {
+            if let Err(e) = self.sink.notify(&rule.name, &line) {
+                eprintln!("Failed to show trigger notification: {}", e);
+            }
+        }
+
+        if let Some(respond) = rule.respond {
+            self.sink.respond(self.session_id.clone(), respond);
+        }
+
+        let event = TriggerMatchedEvent {
+            session_id: self.session_id.clone(),
+            trigger_id: rule.id,
+            name: rule.name,
+            matched,
+            line,
+            mark_tab: rule.mark_tab,
+        };
+        if let Err(e) = self.sink.emit_event("trigger-matched", event) {
+            eprintln!("Failed to emit trigger-matched event: {}", e);
+        }
+    }
+
+    /// 입력 처리 (녹화, 입력 감사)
+    pub fn push_input(&self, data: &str) {
+        self.recording.input(data);
+        if let Some(audit) = self.audit.lock().unwrap().as_mut() {
+            audit.input(data);
+        }
+    }
+
+    /// 붙여넣기 입력 준비 (원격 프로그램이 bracketed paste 모드를 켰으면 시퀀스로 감쌈)
+    pub fn prepare_paste(&self, data: Vec<u8>) -> Vec<u8> {
+        if self.screen.lock().unwrap().bracketed_paste() {
+            bracket_paste(&data)
+        } else {
+            data
+        }
+    }
+
+    /// 입력 감사 시작
+    pub fn start_input_audit(&self, auditor: InputAuditor) {
+        *self.audit.lock().unwrap() = Some(auditor);
+    }
+
+    /// 리사이즈 처리 (화면 모델, 녹화)
+    pub fn push_resize(&self, cols: u16, rows: u16) {
+        self.screen.lock().unwrap().resize(cols, rows);
+        self.recording.resize(cols, rows);
+    }
+
+    /// 스크롤백 재생 (from_offset 이후의 출력)
+    pub fn replay(&self, from_offset: u64) -> ScrollbackReplay {
+        self.scrollback.lock().unwrap().replay(from_offset)
+    }
+
+    /// 스크롤백 검색 (패턴이 잘못되면 에러 메시지 반환)
+    pub fn search(&self, pattern: &str, options: &SearchOptions) -> Result<SearchResult, String> {
+        let matcher = build_matcher(pattern, options).map_err(|e| e.to_string())?;
+        Ok(self.scrollback.lock().unwrap().search(&matcher, options))
+    }
+
+    /// 현재 화면 텍스트
+    pub fn screen_text(&self) -> String {
+        self.screen.lock().unwrap().text()
+    }
+
+    /// 현재 화면 셀 스냅샷 (속성, 커서 포함)
+    pub fn screen_snapshot(&self) -> ScreenSnapshot {
+        self.screen.lock().unwrap().snapshot()
+    }
+
+    /// 셸 통합 상태 (작업 디렉토리, 실행 중인 명령, 마지막 종료 코드)
+    pub fn shell_state(&self) -> ShellState {
+        self.shell.lock().unwrap().state().clone()
+    }
+
+    /// 명령 히스토리 (오래된 순)
+    pub fn command_history(&self) -> Vec<CommandHistoryEntry> {
+        self.history.lock().unwrap().entries()
+    }
+
+    /// 녹화 상태
+    pub fn recording(&self) -> &RecordingSlot {
+        &self.recording
+    }
+
+    /// 로그 상태
+    pub fn logging(&self) -> &LogSlot {
+        &self.logging
+    }
+}
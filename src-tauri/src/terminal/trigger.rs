@@ -0,0 +1,210 @@
+use super::ansi::AnsiStripper;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// 한 줄로 취급할 최대 길이 (개행 없는 출력이 계속되면 여기서 줄을 나눔)
+const MAX_LINE_LEN: usize = 4096;
+
+/// 출력 트리거 규칙 (설정에 저장)
+///
+/// 모든 세션의 출력을 ANSI 제거 후 줄 단위로 검사하며, 개행 전의 프롬프트 같은 미완성 줄도 검사합니다.
+/// 한 줄에서 같은 규칙은 한 번만 발동합니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerRule {
+    pub id: String,
+    pub name: String,
+    /// 정규식 패턴
+    pub pattern: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 데스크톱 알림 표시
+    #[serde(default)]
+    pub notify: bool,
+    /// 매칭 시 세션에 보낼 입력 (`\r` 등 제어 문자 포함 가능)
+    #[serde(default)]
+    pub respond: Option<String>,
+    /// 탭에 표시 (프론트엔드가 trigger-matched 이벤트를 보고 처리)
+    #[serde(default)]
+    pub mark_tab: bool,
+    /// 같은 세션에서 다시 발동하기까지의 최소 간격 (자동 응답 반복 방지)
+    #[serde(default = "default_cooldown_ms")]
+    pub cooldown_ms: u64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_cooldown_ms() -> u64 {
+    1000
+}
+
+/// trigger-matched 이벤트 페이로드
+#[derive(Debug, Clone, Serialize)]
+pub struct TriggerMatchedEvent {
+    pub session_id: String,
+    pub trigger_id: String,
+    pub name: String,
+    /// 매칭된 텍스트
+    pub matched: String,
+    /// 매칭된 줄 (ANSI 제거)
+    pub line: String,
+    pub mark_tab: bool,
+}
+
+/// 검증된 트리거 규칙 목록 (설정이 바뀔 때마다 새로 만듦)
+#[derive(Debug, Default)]
+pub struct TriggerSet {
+    rules: Vec<(TriggerRule, Regex)>,
+}
+
+impl TriggerSet {
+    /// 활성화된 규칙의 패턴 컴파일 (잘못된 패턴이 있으면 에러 메시지 반환)
+    pub fn compile(rules: &[TriggerRule]) -> Result<Self, String> {
+        let rules = rules
+            .iter()
+            .filter(|rule| rule.enabled)
+            .map(|rule| {
+                Regex::new(&rule.pattern)
+                    .map(|regex| (rule.clone(), regex))
+                    .map_err(|e| format!("Invalid trigger pattern '{}': {}", rule.pattern, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+/// 트리거 매칭 결과
+#[derive(Debug, Clone)]
+pub struct TriggerMatch {
+    pub rule: TriggerRule,
+    pub matched: String,
+    pub line: String,
+}
+
+/// 세션별 트리거 매칭 상태
+///
+/// 청크 경계에서 잘린 줄과 이스케이프 시퀀스를 이어 붙이고, 규칙별 발동 시각을 기억합니다.
+#[derive(Default)]
+pub struct TriggerMatcher {
+    stripper: AnsiStripper,
+    /// 아직 개행이 오지 않은 현재 줄
+    line: String,
+    /// 현재 줄에서 이미 발동한 규칙 ID
+    fired: Vec<String>,
+    /// 규칙별 마지막 발동 시각
+    last_fired: HashMap<String, Instant>,
+}
+
+impl TriggerMatcher {
+    /// 출력 프레임을 검사해 발동한 트리거 반환
+    pub fn feed(&mut self, triggers: &TriggerSet, data: &[u8], now: Instant) -> Vec<TriggerMatch> {
+        let text = self.stripper.push(data);
+        let mut matches = Vec::new();
+
+        for segment in text.split_inclusive('\n') {
+            self.line.push_str(segment.trim_end_matches('\n'));
+            self.check(triggers, now, &mut matches);
+            if segment.ends_with('\n') || self.line.len() >= MAX_LINE_LEN {
+                self.line.clear();
+                self.fired.clear();
+            }
+        }
+
+        matches
+    }
+
+    fn check(&mut self, triggers: &TriggerSet, now: Instant, matches: &mut Vec<TriggerMatch>) {
+        for (rule, regex) in &triggers.rules {
+            if self.fired.contains(&rule.id) {
+                continue;
+            }
+            let Some(found) = regex.find(&self.line) else {
+                continue;
+            };
+
+            self.fired.push(rule.id.clone());
+            let cooldown = Duration::from_millis(rule.cooldown_ms);
+            if let Some(last) = self.last_fired.get(&rule.id) {
+                if now.duration_since(*last) < cooldown {
+                    continue;
+                }
+            }
+            self.last_fired.insert(rule.id.clone(), now);
+
+            matches.push(TriggerMatch {
+                rule: rule.clone(),
+                matched: found.as_str().to_string(),
+                line: self.line.clone(),
+            });
+        }
+    }
+}
+
+// ============================================
+// 단위 테스트
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(id: &str, pattern: &str, cooldown_ms: u64) -> TriggerRule {
+        TriggerRule {
+            id: id.to_string(),
+            name: id.to_string(),
+            pattern: pattern.to_string(),
+            enabled: true,
+            notify: false,
+            respond: None,
+            mark_tab: false,
+            cooldown_ms,
+        }
+    }
+
+    #[test]
+    fn test_matches_across_chunks_once_per_line() {
+        let triggers = TriggerSet::compile(&[rule("oom", "OutOfMemoryError", 0)]).unwrap();
+        let mut matcher = TriggerMatcher::default();
+        let now = Instant::now();
+
+        assert!(matcher
+            .feed(&triggers, b"java.lang.OutOfMem", now)
+            .is_empty());
+        let matches = matcher.feed(&triggers, b"\x1b[31moryError\x1b[0m: heap", now);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].matched, "OutOfMemoryError");
+
+        // 같은 줄에서 추가 출력이 와도 다시 발동하지 않음
+        assert!(matcher.feed(&triggers, b" space\r\n", now).is_empty());
+        assert_eq!(matcher.feed(&triggers, b"OutOfMemoryError\n", now).len(), 1);
+    }
+
+    #[test]
+    fn test_cooldown_and_prompt_match() {
+        let triggers = TriggerSet::compile(&[rule("pw", "[Pp]assword:", 1000)]).unwrap();
+        let mut matcher = TriggerMatcher::default();
+        let now = Instant::now();
+
+        assert_eq!(matcher.feed(&triggers, b"Password: ", now).len(), 1);
+        assert!(matcher.feed(&triggers, b"\nPassword: ", now).is_empty());
+        let later = now + Duration::from_secs(2);
+        assert_eq!(matcher.feed(&triggers, b"\npassword: ", later).len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_and_disabled_rules() {
+        assert!(TriggerSet::compile(&[rule("bad", "(", 0)]).is_err());
+
+        let mut disabled = rule("bad", "(", 0);
+        disabled.enabled = false;
+        assert!(TriggerSet::compile(&[disabled]).unwrap().is_empty());
+    }
+}
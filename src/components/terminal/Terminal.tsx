@@ -18,6 +18,7 @@ import {
   type SshOutputEvent,
   toBackendSshConfig,
 } from '@/types/ssh';
+import type { SessionLogOptions } from '@/types/terminal';
 import '@xterm/xterm/css/xterm.css';
 
 interface TerminalProps {
@@ -64,6 +65,8 @@ export function Terminal({
   const [resolvedConfig, setResolvedConfig] = useState<ConnectionConfig | null>(null);
   const [isResolvingCredentials, setIsResolvingCredentials] = useState(false);
   const resolvedConfigRef = useRef<ConnectionConfig | null>(null);
+  // Profile session logging options (undefined: use the global setting)
+  const profileLoggingRef = useRef<SessionLogOptions | undefined>(undefined);
 
   // Track if SSH connection is using PTY (for interactive auth without credentials)
   const [useSshViaPty, setUseSshViaPty] = useState(false);
@@ -108,6 +111,7 @@ export function Terminal({
           }
 
           let config: ConnectionConfig = profile.config;
+          profileLoggingRef.current = profile.logging;
 
           // Restore credentials from keyring for SSH profiles
           if (profile.type === 'ssh' && isSSHConfig(profile.config)) {
@@ -398,7 +402,7 @@ export function Terminal({
     // Create session based on connection type
     if (isLocalConnection) {
       // Create PTY session
-      ptyHook.createPty(cols, rows);
+      ptyHook.createPty(cols, rows, { logging: profileLoggingRef.current });
     } else if (isSshConnection) {
       // Check if this is an IPC-created SSH session
       if (isIpcCreatedSsh) {
@@ -432,7 +436,7 @@ export function Terminal({
         // Use SSH library for direct connection with credentials
         setUseSshViaPty(false);
         const backendConfig = toBackendSshConfig(resolvedConfig);
-        sshHook.connect(backendConfig, cols, rows);
+        sshHook.connect(backendConfig, cols, rows, profileLoggingRef.current);
       } else {
         // Use PTY with ssh command for interactive authentication
         setUseSshViaPty(true);
@@ -441,7 +445,12 @@ export function Terminal({
           '-p',
           resolvedConfig.port.toString(),
         ];
-        ptyHook.createPty(cols, rows, { shell: 'ssh', args: sshArgs });
+        ptyHook.createPty(cols, rows, {
+          shell: 'ssh',
+          args: sshArgs,
+          logging: profileLoggingRef.current,
+          encoding: resolvedConfig.encoding,
+        });
       }
     }
 
@@ -1,7 +1,8 @@
-import { invoke } from '@tauri-apps/api/core';
+import { Channel, invoke } from '@tauri-apps/api/core';
 import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import { useCallback, useEffect, useRef, useState } from 'react';
-import type { CreatePtyResponse, PtyExitEvent, PtyOutputEvent } from '@/types/pty';
+import type { CreatePtyResponse, PtyExitEvent } from '@/types/pty';
+import type { SessionLogOptions } from '@/types/terminal';
 
 interface UsePtyOptions {
   onOutput?: (data: string) => void;
@@ -13,6 +14,9 @@ interface CreatePtyOptions {
   args?: string[];
   cwd?: string;
   env?: Record<string, string>;
+  logging?: SessionLogOptions;
+  profile?: string; // Launch profile name (Settings.launchProfiles)
+  encoding?: string; // Character encoding, e.g. 'euc-kr' (set_session_encoding changes it later)
 }
 
 interface UsePtyReturn {
@@ -21,6 +25,7 @@ interface UsePtyReturn {
   error: string | null;
   createPty: (cols: number, rows: number, options?: CreatePtyOptions) => Promise<void>;
   writeToPty: (data: string) => Promise<void>;
+  writeBytesToPty: (data: Uint8Array, paste?: boolean) => Promise<void>;
   resizePty: (cols: number, rows: number) => Promise<void>;
   closePty: () => Promise<void>;
 }
@@ -62,30 +67,43 @@ export function usePty(options: UsePtyOptions = {}): UsePtyReturn {
       try {
         setError(null);
 
+        // Output channel: raw bytes, the n-th message has seq n
+        const decoder = new TextDecoder();
+        const onOutput = new Channel<ArrayBuffer>();
+        let nextSeq = 0;
+        onOutput.onmessage = (data) => {
+          const seq = nextSeq++;
+          if (onOutputRef.current) {
+            onOutputRef.current(decoder.decode(data, { stream: true }));
+          }
+          // Acknowledge output so the backend can apply backpressure
+          if (ptyIdRef.current) {
+            invoke('ack_output', { sessionId: ptyIdRef.current, seq }).catch(() => {});
+          }
+        };
+
         // Call Tauri command to create PTY
         const response = await invoke<CreatePtyResponse>('create_pty', {
           shell: createOptions?.shell || null, // Use default shell if not provided
           args: createOptions?.args || null, // Command arguments (for ssh, etc.)
           cwd: createOptions?.cwd || null, // Use default working directory
           env: createOptions?.env || null, // Use default environment
+          logging: createOptions?.logging || null, // Use global session logging setting
+          profile: createOptions?.profile || null, // No launch profile
+          encoding: createOptions?.encoding || null, // UTF-8
           cols,
           rows,
+          onOutput,
         });
 
         setPtyId(response.pty_id);
         ptyIdRef.current = response.pty_id;
         setIsConnected(true);
 
-        // Set up output event listener
-        const outputUnlisten = await listen<PtyOutputEvent>(
-          `pty-output-${response.pty_id}`,
-          (event) => {
-            if (onOutputRef.current) {
-              onOutputRef.current(event.payload.data);
-            }
-          }
-        );
-        outputUnlistenRef.current = outputUnlisten;
+        // Detach the output channel on cleanup
+        outputUnlistenRef.current = () => {
+          onOutput.onmessage = () => {};
+        };
 
         // Set up exit event listener
         const exitUnlisten = await listen<PtyExitEvent>(`pty-exit-${response.pty_id}`, (event) => {
@@ -126,6 +144,28 @@ export function usePty(options: UsePtyOptions = {}): UsePtyReturn {
     }
   }, []); // No dependencies - uses ref instead
 
+  /**
+   * Write raw bytes to PTY (no encoding conversion, large data is written in chunks)
+   * With paste, the backend wraps data in bracketed paste sequences if the shell enabled mode 2004
+   */
+  const writeBytesToPty = useCallback(async (data: Uint8Array, paste = false) => {
+    if (!ptyIdRef.current) {
+      console.warn('Cannot write to PTY: not connected');
+      return;
+    }
+
+    try {
+      // Raw body avoids serializing the bytes as a JSON number array
+      await invoke('write_bytes_to_pty', data, {
+        headers: { 'pty-id': ptyIdRef.current, paste: String(paste) },
+      });
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      setError(errorMessage);
+      console.error('Failed to write bytes to PTY:', err);
+    }
+  }, []); // No dependencies - uses ref instead
+
   /**
    * Resize PTY session
    */
@@ -199,6 +239,7 @@ export function usePty(options: UsePtyOptions = {}): UsePtyReturn {
     error,
     createPty,
     writeToPty,
+    writeBytesToPty,
     resizePty,
     closePty,
   };
@@ -1,4 +1,4 @@
-import { invoke } from '@tauri-apps/api/core';
+import { Channel, invoke } from '@tauri-apps/api/core';
 import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import { useCallback, useEffect, useRef, useState } from 'react';
 import type {
@@ -6,8 +6,8 @@ import type {
   SshConfig,
   SshConnectionState,
   SshExitEvent,
-  SshOutputEvent,
 } from '@/types/ssh';
+import type { SessionLogOptions } from '@/types/terminal';
 
 interface UseSshOptions {
   onOutput?: (data: string) => void;
@@ -19,8 +19,14 @@ interface UseSshReturn {
   sessionId: string | null;
   status: SshConnectionState;
   error: string | null;
-  connect: (config: SshConfig, cols: number, rows: number) => Promise<void>;
+  connect: (
+    config: SshConfig,
+    cols: number,
+    rows: number,
+    logging?: SessionLogOptions
+  ) => Promise<void>;
   sendInput: (data: string) => Promise<void>;
+  sendBytes: (data: Uint8Array, paste?: boolean) => Promise<void>;
   resize: (cols: number, rows: number) => Promise<void>;
   disconnect: () => Promise<void>;
 }
@@ -59,55 +65,69 @@ export function useSsh(options: UseSshOptions = {}): UseSshReturn {
   /**
    * Create a new SSH session and connect
    */
-  const connect = useCallback(async (config: SshConfig, cols: number, rows: number) => {
-    try {
-      setError(null);
-      setStatus('connecting');
-      onStateChangeRef.current?.('connecting');
-
-      // Call Tauri command to create SSH session
-      const response = await invoke<CreateSshResponse>('create_ssh_session', {
-        config,
-        cols,
-        rows,
-      });
+  const connect = useCallback(
+    async (config: SshConfig, cols: number, rows: number, logging?: SessionLogOptions) => {
+      try {
+        setError(null);
+        setStatus('connecting');
+        onStateChangeRef.current?.('connecting');
 
-      setSessionId(response.session_id);
-      sessionIdRef.current = response.session_id;
-      setStatus('connected');
-      onStateChangeRef.current?.('connected');
-
-      // Set up output event listener
-      const outputUnlisten = await listen<SshOutputEvent>(
-        `ssh://output/${response.session_id}`,
-        (event) => {
+        // Output channel: raw bytes, the n-th message has seq n
+        const decoder = new TextDecoder();
+        const onOutput = new Channel<ArrayBuffer>();
+        let nextSeq = 0;
+        onOutput.onmessage = (data) => {
+          const seq = nextSeq++;
           if (onOutputRef.current) {
-            onOutputRef.current(event.payload.data);
+            onOutputRef.current(decoder.decode(data, { stream: true }));
           }
-        }
-      );
-      outputUnlistenRef.current = outputUnlisten;
-
-      // Set up exit event listener
-      const exitUnlisten = await listen<SshExitEvent>(
-        `ssh://exit/${response.session_id}`,
-        (event) => {
-          setStatus('disconnected');
-          onStateChangeRef.current?.('disconnected');
-          if (onExitRef.current) {
-            onExitRef.current(event.payload.reason);
+          // Acknowledge output so the backend can apply backpressure
+          if (sessionIdRef.current) {
+            invoke('ack_output', { sessionId: sessionIdRef.current, seq }).catch(() => {});
           }
-        }
-      );
-      exitUnlistenRef.current = exitUnlisten;
-    } catch (err) {
-      const errorMessage = err instanceof Error ? err.message : String(err);
-      setError(errorMessage);
-      setStatus('failed');
-      onStateChangeRef.current?.('failed');
-      console.error('Failed to create SSH session:', err);
-    }
-  }, []); // No dependencies - uses refs instead
+        };
+
+        // Call Tauri command to create SSH session
+        const response = await invoke<CreateSshResponse>('create_ssh_session', {
+          config,
+          cols,
+          rows,
+          onOutput,
+          logging: logging || null, // Use global session logging setting
+        });
+
+        setSessionId(response.session_id);
+        sessionIdRef.current = response.session_id;
+        setStatus('connected');
+        onStateChangeRef.current?.('connected');
+
+        // Detach the output channel on cleanup
+        outputUnlistenRef.current = () => {
+          onOutput.onmessage = () => {};
+        };
+
+        // Set up exit event listener
+        const exitUnlisten = await listen<SshExitEvent>(
+          `ssh://exit/${response.session_id}`,
+          (event) => {
+            setStatus('disconnected');
+            onStateChangeRef.current?.('disconnected');
+            if (onExitRef.current) {
+              onExitRef.current(event.payload.reason);
+            }
+          }
+        );
+        exitUnlistenRef.current = exitUnlisten;
+      } catch (err) {
+        const errorMessage = err instanceof Error ? err.message : String(err);
+        setError(errorMessage);
+        setStatus('failed');
+        onStateChangeRef.current?.('failed');
+        console.error('Failed to create SSH session:', err);
+      }
+    },
+    [] // No dependencies - uses refs instead
+  );
 
   /**
    * Send user input to SSH session
@@ -132,6 +152,30 @@ export function useSsh(options: UseSshOptions = {}): UseSshReturn {
     }
   }, []); // No dependencies - uses ref instead
 
+  /**
+   * Send raw bytes to SSH session (no encoding conversion, large data is sent in chunks)
+   * With paste, the backend wraps data in bracketed paste sequences if the remote enabled mode 2004
+   */
+  const sendBytes = useCallback(async (data: Uint8Array, paste = false) => {
+    if (!sessionIdRef.current) {
+      console.warn('Cannot send input: not connected');
+      return;
+    }
+
+    try {
+      // Raw body avoids serializing the bytes as a JSON number array
+      await invoke('write_bytes_to_ssh', data, {
+        headers: { 'session-id': sessionIdRef.current, paste: String(paste) },
+      });
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      setError(errorMessage);
+      setStatus('error');
+      onStateChangeRef.current?.('error');
+      console.error('Failed to write bytes to SSH:', err);
+    }
+  }, []); // No dependencies - uses ref instead
+
   /**
    * Resize SSH session terminal
    */
@@ -206,6 +250,7 @@ export function useSsh(options: UseSshOptions = {}): UseSshReturn {
     error,
     connect,
     sendInput,
+    sendBytes,
     resize,
     disconnect,
   };
@@ -5,6 +5,8 @@
  * Supports local, SSH, and SFTP connections
  */
 
+import type { SessionLogOptions } from './terminal';
+
 // Connection type enum
 export type ConnectionType = 'local' | 'ssh' | 'sftp';
 
@@ -23,6 +25,7 @@ export interface SSHConfig {
   password?: string; // Password authentication (optional)
   privateKey?: string; // Private key path or content (optional)
   passphrase?: string; // Passphrase for private key (optional)
+  encoding?: string; // Server character encoding, e.g. 'euc-kr' (default: UTF-8)
 }
 
 // SFTP connection configuration
@@ -71,6 +74,7 @@ export interface ConnectionProfile {
   config: ConnectionConfig; // Type-specific configuration
   savedAuthType?: 'password' | 'privateKey' | 'passphrase' | 'interactive'; // Auth type saved in keyring (for UI display)
   tags?: string[]; // Tags for search/categorization (optional)
+  logging?: SessionLogOptions; // Log sessions opened from this profile (overrides the global setting)
   createdAt: number; // Creation timestamp
 }
 
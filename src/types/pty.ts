@@ -7,14 +7,59 @@ export interface CreatePtyResponse {
   shell: string; // Actual shell path executed
 }
 
+// Installed shell (list_available_shells command, from /etc/shells and PATH)
+export interface ShellInfo {
+  name: string; // e.g. "zsh"
+  path: string;
+  version: string | null; // Only probed for bash, zsh, fish, nu and pwsh
+  is_default: boolean; // Shell used when create_pty gets no shell
+}
+
 // PTY output event payload
 export interface PtyOutputEvent {
   pty_id: string;
   data: string; // Terminal output (with ANSI escape sequences)
+  seq: number; // Output sequence number (increments by 1, use to detect gaps)
+}
+
+// PTY output mode (create_pty `outputMode` parameter)
+// - text: UTF-8 decoded string (default)
+// - raw: raw bytes, nothing the child writes is discarded
+export type PtyOutputMode = 'text' | 'raw';
+
+// PTY raw output event payload (outputMode: 'raw')
+export interface PtyRawOutputEvent {
+  pty_id: string;
+  data: number[]; // Raw bytes (use new Uint8Array(data))
+  seq: number; // Output sequence number
+}
+
+// PTY foreground process info (get_pty_process_info command)
+// name, cmdline and cwd are read from /proc and only available on Linux
+export interface PtyProcessInfo {
+  pty_id: string;
+  shell_pid: number;
+  foreground_pgid: number | null; // Foreground process group (tcgetpgrp)
+  name: string | null; // e.g. "vim"
+  cmdline: string[]; // e.g. ["vim", "main.rs"]
+  cwd: string | null; // Falls back to the shell's cwd
+  running_job: boolean; // A job other than the shell is in the foreground
+}
+
+// PTY process tree resource usage (get_pty_resource_usage command)
+// Global `pty-resource-usage` event sends PtyResourceUsage[] for all PTYs (set_pty_resource_interval)
+// Totals for the shell and all descendants, read from /proc (Linux only)
+export interface PtyResourceUsage {
+  pty_id: string;
+  cpu_percent: number | null; // Since the previous poll, or the previous event for `pty-resource-usage` (100 = one core), null on the first sample
+  rss_bytes: number;
+  child_processes: number; // Descendants of the shell
+  sampled_at: number; // Unix epoch milliseconds
 }
 
 // PTY exit event payload
 export interface PtyExitEvent {
   pty_id: string;
-  exit_code: number; // Exit code (0 = success)
+  exit_code: number | null; // Exit code (0 = success), null if killed by a signal or unknown
+  signal: string | null; // Signal name when killed (e.g. "SIGKILL")
 }
@@ -5,12 +5,78 @@
  * Keep them in sync when making changes!
  */
 
+import type { SessionLogOptions } from './terminal';
+
 export interface Settings {
   version: string;
   appTheme: 'dark' | 'light';
   fontSize: number;
   fontFamily: string;
   terminalThemeId: string;
+  scrollbackLimit: ScrollbackLimit;
+  triggers: TriggerRule[];
+  highlights: HighlightRule[];
+  sessionLogging: SessionLogSettings;
+  auditSshInput: boolean; // Record SSH input lines per host (see InputAuditRecord)
+  launchProfiles: LaunchProfile[];
+}
+
+/**
+ * Backend scrollback retention limit per session
+ * Matches: Rust ScrollbackLimit (serde tag = "type", content = "value")
+ */
+export type ScrollbackLimit = { type: 'bytes'; value: number } | { type: 'lines'; value: number };
+
+/**
+ * Output trigger rule, matched against every session's output (ANSI stripped, line by line)
+ * Matches: Rust TriggerRule
+ */
+export interface TriggerRule {
+  id: string;
+  name: string;
+  pattern: string; // Regular expression
+  enabled: boolean;
+  notify: boolean; // Show a desktop notification
+  respond?: string | null; // Input sent to the session on match
+  markTab: boolean; // Mark the tab when the trigger-matched event arrives
+  cooldownMs: number; // Minimum interval between firings per session
+}
+
+/**
+ * Output highlight rule, applied by the backend before output is emitted
+ * Only text with default attributes is coloured, and never on the alternate screen
+ * Matches: Rust HighlightRule
+ */
+export interface HighlightRule {
+  pattern: string; // Regular expression
+  sgr: string; // SGR parameters, e.g. "1;31" or "38;5;208"
+  enabled: boolean;
+}
+
+/**
+ * Session logging applied automatically to new sessions
+ * A profile's own logging options take precedence
+ * Matches: Rust SessionLogSettings
+ */
+export interface SessionLogSettings {
+  ssh: boolean; // Log every SSH session
+  local: boolean; // Log every local PTY session
+  options: SessionLogOptions;
+}
+
+/**
+ * Local tab launch profile, referenced by name from create_pty (`profile`) and IPC add_local_tab
+ * Explicitly passed shell, args and cwd take precedence; env is merged over the profile's
+ * Matches: Rust LaunchProfile
+ */
+export interface LaunchProfile {
+  name: string;
+  shell?: string | null; // Default shell if omitted (see list_available_shells)
+  args?: string[];
+  env?: Record<string, string>;
+  cwd?: string | null;
+  loginShell?: boolean; // Prepends "-l" to args (ignored on Windows)
+  encoding?: string | null; // Character encoding, e.g. 'euc-kr' (default: UTF-8)
 }
 
 export interface TerminalTheme {
@@ -47,4 +113,10 @@ export const defaultSettings: Settings = {
   fontSize: 14,
   fontFamily: 'Cascadia Code, Consolas, Monaco, monospace',
   terminalThemeId: 'retro',
+  scrollbackLimit: { type: 'lines', value: 10000 },
+  triggers: [],
+  highlights: [],
+  sessionLogging: { ssh: false, local: false, options: {} },
+  auditSshInput: false,
+  launchProfiles: [],
 };
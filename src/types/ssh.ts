@@ -26,6 +26,7 @@ export interface SshConfig {
   port: number;
   username: string;
   authMethod?: AuthMethod; // Optional - allows keyboard-interactive auth
+  encoding?: string; // Server character encoding, e.g. 'euc-kr', 'shift_jis' (default: UTF-8)
 }
 
 /**
@@ -64,6 +65,7 @@ export type SshConnectionState =
 export interface SshOutputEvent {
   session_id: string;
   data: string;
+  seq: number; // Output sequence number (increments by 1, use to detect gaps)
 }
 
 /**
@@ -108,5 +110,6 @@ export function toBackendSshConfig(uiConfig: import('./connection').SSHConfig):
     port: uiConfig.port,
     username: uiConfig.username,
     authMethod,
+    encoding: uiConfig.encoding,
   };
 }
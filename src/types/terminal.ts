@@ -46,3 +46,319 @@ export interface TerminalMetadata {
   createdAt: number; // Creation time (timestamp)
   pid?: number; // Process ID (from backend)
 }
+
+// Backend scrollback replay response (replay_session command, PTY/SSH)
+export interface ScrollbackReplay {
+  data: string; // Output from start_offset to end_offset
+  start_offset: number; // Stream offset of the first returned byte
+  end_offset: number; // Stream offset after the last returned byte
+  next_seq: number; // Output events with seq < next_seq are already included in data
+  truncated: boolean; // True if the requested offset was already evicted
+}
+
+// Session recording info (start_recording / stop_recording commands, asciicast v2)
+export interface RecordingInfo {
+  session_id: string;
+  path: string; // .cast file path
+}
+
+// Session logging (start_session_log / stop_session_log commands, `logging` option of create_pty / create_ssh_session)
+export interface SessionLogOptions {
+  format?: 'plain' | 'raw'; // plain: ANSI sequences stripped (default), raw: output as received
+  timestamps?: boolean; // Prefix every line with a UTC timestamp
+  fileName?: string; // Template with {host}, {user}, {date}, {time}, {session} (default: "{user}@{host}_{date}_{time}.log")
+  directory?: string | null; // Default: <config dir>/rusterm/logs
+  maxFileBytes?: number; // Rotate when a file exceeds this size (default: 10 MiB, 0: never)
+  maxFiles?: number; // Files kept including the current one (default: 5)
+}
+
+export interface SessionLogInfo {
+  session_id: string;
+  path: string; // Current log file path
+}
+
+// Recording playback session (create_playback command)
+// Pass an `onOutput` Channel<ArrayBuffer> like create_pty; exit is emitted as `pty-exit-{playback_id}`
+export interface CreatePlaybackResponse {
+  playback_id: string;
+  width: number;
+  height: number;
+  duration: number; // Seconds (after idle time capping)
+  title: string | null;
+}
+
+// Playback status (get_playback_status command)
+export interface PlaybackStatus {
+  playback_id: string;
+  position: number; // Current position in seconds
+  duration: number;
+  playing: boolean;
+  speed: number; // Speed multiplier
+  finished: boolean;
+}
+
+// Backend screen model (get_screen_cells command)
+export type CellColor =
+  | { type: 'default' }
+  | { type: 'indexed'; value: number } // 256-color palette index
+  | { type: 'rgb'; value: [number, number, number] };
+
+export interface ScreenCell {
+  text: string; // Empty for the second half of a wide character
+  width: number; // 0: wide spacer, 1: normal, 2: wide character
+  fg?: CellColor; // Omitted when default
+  bg?: CellColor;
+  bold?: boolean; // Attribute flags are omitted when false
+  dim?: boolean;
+  italic?: boolean;
+  underline?: boolean;
+  blink?: boolean;
+  inverse?: boolean;
+  hidden?: boolean;
+  strikethrough?: boolean;
+}
+
+export interface ScreenSnapshot {
+  cols: number;
+  rows: number;
+  cursor: { row: number; col: number; visible: boolean }; // 0-based
+  alternate_screen: boolean;
+  lines: ScreenCell[][];
+}
+
+// Scrollback search (search_session command)
+export interface SearchOptions {
+  regex?: boolean; // Treat pattern as a regular expression (default: plain text)
+  caseSensitive?: boolean; // Default: false
+  contextLines?: number; // Lines of context around each match (default: 2)
+  maxResults?: number; // Default: 1000
+}
+
+export interface SearchMatch {
+  line: number; // Line number since session start (0-based)
+  offset: number; // Stream offset of the line start (usable as replay_session from_offset)
+  column: number; // Match start within the line (characters, ANSI stripped)
+  text: string;
+  line_text: string;
+  context_before: string[];
+  context_after: string[];
+}
+
+export interface SearchResult {
+  matches: SearchMatch[];
+  truncated: boolean; // True if maxResults was reached
+}
+
+// Shell integration (OSC 133 / OSC 7)
+export type ShellKind = 'bash' | 'zsh' | 'fish';
+
+export interface RunningCommand {
+  command: string | null; // Command line reported by the shell (if available)
+  cwd: string | null;
+  started_at: number; // Unix epoch milliseconds
+  output_start: number; // Stream offset where command output starts
+}
+
+export interface FinishedCommand {
+  command: string | null;
+  cwd: string | null;
+  started_at: number;
+  duration_ms: number;
+  exit_code: number | null;
+  output_start: number; // Output range [output_start, output_end) in stream offsets
+  output_end: number;
+}
+
+export interface ShellState {
+  integration_active: boolean; // True once any shell integration marker was received
+  cwd: string | null;
+  host: string | null;
+  running: RunningCommand | null;
+  last_exit_code: number | null;
+}
+
+// Global `command-started` / `command-finished` event payloads
+export interface CommandStartedEvent extends RunningCommand {
+  session_id: string;
+}
+
+export interface CommandFinishedEvent extends FinishedCommand {
+  session_id: string;
+}
+
+// Command history (list_command_history / list_host_command_history commands)
+export interface CommandHistoryEntry {
+  index: number; // Order within the session (0-based)
+  session_id: string;
+  host: string; // "localhost" for local PTY sessions
+  command: string | null;
+  cwd: string | null;
+  started_at: number; // Unix epoch milliseconds
+  duration_ms: number;
+  exit_code: number | null;
+  output_start: number; // Output range [output_start, output_end) in stream offsets
+  output_end: number;
+}
+
+// Input audit log (Settings.auditSshInput, export_input_audit command exports one host as JSON Lines)
+export interface InputAuditRecord {
+  time: number; // Unix epoch milliseconds when the line was entered
+  session_id: string;
+  host: string;
+  user: string;
+  line: string | null; // Null when redacted
+  redacted: boolean; // Not echoed back within 5 s, or typed after a password prompt
+  cancelled: boolean; // Ended with Ctrl+C instead of Enter
+}
+
+// Broadcast input groups (synchronized typing)
+export interface BroadcastMember {
+  session_id: string; // PTY or SSH session id
+  paused: boolean; // Paused members stay in the group but receive no input
+}
+
+export interface BroadcastGroupInfo {
+  group_id: string;
+  name: string | null;
+  members: BroadcastMember[];
+}
+
+export interface BroadcastWriteResult {
+  written: string[];
+  skipped: string[]; // Paused members
+  failed: { session_id: string; error: string }[]; // Includes members that took over 5 s to accept input (still delivered later)
+}
+
+// Session host (background process that keeps PTY and SSH sessions alive across app restarts)
+// The host registers on the app's IPC socket; hosted SSH sessions also work with the regular SSH commands
+export interface HostStatus {
+  version: string;
+  pid: number;
+  sessions: number;
+}
+
+export type HostSessionKind = 'pty' | 'ssh';
+
+export interface HostSessionInfo {
+  session_id: string;
+  kind: HostSessionKind;
+  shell: string; // Shell path, or "user@host" for SSH sessions
+  pid: number | null; // null for SSH sessions
+  cols: number;
+  rows: number;
+  created_at: number; // Unix epoch milliseconds
+  attached: number; // Number of attached links
+  exited: boolean; // Exited sessions keep their output until killed
+  exit_code: number | null;
+  signal: string | null;
+  reason: string | null; // SSH disconnect reason
+}
+
+export interface HostAttachResponse {
+  session_id: string;
+  kind: HostSessionKind;
+  data: string; // Buffered raw output after from_offset (base64, same bytes as onOutput frames)
+  start_offset: number;
+  end_offset: number; // Pass as from_offset when re-attaching
+  next_seq: number; // seq of the first onOutput frame (for ack_output)
+  truncated: boolean;
+}
+
+// Global `host-detached` event payload (output forwarding stopped before the session exited)
+export interface HostDetachedEvent {
+  session_id: string;
+}
+
+// Expect-style automation scripts (run_automation_script / IPC run_script)
+// Output is matched after ANSI sequences and carriage returns are stripped
+export interface ExpectAlternative {
+  pattern: string;
+  regex?: boolean; // Default: plain text
+  goto?: string; // Label to jump to when this alternative matches
+}
+
+export type AutomationStep =
+  | { type: 'label'; name: string }
+  | { type: 'send'; text: string }
+  | {
+      type: 'expect';
+      alternatives: ExpectAlternative[];
+      timeoutMs?: number;
+      onTimeout?: string; // Label to jump to on timeout (fails the script if omitted)
+    }
+  | {
+      type: 'capture';
+      name: string;
+      start: string; // Start marker
+      end: string; // End marker
+      regex?: boolean;
+      timeoutMs?: number;
+      onTimeout?: string;
+    }
+  | { type: 'goto'; label: string }
+  | { type: 'sleep'; ms: number }
+  | { type: 'fail'; message: string }
+  | { type: 'end' };
+
+export interface AutomationScript {
+  steps: AutomationStep[];
+  defaultTimeoutMs?: number; // Default: 10000
+  includeExistingOutput?: boolean; // Default: only output produced after the script starts
+}
+
+export type TranscriptEntry =
+  | { kind: 'sent'; step: number; text: string; elapsed_ms: number }
+  | {
+      kind: 'matched';
+      step: number;
+      alternative: number;
+      pattern: string;
+      matched: string;
+      output: string; // Output since the previous match, before this one
+      elapsed_ms: number;
+    }
+  | { kind: 'captured'; step: number; name: string; text: string; elapsed_ms: number }
+  | { kind: 'timed_out'; step: number; output: string; elapsed_ms: number }
+  | { kind: 'jumped'; step: number; label: string; elapsed_ms: number }
+  | { kind: 'failed'; step: number; message: string; elapsed_ms: number };
+
+export interface AutomationResult {
+  session_id: string;
+  success: boolean;
+  error: string | null;
+  captures: Record<string, string>;
+  transcript: TranscriptEntry[];
+  duration_ms: number;
+}
+
+// Global `trigger-matched` event payload (Settings.triggers)
+export interface TriggerMatchedEvent {
+  session_id: string;
+  trigger_id: string;
+  name: string;
+  matched: string;
+  line: string; // Matched line, ANSI stripped
+  mark_tab: boolean;
+}
+
+// Activity / silence monitoring (set_session_monitor / get_session_monitor commands)
+export interface MonitorConfig {
+  silenceMs?: number | null; // Emit `session-silence` after this long without output
+  activityMs?: number | null; // Emit `session-activity` when output resumes after this long quiet
+}
+
+// Global `session-activity` / `session-silence` / `session-bell` event payloads
+export interface SessionActivityEvent {
+  session_id: string;
+  idle_ms: number; // How long the session was quiet
+}
+
+export interface SessionSilenceEvent {
+  session_id: string;
+  silent_ms: number;
+}
+
+export interface SessionBellEvent {
+  session_id: string;
+  count: number; // BEL characters in one output frame
+}